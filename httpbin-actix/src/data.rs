@@ -1,8 +1,25 @@
 use actix_web::web::ServiceConfig;
 
+mod barcode;
 mod base64;
+mod codec;
+mod compress;
+mod fake;
+mod hmac;
+mod ids;
+mod random;
+mod time;
 mod uuid;
 
 pub fn api(cfg: &mut ServiceConfig) {
-    cfg.configure(base64::api).configure(uuid::api);
+    cfg.configure(barcode::api)
+        .configure(base64::api)
+        .configure(codec::api)
+        .configure(compress::api)
+        .configure(fake::api)
+        .configure(hmac::api)
+        .configure(ids::api)
+        .configure(random::api)
+        .configure(time::api)
+        .configure(uuid::api);
 }
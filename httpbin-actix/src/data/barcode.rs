@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{get, resource, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::barcode::{BarcodeFormat, QrEcc, Symbology};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Code {
+    data: String,
+    size: Option<usize>,
+    format: Option<BarcodeFormat>,
+}
+
+#[derive(Deserialize)]
+struct Ecc {
+    ecc: Option<QrEcc>,
+}
+
+#[derive(Debug)]
+struct BarcodeError(pub httpbin::data::barcode::BarcodeError);
+
+impl Display for BarcodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for BarcodeError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(resource("/qrcode").route(get().to(qrcode)))
+        .service(resource("/barcode/{symbology}").route(get().to(barcode)));
+}
+
+async fn qrcode(
+    Query(Code { data, size, format }): Query<Code>,
+    Query(Ecc { ecc }): Query<Ecc>,
+) -> Result<HttpResponse> {
+    let format = format.unwrap_or_default();
+
+    let data = httpbin::data::barcode::qrcode(&data, ecc, size, format).map_err(BarcodeError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .body(data))
+}
+
+async fn barcode(
+    symbology: Path<Symbology>,
+    Query(Code { data, size, format }): Query<Code>,
+) -> Result<HttpResponse> {
+    let format = format.unwrap_or_default();
+
+    let data = httpbin::data::barcode::barcode(&data, symbology.into_inner(), size, format)
+        .map_err(BarcodeError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .body(data))
+}
@@ -1,16 +1,26 @@
 use std::fmt::Display;
 
 use actix_web::{
-    web::{post, resource, scope, Bytes, Path, Query, ServiceConfig},
+    web::{get, post, resource, scope, Bytes, Json, Path, Payload, Query, ServiceConfig},
     HttpResponse, ResponseError, Result,
 };
-use httpbin::data::base64::Base64Engine;
+use futures_util::TryStreamExt;
+use httpbin::data::base64::{
+    Base64Engine, Base64EngineInfo, Base64Padding, Base64Wrap, Base64WrapMode,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
 struct Base64Config {
     pub alphabet: Option<String>,
     pub pad: Option<bool>,
+    pub padding: Option<Base64Padding>,
+}
+
+#[derive(Deserialize)]
+struct Wrap {
+    pub wrap: Option<Base64WrapMode>,
+    pub label: Option<String>,
 }
 
 #[derive(Debug)]
@@ -32,7 +42,10 @@ pub fn api(cfg: &mut ServiceConfig) {
     cfg.service(
         scope("/base64")
             .service(resource("/encode/{engine}").route(post().to(base64_encode)))
-            .service(resource("/decode/{engine}").route(post().to(base64_decode))),
+            .service(resource("/decode/{engine}").route(post().to(base64_decode)))
+            .service(resource("/stream/encode/{engine}").route(post().to(base64_encode_stream)))
+            .service(resource("/stream/decode/{engine}").route(post().to(base64_decode_stream)))
+            .service(resource("/engines").route(get().to(base64_engines))),
     );
 }
 
@@ -40,6 +53,7 @@ async fn base64_encode(
     data: Bytes,
     engine: Path<Base64Engine>,
     Query(config): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
 ) -> Result<HttpResponse> {
     let engine = engine.into_inner();
 
@@ -47,11 +61,21 @@ async fn base64_encode(
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: config.alphabet.unwrap_or_default(),
             pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
         }),
         _ => None,
     };
 
-    let encoded = httpbin::data::base64::encode(&data, engine, config).map_err(Base64Error)?;
+    let encoded = match wrap {
+        Some(wrap) => httpbin::data::base64::encode_wrapped(
+            &data,
+            engine,
+            config,
+            Base64Wrap::new(wrap, label),
+        ),
+        None => httpbin::data::base64::encode(&data, engine, config),
+    }
+    .map_err(Base64Error)?;
 
     Ok(HttpResponse::Ok().body(encoded))
 }
@@ -67,6 +91,7 @@ async fn base64_decode(
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: config.alphabet.unwrap_or_default(),
             pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
         }),
         _ => None,
     };
@@ -87,3 +112,57 @@ async fn base64_decode(
         }
     }
 }
+
+async fn base64_encode_stream(
+    data: Payload,
+    engine: Path<Base64Engine>,
+    Query(config): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
+) -> Result<HttpResponse> {
+    let engine = engine.into_inner();
+
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: config.alphabet.unwrap_or_default(),
+            pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
+        }),
+        _ => None,
+    };
+    let wrap = wrap.map(|wrap| Base64Wrap::new(wrap, label));
+
+    let encoded =
+        httpbin::data::base64::encode_stream(data, engine, config, wrap).map_err(Base64Error)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .streaming(encoded.map_ok(Bytes::from)))
+}
+
+async fn base64_decode_stream(
+    data: Payload,
+    engine: Path<Base64Engine>,
+    Query(config): Query<Base64Config>,
+) -> Result<HttpResponse> {
+    let engine = engine.into_inner();
+
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: config.alphabet.unwrap_or_default(),
+            pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
+        }),
+        _ => None,
+    };
+
+    let decoded =
+        httpbin::data::base64::decode_stream(data, engine, config).map_err(Base64Error)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/octet-stream")
+        .streaming(decoded.map_ok(Bytes::from)))
+}
+
+async fn base64_engines() -> Json<Vec<Base64EngineInfo>> {
+    Json(httpbin::data::base64::engines())
+}
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{post, resource, Bytes, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::codec::{Codec, CodecConfig, PercentSet};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Config {
+    set: Option<PercentSet>,
+}
+
+#[derive(Debug)]
+struct CodecError(pub httpbin::data::codec::CodecError);
+
+impl Display for CodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for CodecError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(resource("/encode/{codec}").route(post().to(codec_encode)))
+        .service(resource("/decode/{codec}").route(post().to(codec_decode)));
+}
+
+async fn codec_encode(
+    data: Bytes,
+    codec: Path<Codec>,
+    Query(Config { set }): Query<Config>,
+) -> Result<HttpResponse> {
+    let config = CodecConfig {
+        percent_set: set.unwrap_or_default(),
+    };
+
+    let encoded = httpbin::data::codec::encode(&data, codec.into_inner(), Some(config))
+        .map_err(CodecError)?;
+
+    Ok(HttpResponse::Ok().body(encoded))
+}
+
+async fn codec_decode(data: String, codec: Path<Codec>) -> Result<HttpResponse> {
+    let decoded = httpbin::data::codec::decode(&data, codec.into_inner()).map_err(CodecError)?;
+
+    match String::from_utf8(decoded.clone()) {
+        Ok(text) => Ok(HttpResponse::Ok().body(text)),
+        Err(_) => {
+            let kind = infer::get(&decoded);
+
+            Ok(HttpResponse::Ok()
+                .content_type(
+                    kind.map(|k| k.mime_type())
+                        .unwrap_or("application/octet-stream"),
+                )
+                .body(decoded))
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{post, resource, Bytes, Data, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::{cli::CompressConfig, data::compress::Compression};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Level {
+    level: Option<i32>,
+}
+
+#[derive(Debug)]
+struct CompressError(pub httpbin::data::compress::CompressError);
+
+impl Display for CompressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for CompressError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(resource("/compress/{algo}").route(post().to(compress)))
+        .service(resource("/decompress/{algo}").route(post().to(decompress)));
+}
+
+async fn compress(
+    data: Bytes,
+    algo: Path<Compression>,
+    Query(Level { level }): Query<Level>,
+) -> Result<HttpResponse> {
+    let algo = algo.into_inner();
+
+    let compressed =
+        httpbin::data::compress::compress(&data, algo, level).map_err(CompressError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(algo.content_type())
+        .body(compressed))
+}
+
+async fn decompress(
+    cfg: Data<CompressConfig>,
+    data: Bytes,
+    algo: Path<Compression>,
+) -> Result<HttpResponse> {
+    let decompressed = httpbin::data::compress::decompress(&data, algo.into_inner(), cfg.max_size)
+        .map_err(CompressError)?;
+
+    match String::from_utf8(decompressed.clone()) {
+        Ok(text) => Ok(HttpResponse::Ok().body(text)),
+        Err(_) => {
+            let kind = infer::get(&decompressed);
+
+            Ok(HttpResponse::Ok()
+                .content_type(
+                    kind.map(|k| k.mime_type())
+                        .unwrap_or("application/octet-stream"),
+                )
+                .body(decompressed))
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{get, post, resource, scope, Data, Json, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::{
+    cli::FakeConfig,
+    data::fake::{FakeKind, FakeLocale},
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct CountSeed {
+    count: Option<usize>,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Locale {
+    locale: Option<FakeLocale>,
+}
+
+#[derive(Debug)]
+struct FakeError(pub httpbin::data::fake::FakeError);
+
+impl Display for FakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for FakeError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    // `/schema` is registered first as `/{kind}` would also match it
+    cfg.service(
+        scope("/fake")
+            .service(resource("/schema").route(post().to(fake_schema)))
+            .service(resource("/{kind}").route(get().to(fake_records))),
+    );
+}
+
+async fn fake_schema(
+    cfg: Data<FakeConfig>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+    Json(schema): Json<Value>,
+) -> Result<Json<Value>, FakeError> {
+    let instances =
+        httpbin::data::fake::schema(&schema, count, seed, cfg.max_count).map_err(FakeError)?;
+
+    Ok(Json(instances))
+}
+
+async fn fake_records(
+    cfg: Data<FakeConfig>,
+    kind: Path<FakeKind>,
+    Query(Locale { locale }): Query<Locale>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+) -> Result<Json<Value>, FakeError> {
+    let records =
+        httpbin::data::fake::records(kind.into_inner(), locale, count, seed, cfg.max_count)
+            .map_err(FakeError)?;
+
+    Ok(Json(records))
+}
@@ -0,0 +1,84 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{post, resource, scope, Bytes, Json, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::{
+    base64::Base64Engine,
+    hmac::{HmacAlgorithm, HmacKeyEncoding, HmacMac, HmacMacEncoding, HmacVerification},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Key {
+    key: String,
+    key_encoding: Option<HmacKeyEncoding>,
+    engine: Option<Base64Engine>,
+}
+
+#[derive(Deserialize)]
+struct Mac {
+    mac: String,
+    mac_encoding: Option<HmacMacEncoding>,
+}
+
+#[derive(Debug)]
+struct HmacError(pub httpbin::data::hmac::HmacError);
+
+impl Display for HmacError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for HmacError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/hmac")
+            .service(resource("/{algorithm}/sign").route(post().to(hmac_sign)))
+            .service(resource("/{algorithm}/verify").route(post().to(hmac_verify))),
+    );
+}
+
+async fn hmac_sign(
+    data: Bytes,
+    algorithm: Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+) -> Result<Json<HmacMac>> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(HmacError)?;
+
+    let mac = httpbin::data::hmac::sign(algorithm.into_inner(), &key, &data);
+
+    Ok(Json(mac.as_slice().into()))
+}
+
+async fn hmac_verify(
+    data: Bytes,
+    algorithm: Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+    Query(Mac { mac, mac_encoding }): Query<Mac>,
+) -> Result<Json<HmacVerification>> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(HmacError)?;
+    let mac = httpbin::data::hmac::decode_mac(&mac, mac_encoding.unwrap_or_default())
+        .map_err(HmacError)?;
+
+    let verification = httpbin::data::hmac::verify(algorithm.into_inner(), &key, &data, &mac);
+
+    Ok(Json(verification))
+}
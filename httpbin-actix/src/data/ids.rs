@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{get, resource, scope, Json, Path, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::ids::{IdInspection, IdKind, IdOptions};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Id {
+    id: String,
+}
+
+#[derive(Debug)]
+struct IdError(pub httpbin::data::ids::IdError);
+
+impl Display for IdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for IdError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/id")
+            .service(resource("/{kind}").route(get().to(id_generate)))
+            .service(resource("/{kind}/parse").route(get().to(id_parse))),
+    );
+}
+
+async fn id_generate(
+    kind: Path<IdKind>,
+    Query(options): Query<IdOptions>,
+) -> Result<String, IdError> {
+    let id = httpbin::data::ids::generate(kind.into_inner(), options).map_err(IdError)?;
+
+    Ok(id)
+}
+
+async fn id_parse(
+    kind: Path<IdKind>,
+    Query(Id { id }): Query<Id>,
+    Query(options): Query<IdOptions>,
+) -> Result<Json<IdInspection>, IdError> {
+    let inspection = httpbin::data::ids::parse(kind.into_inner(), &id, options).map_err(IdError)?;
+
+    Ok(Json(inspection))
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{get, resource, scope, Json, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::random::{RandomCharset, RandomItems};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Seed {
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Range<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct Charset {
+    charset: Option<RandomCharset>,
+    length: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Items {
+    items: RandomItems,
+}
+
+#[derive(Debug)]
+struct RandomError(pub httpbin::data::random::RandomError);
+
+impl Display for RandomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for RandomError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/random")
+            .service(resource("/int").route(get().to(random_int)))
+            .service(resource("/float").route(get().to(random_float)))
+            .service(resource("/string").route(get().to(random_string)))
+            .service(resource("/choice").route(get().to(random_choice)))
+            .service(resource("/shuffle").route(get().to(random_shuffle))),
+    );
+}
+
+async fn random_int(
+    Query(Range { min, max }): Query<Range<i64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, RandomError> {
+    let int = httpbin::data::random::int(min, max, seed).map_err(RandomError)?;
+
+    Ok(int.to_string())
+}
+
+async fn random_float(
+    Query(Range { min, max }): Query<Range<f64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, RandomError> {
+    let float = httpbin::data::random::float(min, max, seed).map_err(RandomError)?;
+
+    Ok(float.to_string())
+}
+
+async fn random_string(
+    Query(Charset { charset, length }): Query<Charset>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, RandomError> {
+    let string = httpbin::data::random::string(charset, length, seed).map_err(RandomError)?;
+
+    Ok(string)
+}
+
+async fn random_choice(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, RandomError> {
+    let choice = httpbin::data::random::choice(items, seed).map_err(RandomError)?;
+
+    Ok(choice)
+}
+
+async fn random_shuffle(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Json<Vec<String>> {
+    Json(httpbin::data::random::shuffle(items, seed))
+}
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use actix_web::{
+    web::{get, resource, scope, Json, Query, ServiceConfig},
+    HttpResponse, ResponseError, Result,
+};
+use httpbin::data::time::{TimeFormat, TimeInspection};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Now {
+    format: Option<TimeFormat>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Parse {
+    time: String,
+    from: Option<String>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Format {
+    format: Option<TimeFormat>,
+}
+
+#[derive(Debug)]
+struct TimeError(pub httpbin::data::time::TimeError);
+
+impl Display for TimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for TimeError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(
+        scope("/time")
+            .service(resource("/now").route(get().to(time_now)))
+            .service(resource("/parse").route(get().to(time_parse)))
+            .service(resource("/convert").route(get().to(time_convert))),
+    );
+}
+
+async fn time_now(Query(Now { format, tz }): Query<Now>) -> Result<String, TimeError> {
+    let now =
+        httpbin::data::time::now(format.unwrap_or_default(), tz.as_deref()).map_err(TimeError)?;
+
+    Ok(now)
+}
+
+async fn time_parse(
+    Query(Parse { time, from, tz }): Query<Parse>,
+) -> Result<Json<TimeInspection>, TimeError> {
+    let inspection =
+        httpbin::data::time::parse(&time, from.as_deref(), tz.as_deref()).map_err(TimeError)?;
+
+    Ok(Json(inspection))
+}
+
+async fn time_convert(
+    Query(Parse { time, from, tz }): Query<Parse>,
+    Query(Format { format }): Query<Format>,
+) -> Result<String, TimeError> {
+    let time = httpbin::data::time::convert(
+        &time,
+        from.as_deref(),
+        tz.as_deref(),
+        format.unwrap_or_default(),
+    )
+    .map_err(TimeError)?;
+
+    Ok(time)
+}
@@ -1,10 +1,19 @@
 use std::fmt::Display;
 
 use actix_web::{
-    web::{get, resource, scope, Query, ServiceConfig},
+    web::{get, resource, scope, Data, Json, Query, ServiceConfig},
     HttpResponse, ResponseError, Result,
 };
-use httpbin::data::uuid::{UuidBuffer, UuidFormat, UuidNamespace, UuidNodeId};
+use httpbin::{
+    cli::UuidConfig,
+    data::{
+        base64::Base64Engine,
+        uuid::{
+            UuidBuffer, UuidConvertVersion, UuidFormat, UuidInspection, UuidNamespace, UuidNodeId,
+            UuidOutput, UuidRepresentation, UuidTimestamp, UuidTimestampUnit, UuidV7Method,
+        },
+    },
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -12,15 +21,41 @@ struct Format {
     format: Option<UuidFormat>,
 }
 
+#[derive(Deserialize)]
+struct Batch {
+    count: Option<usize>,
+    output: Option<UuidOutput>,
+}
+
 #[derive(Deserialize)]
 struct TimestampCounter {
-    timestamp: Option<u64>,
+    timestamp: Option<UuidTimestamp>,
+    timestamp_unit: Option<UuidTimestampUnit>,
     counter: Option<u16>,
 }
 
+impl TimestampCounter {
+    fn resolve(self) -> Result<Option<(u64, u16)>, UuidError> {
+        self.timestamp
+            .map(|timestamp| {
+                let ticks = timestamp
+                    .to_ticks(self.timestamp_unit.unwrap_or_default())
+                    .map_err(UuidError)?;
+
+                Ok((ticks, self.counter.unwrap_or(0)))
+            })
+            .transpose()
+    }
+}
+
 #[derive(Deserialize)]
 struct NodeId {
-    node_id: UuidNodeId,
+    node_id: Option<UuidNodeId>,
+}
+
+#[derive(Deserialize)]
+struct Method {
+    method: Option<UuidV7Method>,
 }
 
 #[derive(Deserialize)]
@@ -34,6 +69,19 @@ struct Buf {
     buf: UuidBuffer,
 }
 
+#[derive(Deserialize)]
+struct Inspect {
+    uuid: String,
+}
+
+#[derive(Deserialize)]
+struct Convert {
+    uuid: String,
+    version: Option<UuidConvertVersion>,
+    to: Option<UuidRepresentation>,
+    engine: Option<Base64Engine>,
+}
+
 #[derive(Debug)]
 struct UuidError(pub httpbin::data::uuid::UuidError);
 
@@ -58,23 +106,40 @@ pub fn api(cfg: &mut ServiceConfig) {
             .service(resource("/v5").route(get().to(uuid_v5)))
             .service(resource("/v6").route(get().to(uuid_v6)))
             .service(resource("/v7").route(get().to(uuid_v7)))
-            .service(resource("/v8").route(get().to(uuid_v8))),
+            .service(resource("/v8").route(get().to(uuid_v8)))
+            .service(resource("/nil").route(get().to(uuid_nil)))
+            .service(resource("/max").route(get().to(uuid_max)))
+            .service(resource("/inspect").route(get().to(uuid_inspect)))
+            .service(resource("/convert").route(get().to(uuid_convert))),
     );
 }
 
 async fn uuid_v1(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    cfg: Data<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String, UuidError> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<HttpResponse, UuidError> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(UuidError)?;
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(UuidError)?;
 
-    Ok(uuid)
+        return Ok(HttpResponse::Ok().body(uuid));
+    }
+
+    let uuids = httpbin::data::uuid::new_v1_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(UuidError)?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v3(
@@ -88,12 +153,23 @@ async fn uuid_v3(
     Ok(uuid)
 }
 
-async fn uuid_v4(Query(Format { format }): Query<Format>) -> Result<String> {
+async fn uuid_v4(
+    cfg: Data<UuidConfig>,
+    Query(Format { format }): Query<Format>,
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<HttpResponse, UuidError> {
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v4(format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v4(format);
 
-    Ok(uuid)
+        return Ok(HttpResponse::Ok().body(uuid));
+    }
+
+    let uuids = httpbin::data::uuid::new_v4_batch(format, count.unwrap_or(1), cfg.max_count)
+        .map_err(UuidError)?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v5(
@@ -108,32 +184,60 @@ async fn uuid_v5(
 }
 
 async fn uuid_v6(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    cfg: Data<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String, UuidError> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<HttpResponse, UuidError> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(UuidError)?;
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(UuidError)?;
 
-    Ok(uuid)
+        return Ok(HttpResponse::Ok().body(uuid));
+    }
+
+    let uuids = httpbin::data::uuid::new_v6_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(UuidError)?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v7(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    cfg: Data<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
+    Query(Method { method }): Query<Method>,
     Query(Format { format }): Query<Format>,
-) -> Result<String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<HttpResponse, UuidError> {
+    let timestamp = timestamp.resolve()?;
+    let method = method.unwrap_or_default();
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v7(timestamp, format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v7(timestamp, method, format);
 
-    Ok(uuid)
+        return Ok(HttpResponse::Ok().body(uuid));
+    }
+
+    let uuids = httpbin::data::uuid::new_v7_batch(
+        timestamp,
+        method,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(UuidError)?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v8(
@@ -146,3 +250,42 @@ async fn uuid_v8(
 
     Ok(uuid)
 }
+
+async fn uuid_nil(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_nil(format.unwrap_or_default())
+}
+
+async fn uuid_max(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_max(format.unwrap_or_default())
+}
+
+async fn uuid_inspect(
+    Query(Inspect { uuid }): Query<Inspect>,
+) -> Result<Json<UuidInspection>, UuidError> {
+    let inspection = httpbin::data::uuid::inspect(&uuid).map_err(UuidError)?;
+
+    Ok(Json(inspection))
+}
+
+async fn uuid_convert(
+    Query(Convert {
+        uuid,
+        version,
+        to,
+        engine,
+    }): Query<Convert>,
+) -> Result<HttpResponse, UuidError> {
+    let to = to.unwrap_or_default();
+
+    let uuid = httpbin::data::uuid::convert(&uuid, version, to, engine).map_err(UuidError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(to.content_type())
+        .body(uuid))
+}
+
+fn batch(uuids: &[String], output: UuidOutput) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(output.content_type())
+        .body(httpbin::data::uuid::render(uuids, output))
+}
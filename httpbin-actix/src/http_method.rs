@@ -11,6 +11,7 @@ use serde::Serialize;
 struct Http {
     method: String,
     uri: String,
+    protocol: String,
     headers: HashMap<String, String>,
     origin: String,
     query: Option<HashMap<String, String>>,
@@ -50,6 +51,8 @@ async fn anything(req: HttpRequest, data: Bytes) -> Json<Http> {
 
     let uri = req.uri().to_string();
 
+    let protocol = format!("{:?}", req.version());
+
     let headers = req.headers();
     let headers = headers
         .iter()
@@ -108,6 +111,7 @@ async fn anything(req: HttpRequest, data: Bytes) -> Json<Http> {
     Json(Http {
         method,
         uri,
+        protocol,
         headers,
         origin,
         query,
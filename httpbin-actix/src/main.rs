@@ -1,11 +1,17 @@
 use actix_cors::Cors;
-use actix_web::{middleware, App, HttpServer};
-use anyhow::Result;
-use httpbin::cli::Cli;
+use actix_web::{middleware, web::Data, App, HttpServer};
+use anyhow::{bail, Result};
+use httpbin::{
+    cli::{Cli, ListenerConfig},
+    tls::{Ca, Tls},
+};
 
 mod data;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
+mod websocket;
 
 #[actix_web::main]
 async fn main() -> Result<()> {
@@ -13,19 +19,66 @@ async fn main() -> Result<()> {
 
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
-    log::info!("Starting httpbin-actix on {}:{}", cfg.ip, cfg.port);
+    let uuid_cfg = Data::new(cfg.uuid.clone());
+    let fake_cfg = Data::new(cfg.fake.clone());
+    let compress_cfg = Data::new(cfg.compress.clone());
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    if tls.as_ref().is_some_and(|tls| tls.http3) {
+        bail!("actix cannot serve HTTP/3, so tls.http3 cannot be set");
+    }
+    // actix-http puts h2 and http/1.1 in front of the ALPN protocols of the rustls config
+    if tls
+        .as_ref()
+        .is_some_and(|tls| tls.alpn != ["h2", "http/1.1"])
+    {
+        bail!("actix always offers h2 and http/1.1, so tls.alpn cannot be changed");
+    }
+    let ca = Data::new(Ca::from(tls.as_ref()));
 
-    Ok(HttpServer::new(|| {
+    let mut server = HttpServer::new(move || {
         let cors = Cors::default().allowed_origin_fn(|_, _| true);
 
         App::new()
+            .app_data(uuid_cfg.clone())
+            .app_data(fake_cfg.clone())
+            .app_data(compress_cfg.clone())
+            .app_data(ca.clone())
             .wrap(middleware::Logger::default())
             .wrap(cors)
             .configure(data::api)
             .configure(http_method::api)
             .configure(request_inspection::api)
+            .configure(sse::api)
+            .configure(tls::api)
+            .configure(websocket::api)
     })
-    .bind((cfg.ip, cfg.port))?
-    .run()
-    .await?)
+    .on_connect(tls::on_connect);
+
+    #[cfg(unix)]
+    let mut sockets = Vec::new();
+    for listener in cfg.listeners() {
+        server = match listener {
+            ListenerConfig::Tcp { ip, port } => {
+                log::info!("Starting httpbin-actix on {ip}:{port}");
+                match &tls {
+                    Some(tls) => server.bind_rustls_0_23((ip, port), tls.server_config()?)?,
+                    None => server.bind_auto_h2c((ip, port))?,
+                }
+            }
+            #[cfg(unix)]
+            ListenerConfig::Unix { path, mode } => {
+                log::info!("Starting httpbin-actix on {}", path.display());
+                let (listener, socket) = httpbin::listener::bind_unix(&path, mode)?;
+                sockets.push(socket);
+                server.listen_uds(listener)?
+            }
+        };
+    }
+
+    // actix returns on Ctrl+C and SIGTERM, after which the sockets are removed
+    server.run().await?;
+    #[cfg(unix)]
+    drop(sockets);
+
+    Ok(())
 }
@@ -3,10 +3,12 @@ use std::collections::HashMap;
 use actix_web::{
     dev::ConnectionInfo,
     get,
-    http::header::USER_AGENT,
-    web::{Json, ServiceConfig},
+    http::header::{CONTENT_TYPE, USER_AGENT},
+    post,
+    web::{Bytes, Json, ServiceConfig},
     Either, HttpRequest, HttpResponse, Responder,
 };
+use httpbin::inspect::MimeInspection;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -28,7 +30,10 @@ struct UserAgent {
 }
 
 pub fn api(cfg: &mut ServiceConfig) {
-    cfg.service(headers).service(ip).service(user_agent);
+    cfg.service(headers)
+        .service(ip)
+        .service(user_agent)
+        .service(inspect_mime);
 }
 
 #[get("/headers")]
@@ -80,3 +85,13 @@ async fn user_agent(req: HttpRequest) -> Either<Json<UserAgent>, impl Responder>
         ),
     }
 }
+
+#[post("/inspect/mime")]
+async fn inspect_mime(req: HttpRequest, body: Bytes) -> Json<MimeInspection> {
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+
+    Json(httpbin::inspect::mime(&body, content_type))
+}
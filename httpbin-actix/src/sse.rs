@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use actix_web::{
+    get,
+    http::header::CACHE_CONTROL,
+    web::{Bytes, Query, ServiceConfig},
+    HttpRequest, HttpResponse, ResponseError, Result,
+};
+use futures_util::TryStreamExt;
+use httpbin::sse::SseOptions;
+
+#[derive(Debug)]
+struct SseError(pub httpbin::sse::SseError);
+
+impl Display for SseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for SseError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(sse);
+}
+
+#[get("/sse")]
+async fn sse(req: HttpRequest, Query(options): Query<SseOptions>) -> Result<HttpResponse> {
+    let last_event_id = req
+        .headers()
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let events = httpbin::sse::events(options, last_event_id).map_err(SseError)?;
+
+    Ok(match events {
+        Some(events) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header((CACHE_CONTROL, "no-cache"))
+            .streaming(events.map_ok(Bytes::from)),
+        None => HttpResponse::NoContent().finish(),
+    })
+}
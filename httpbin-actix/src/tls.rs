@@ -0,0 +1,57 @@
+use std::{any::Any, fmt::Display};
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::{
+    dev::Extensions,
+    rt::net::TcpStream,
+    web::{get, resource, Data, Json, ServiceConfig},
+    HttpRequest, HttpResponse, ResponseError, Result,
+};
+use httpbin::tls::{Ca, CertificateInfo, ClientCertificates};
+
+#[derive(Debug)]
+struct TlsError(pub httpbin::tls::TlsError);
+
+impl Display for TlsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for TlsError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(resource("/tls/ca.pem").route(get().to(ca_pem)))
+        .service(resource("/client-cert").route(get().to(client_cert)));
+}
+
+/// Keep the chain a client presented with the connection
+pub fn on_connect(conn: &dyn Any, data: &mut Extensions) {
+    if let Some(stream) = conn.downcast_ref::<TlsStream<TcpStream>>() {
+        data.insert(ClientCertificates::from_peer(
+            stream.get_ref().1.peer_certificates(),
+        ));
+    }
+}
+
+async fn ca_pem(ca: Data<Ca>) -> HttpResponse {
+    match &ca.0 {
+        Some(pem) => HttpResponse::Ok()
+            .content_type("application/x-pem-file")
+            .body(pem.clone()),
+        None => HttpResponse::NotFound().body("the certificate was not generated at startup"),
+    }
+}
+
+async fn client_cert(req: HttpRequest) -> Result<Json<Vec<CertificateInfo>>> {
+    let certs = req
+        .conn_data::<ClientCertificates>()
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Json(certs.inspect().map_err(TlsError)?))
+}
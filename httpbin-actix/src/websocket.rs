@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use actix_web::{
+    get,
+    http::header::HeaderMap,
+    rt,
+    web::{Payload, Query, ServiceConfig},
+    HttpRequest, HttpResponse, ResponseError, Result,
+};
+use futures_util::StreamExt;
+use httpbin::websocket::EchoOptions;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+#[derive(Debug)]
+struct WebSocketError(pub httpbin::websocket::WebSocketError);
+
+impl Display for WebSocketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for WebSocketError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().body(self.0.to_string())
+    }
+}
+
+pub fn api(cfg: &mut ServiceConfig) {
+    cfg.service(echo);
+}
+
+/// The values of a header joined by commas, empty if absent
+fn header_values(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(name)
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[get("/ws/echo")]
+async fn echo(
+    req: HttpRequest,
+    mut payload: Payload,
+    Query(options): Query<EchoOptions>,
+) -> Result<HttpResponse> {
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let handshake = httpbin::websocket::handshake(
+        header("upgrade"),
+        header("sec-websocket-key"),
+        header("sec-websocket-version"),
+        &header_values(headers, "sec-websocket-protocol"),
+        &header_values(headers, "sec-websocket-extensions"),
+        options,
+    )
+    .map_err(WebSocketError)?;
+
+    let mut res = HttpResponse::SwitchingProtocols();
+    for header in handshake.headers() {
+        res.insert_header(header);
+    }
+    // Sets the connection type, as the `Connection` header is written from it
+    res.upgrade("websocket");
+
+    // The upgraded connection is the payload of the request and the body of the response, which
+    // are bridged to one stream
+    let (io, peer) = tokio::io::duplex(64 * 1024);
+    let (peer_read, mut peer_write) = tokio::io::split(peer);
+    rt::spawn(async move {
+        while let Some(Ok(chunk)) = payload.next().await {
+            if peer_write.write_all(&chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+    rt::spawn(async move {
+        let _ = httpbin::websocket::echo(io, handshake).await;
+    });
+
+    Ok(res.streaming(ReaderStream::new(peer_read)))
+}
@@ -1,10 +1,26 @@
 use axum::Router;
 
+pub mod barcode;
 pub mod base64;
+pub mod codec;
+pub mod compress;
+pub mod fake;
+pub mod hmac;
+pub mod ids;
+pub mod random;
+pub mod time;
 pub mod uuid;
 
 pub fn api() -> Router {
     Router::new()
+        .merge(barcode::api())
         .nest("/base64", base64::api())
+        .merge(codec::api())
+        .merge(compress::api())
+        .nest("/fake", fake::api())
+        .nest("/hmac", hmac::api())
+        .nest("/id", ids::api())
+        .nest("/random", random::api())
+        .nest("/time", time::api())
         .nest("/uuid", uuid::api())
 }
@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use httpbin::data::barcode::{BarcodeFormat, QrEcc, Symbology};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Code {
+    data: String,
+    size: Option<usize>,
+    format: Option<BarcodeFormat>,
+}
+
+#[derive(Deserialize)]
+struct Ecc {
+    ecc: Option<QrEcc>,
+}
+
+struct BarcodeRes {
+    data: Vec<u8>,
+    content_type: &'static str,
+}
+
+impl IntoResponse for BarcodeRes {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::OK,
+            [("Content-Type", self.content_type)],
+            self.data,
+        )
+            .into_response()
+    }
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/qrcode", get(qrcode))
+        .route("/barcode/:symbology", get(barcode))
+}
+
+async fn qrcode(
+    Query(Code { data, size, format }): Query<Code>,
+    Query(Ecc { ecc }): Query<Ecc>,
+) -> Result<BarcodeRes, String> {
+    let format = format.unwrap_or_default();
+
+    let data =
+        httpbin::data::barcode::qrcode(&data, ecc, size, format).map_err(|e| e.to_string())?;
+
+    Ok(BarcodeRes {
+        data,
+        content_type: format.content_type(),
+    })
+}
+
+async fn barcode(
+    Path(symbology): Path<Symbology>,
+    Query(Code { data, size, format }): Query<Code>,
+) -> Result<BarcodeRes, String> {
+    let format = format.unwrap_or_default();
+
+    let data = httpbin::data::barcode::barcode(&data, symbology, size, format)
+        .map_err(|e| e.to_string())?;
+
+    Ok(BarcodeRes {
+        data,
+        content_type: format.content_type(),
+    })
+}
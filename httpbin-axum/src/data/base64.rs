@@ -1,18 +1,27 @@
 use axum::{
-    body,
+    body::{self, Body},
     extract::{Path, Query},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::post,
-    Router,
+    routing::{get, post},
+    Json, Router,
+};
+use httpbin::data::base64::{
+    Base64Engine, Base64EngineInfo, Base64Padding, Base64Wrap, Base64WrapMode,
 };
-use httpbin::data::base64::Base64Engine;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
 struct Base64Config {
     pub alphabet: Option<String>,
     pub pad: Option<bool>,
+    pub padding: Option<Base64Padding>,
+}
+
+#[derive(Deserialize)]
+struct Wrap {
+    pub wrap: Option<Base64WrapMode>,
+    pub label: Option<String>,
 }
 
 enum Base64Res {
@@ -35,23 +44,36 @@ pub fn api() -> Router {
     Router::new()
         .route("/encode/:engine", post(base64_encode))
         .route("/decode/:engine", post(base64_decode))
+        .route("/stream/encode/:engine", post(base64_encode_stream))
+        .route("/stream/decode/:engine", post(base64_decode_stream))
+        .route("/engines", get(base64_engines))
 }
 
 async fn base64_encode(
     Path(engine): Path<Base64Engine>,
     Query(config): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
     data: body::Bytes,
 ) -> Result<Base64Res, String> {
     let config = match engine {
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: config.alphabet.unwrap_or_default(),
             pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
         }),
         _ => None,
     };
 
-    let encoded =
-        httpbin::data::base64::encode(&data, engine, config).map_err(|e| e.to_string())?;
+    let encoded = match wrap {
+        Some(wrap) => httpbin::data::base64::encode_wrapped(
+            &data,
+            engine,
+            config,
+            Base64Wrap::new(wrap, label),
+        ),
+        None => httpbin::data::base64::encode(&data, engine, config),
+    }
+    .map_err(|e| e.to_string())?;
 
     Ok(Base64Res::OkText(encoded))
 }
@@ -65,6 +87,7 @@ async fn base64_decode(
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: config.alphabet.unwrap_or_default(),
             pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
         }),
         _ => None,
     };
@@ -87,3 +110,58 @@ async fn base64_decode(
         }
     }
 }
+
+async fn base64_encode_stream(
+    Path(engine): Path<Base64Engine>,
+    Query(config): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
+    data: Body,
+) -> Result<Response, String> {
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: config.alphabet.unwrap_or_default(),
+            pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
+        }),
+        _ => None,
+    };
+    let wrap = wrap.map(|wrap| Base64Wrap::new(wrap, label));
+
+    let encoded =
+        httpbin::data::base64::encode_stream(data.into_data_stream(), engine, config, wrap)
+            .map_err(|e| e.to_string())?;
+
+    Ok((
+        [("Content-Type", "text/plain; charset=utf-8")],
+        Body::from_stream(encoded),
+    )
+        .into_response())
+}
+
+async fn base64_decode_stream(
+    Path(engine): Path<Base64Engine>,
+    Query(config): Query<Base64Config>,
+    data: Body,
+) -> Result<Response, String> {
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: config.alphabet.unwrap_or_default(),
+            pad: config.pad.unwrap_or_default(),
+            padding: config.padding,
+        }),
+        _ => None,
+    };
+
+    let decoded = httpbin::data::base64::decode_stream(data.into_data_stream(), engine, config)
+        .map_err(|e| e.to_string())?;
+
+    Ok((
+        [("Content-Type", "application/octet-stream")],
+        Body::from_stream(decoded),
+    )
+        .into_response())
+}
+
+async fn base64_engines() -> Json<Vec<Base64EngineInfo>> {
+    Json(httpbin::data::base64::engines())
+}
@@ -0,0 +1,71 @@
+use axum::{
+    body,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use httpbin::data::codec::{Codec, CodecConfig, PercentSet};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Config {
+    set: Option<PercentSet>,
+}
+
+enum CodecRes {
+    OkText(String),
+    OkBinary { data: Vec<u8>, content_type: String },
+}
+
+impl IntoResponse for CodecRes {
+    fn into_response(self) -> Response {
+        match self {
+            CodecRes::OkText(text) => (StatusCode::OK, text).into_response(),
+            CodecRes::OkBinary { data, content_type } => {
+                (StatusCode::OK, [("Content-Type", content_type)], data).into_response()
+            }
+        }
+    }
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/encode/:codec", post(codec_encode))
+        .route("/decode/:codec", post(codec_decode))
+}
+
+async fn codec_encode(
+    Path(codec): Path<Codec>,
+    Query(Config { set }): Query<Config>,
+    data: body::Bytes,
+) -> Result<CodecRes, String> {
+    let config = CodecConfig {
+        percent_set: set.unwrap_or_default(),
+    };
+
+    let encoded =
+        httpbin::data::codec::encode(&data, codec, Some(config)).map_err(|e| e.to_string())?;
+
+    Ok(CodecRes::OkText(encoded))
+}
+
+async fn codec_decode(Path(codec): Path<Codec>, data: String) -> Result<CodecRes, String> {
+    let decoded = httpbin::data::codec::decode(&data, codec).map_err(|e| e.to_string())?;
+
+    match String::from_utf8(decoded.clone()) {
+        Ok(decoded) => Ok(CodecRes::OkText(decoded)),
+        Err(_) => {
+            let kind = infer::get(&decoded);
+
+            Ok(CodecRes::OkBinary {
+                data: decoded,
+                content_type: kind
+                    .map(|k| k.mime_type())
+                    .unwrap_or("application/octet-stream")
+                    .to_string(),
+            })
+        }
+    }
+}
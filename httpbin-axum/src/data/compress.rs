@@ -0,0 +1,75 @@
+use axum::{
+    body,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Router,
+};
+use httpbin::{cli::CompressConfig, data::compress::Compression};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Level {
+    level: Option<i32>,
+}
+
+enum CompressRes {
+    OkText(String),
+    OkBinary { data: Vec<u8>, content_type: String },
+}
+
+impl IntoResponse for CompressRes {
+    fn into_response(self) -> Response {
+        match self {
+            CompressRes::OkText(text) => (StatusCode::OK, text).into_response(),
+            CompressRes::OkBinary { data, content_type } => {
+                (StatusCode::OK, [("Content-Type", content_type)], data).into_response()
+            }
+        }
+    }
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/compress/:algo", post(compress))
+        .route("/decompress/:algo", post(decompress))
+}
+
+async fn compress(
+    Path(algo): Path<Compression>,
+    Query(Level { level }): Query<Level>,
+    data: body::Bytes,
+) -> Result<CompressRes, String> {
+    let compressed =
+        httpbin::data::compress::compress(&data, algo, level).map_err(|e| e.to_string())?;
+
+    Ok(CompressRes::OkBinary {
+        data: compressed,
+        content_type: algo.content_type().to_string(),
+    })
+}
+
+async fn decompress(
+    Extension(cfg): Extension<CompressConfig>,
+    Path(algo): Path<Compression>,
+    data: body::Bytes,
+) -> Result<CompressRes, String> {
+    let decompressed = httpbin::data::compress::decompress(&data, algo, cfg.max_size)
+        .map_err(|e| e.to_string())?;
+
+    match String::from_utf8(decompressed.clone()) {
+        Ok(decompressed) => Ok(CompressRes::OkText(decompressed)),
+        Err(_) => {
+            let kind = infer::get(&decompressed);
+
+            Ok(CompressRes::OkBinary {
+                data: decompressed,
+                content_type: kind
+                    .map(|k| k.mime_type())
+                    .unwrap_or("application/octet-stream")
+                    .to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, Query},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use httpbin::{
+    cli::FakeConfig,
+    data::fake::{FakeKind, FakeLocale},
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct CountSeed {
+    count: Option<usize>,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Locale {
+    locale: Option<FakeLocale>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/schema", post(fake_schema))
+        .route("/:kind", get(fake_records))
+}
+
+async fn fake_schema(
+    Extension(cfg): Extension<FakeConfig>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+    Json(schema): Json<Value>,
+) -> Result<Json<Value>, String> {
+    let instances = httpbin::data::fake::schema(&schema, count, seed, cfg.max_count)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Json(instances))
+}
+
+async fn fake_records(
+    Extension(cfg): Extension<FakeConfig>,
+    Path(kind): Path<FakeKind>,
+    Query(Locale { locale }): Query<Locale>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+) -> Result<Json<Value>, String> {
+    let records = httpbin::data::fake::records(kind, locale, count, seed, cfg.max_count)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Json(records))
+}
@@ -0,0 +1,67 @@
+use axum::{
+    body,
+    extract::{Path, Query},
+    routing::post,
+    Json, Router,
+};
+use httpbin::data::{
+    base64::Base64Engine,
+    hmac::{HmacAlgorithm, HmacKeyEncoding, HmacMac, HmacMacEncoding, HmacVerification},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Key {
+    key: String,
+    key_encoding: Option<HmacKeyEncoding>,
+    engine: Option<Base64Engine>,
+}
+
+#[derive(Deserialize)]
+struct Mac {
+    mac: String,
+    mac_encoding: Option<HmacMacEncoding>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/:algorithm/sign", post(hmac_sign))
+        .route("/:algorithm/verify", post(hmac_verify))
+}
+
+async fn hmac_sign(
+    Path(algorithm): Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+    data: body::Bytes,
+) -> Result<Json<HmacMac>, String> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(|e| e.to_string())?;
+
+    let mac = httpbin::data::hmac::sign(algorithm, &key, &data);
+
+    Ok(Json(mac.as_slice().into()))
+}
+
+async fn hmac_verify(
+    Path(algorithm): Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+    Query(Mac { mac, mac_encoding }): Query<Mac>,
+    data: body::Bytes,
+) -> Result<Json<HmacVerification>, String> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(|e| e.to_string())?;
+    let mac = httpbin::data::hmac::decode_mac(&mac, mac_encoding.unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+
+    let verification = httpbin::data::hmac::verify(algorithm, &key, &data, &mac);
+
+    Ok(Json(verification))
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, Query},
+    routing::get,
+    Json, Router,
+};
+use httpbin::data::ids::{IdInspection, IdKind, IdOptions};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Id {
+    id: String,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/:kind", get(id_generate))
+        .route("/:kind/parse", get(id_parse))
+}
+
+async fn id_generate(
+    Path(kind): Path<IdKind>,
+    Query(options): Query<IdOptions>,
+) -> Result<String, String> {
+    let id = httpbin::data::ids::generate(kind, options).map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
+
+async fn id_parse(
+    Path(kind): Path<IdKind>,
+    Query(Id { id }): Query<Id>,
+    Query(options): Query<IdOptions>,
+) -> Result<Json<IdInspection>, String> {
+    let inspection = httpbin::data::ids::parse(kind, &id, options).map_err(|e| e.to_string())?;
+
+    Ok(Json(inspection))
+}
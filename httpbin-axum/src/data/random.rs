@@ -0,0 +1,77 @@
+use axum::{extract::Query, routing::get, Json, Router};
+use httpbin::data::random::{RandomCharset, RandomItems};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Seed {
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Range<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct Charset {
+    charset: Option<RandomCharset>,
+    length: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Items {
+    items: RandomItems,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/int", get(random_int))
+        .route("/float", get(random_float))
+        .route("/string", get(random_string))
+        .route("/choice", get(random_choice))
+        .route("/shuffle", get(random_shuffle))
+}
+
+async fn random_int(
+    Query(Range { min, max }): Query<Range<i64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, String> {
+    let int = httpbin::data::random::int(min, max, seed).map_err(|e| e.to_string())?;
+
+    Ok(int.to_string())
+}
+
+async fn random_float(
+    Query(Range { min, max }): Query<Range<f64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, String> {
+    let float = httpbin::data::random::float(min, max, seed).map_err(|e| e.to_string())?;
+
+    Ok(float.to_string())
+}
+
+async fn random_string(
+    Query(Charset { charset, length }): Query<Charset>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, String> {
+    let string = httpbin::data::random::string(charset, length, seed).map_err(|e| e.to_string())?;
+
+    Ok(string)
+}
+
+async fn random_choice(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String, String> {
+    let choice = httpbin::data::random::choice(items, seed).map_err(|e| e.to_string())?;
+
+    Ok(choice)
+}
+
+async fn random_shuffle(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Json<Vec<String>> {
+    Json(httpbin::data::random::shuffle(items, seed))
+}
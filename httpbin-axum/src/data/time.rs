@@ -0,0 +1,59 @@
+use axum::{extract::Query, routing::get, Json, Router};
+use httpbin::data::time::{TimeFormat, TimeInspection};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Now {
+    format: Option<TimeFormat>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Parse {
+    time: String,
+    from: Option<String>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Format {
+    format: Option<TimeFormat>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/now", get(time_now))
+        .route("/parse", get(time_parse))
+        .route("/convert", get(time_convert))
+}
+
+async fn time_now(Query(Now { format, tz }): Query<Now>) -> Result<String, String> {
+    let now = httpbin::data::time::now(format.unwrap_or_default(), tz.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    Ok(now)
+}
+
+async fn time_parse(
+    Query(Parse { time, from, tz }): Query<Parse>,
+) -> Result<Json<TimeInspection>, String> {
+    let inspection = httpbin::data::time::parse(&time, from.as_deref(), tz.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    Ok(Json(inspection))
+}
+
+async fn time_convert(
+    Query(Parse { time, from, tz }): Query<Parse>,
+    Query(Format { format }): Query<Format>,
+) -> Result<String, String> {
+    let time = httpbin::data::time::convert(
+        &time,
+        from.as_deref(),
+        tz.as_deref(),
+        format.unwrap_or_default(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(time)
+}
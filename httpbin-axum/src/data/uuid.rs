@@ -1,5 +1,19 @@
-use axum::{extract::Query, routing::get, Router};
-use httpbin::data::uuid::{UuidBuffer, UuidFormat, UuidNamespace, UuidNodeId};
+use axum::{
+    extract::Query,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use httpbin::{
+    cli::UuidConfig,
+    data::{
+        base64::Base64Engine,
+        uuid::{
+            UuidBuffer, UuidConvertVersion, UuidFormat, UuidInspection, UuidNamespace, UuidNodeId,
+            UuidOutput, UuidRepresentation, UuidTimestamp, UuidTimestampUnit, UuidV7Method,
+        },
+    },
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -7,15 +21,41 @@ struct Format {
     format: Option<UuidFormat>,
 }
 
+#[derive(Deserialize)]
+struct Batch {
+    count: Option<usize>,
+    output: Option<UuidOutput>,
+}
+
 #[derive(Deserialize)]
 struct TimestampCounter {
-    timestamp: Option<u64>,
+    timestamp: Option<UuidTimestamp>,
+    timestamp_unit: Option<UuidTimestampUnit>,
     counter: Option<u16>,
 }
 
+impl TimestampCounter {
+    fn resolve(self) -> Result<Option<(u64, u16)>, String> {
+        self.timestamp
+            .map(|timestamp| {
+                let ticks = timestamp
+                    .to_ticks(self.timestamp_unit.unwrap_or_default())
+                    .map_err(|e| e.to_string())?;
+
+                Ok((ticks, self.counter.unwrap_or(0)))
+            })
+            .transpose()
+    }
+}
+
 #[derive(Deserialize)]
 struct NodeId {
-    node_id: UuidNodeId,
+    node_id: Option<UuidNodeId>,
+}
+
+#[derive(Deserialize)]
+struct Method {
+    method: Option<UuidV7Method>,
 }
 
 #[derive(Deserialize)]
@@ -29,6 +69,19 @@ struct Buf {
     buf: UuidBuffer,
 }
 
+#[derive(Deserialize)]
+struct Inspect {
+    uuid: String,
+}
+
+#[derive(Deserialize)]
+struct Convert {
+    uuid: String,
+    version: Option<UuidConvertVersion>,
+    to: Option<UuidRepresentation>,
+    engine: Option<Base64Engine>,
+}
+
 pub fn api() -> Router {
     Router::new()
         .route("/v1", get(uuid_v1))
@@ -38,22 +91,39 @@ pub fn api() -> Router {
         .route("/v6", get(uuid_v6))
         .route("/v7", get(uuid_v7))
         .route("/v8", get(uuid_v8))
+        .route("/nil", get(uuid_nil))
+        .route("/max", get(uuid_max))
+        .route("/inspect", get(uuid_inspect))
+        .route("/convert", get(uuid_convert))
 }
 
 async fn uuid_v1(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Extension(cfg): Extension<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String, String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response, String> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid =
-        httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(|e| e.to_string())?;
+    if count.is_none() && output.is_none() {
+        let uuid =
+            httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(|e| e.to_string())?;
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v1_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v3(
@@ -67,12 +137,23 @@ async fn uuid_v3(
     Ok(uuid)
 }
 
-async fn uuid_v4(Query(Format { format }): Query<Format>) -> Result<String, String> {
+async fn uuid_v4(
+    Extension(cfg): Extension<UuidConfig>,
+    Query(Format { format }): Query<Format>,
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response, String> {
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v4(format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v4(format);
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v4_batch(format, count.unwrap_or(1), cfg.max_count)
+        .map_err(|e| e.to_string())?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v5(
@@ -87,33 +168,61 @@ async fn uuid_v5(
 }
 
 async fn uuid_v6(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Extension(cfg): Extension<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String, String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response, String> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid =
-        httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(|e| e.to_string())?;
+    if count.is_none() && output.is_none() {
+        let uuid =
+            httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(|e| e.to_string())?;
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v6_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v7(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Extension(cfg): Extension<UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
+    Query(Method { method }): Query<Method>,
     Query(Format { format }): Query<Format>,
-) -> Result<String, String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response, String> {
+    let timestamp = timestamp.resolve()?;
+    let method = method.unwrap_or_default();
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v7(timestamp, format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v7(timestamp, method, format);
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v7_batch(
+        timestamp,
+        method,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 async fn uuid_v8(
@@ -126,3 +235,43 @@ async fn uuid_v8(
 
     Ok(uuid)
 }
+
+async fn uuid_nil(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_nil(format.unwrap_or_default())
+}
+
+async fn uuid_max(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_max(format.unwrap_or_default())
+}
+
+async fn uuid_inspect(
+    Query(Inspect { uuid }): Query<Inspect>,
+) -> Result<Json<UuidInspection>, String> {
+    let inspection = httpbin::data::uuid::inspect(&uuid).map_err(|e| e.to_string())?;
+
+    Ok(Json(inspection))
+}
+
+async fn uuid_convert(
+    Query(Convert {
+        uuid,
+        version,
+        to,
+        engine,
+    }): Query<Convert>,
+) -> Result<Response, String> {
+    let to = to.unwrap_or_default();
+
+    let uuid =
+        httpbin::data::uuid::convert(&uuid, version, to, engine).map_err(|e| e.to_string())?;
+
+    Ok(([("Content-Type", to.content_type())], uuid).into_response())
+}
+
+fn batch(uuids: &[String], output: UuidOutput) -> Response {
+    (
+        [("Content-Type", output.content_type())],
+        httpbin::data::uuid::render(uuids, output),
+    )
+        .into_response()
+}
@@ -0,0 +1,257 @@
+use std::{collections::HashMap, pin::Pin, time::Duration};
+
+use axum::Router;
+use futures_util::{stream, Stream};
+use tokio::time::{self, Instant};
+use tonic::{
+    metadata::KeyAndValueRef, service::Routes, Code, Request, Response, Status, Streaming,
+};
+use tonic_reflection::server::{Builder, Error as ReflectionError};
+use tonic_web::GrpcWebLayer;
+use tower_layer::Layer;
+
+use proto::{
+    echo_server::{Echo, EchoServer},
+    EchoRequest, EchoResponse,
+};
+
+mod proto {
+    tonic::include_proto!("httpbin.echo");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("echo_descriptor");
+}
+
+/// The maximum number of responses of one server stream
+const MAX_COUNT: u32 = 10_000;
+
+/// The echo service with server reflection, also served as gRPC-Web
+pub fn api() -> Result<Router, ReflectionError> {
+    let reflection =
+        || Builder::configure().register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET);
+
+    Ok(
+        Routes::new(GrpcWebLayer::new().layer(EchoServer::new(EchoService)))
+            .add_service(reflection().build_v1()?)
+            .add_service(reflection().build_v1alpha()?)
+            .into_axum_router(),
+    )
+}
+
+struct EchoService;
+
+type ResponseStream = Pin<Box<dyn Stream<Item = Result<EchoResponse, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Echo for EchoService {
+    async fn unary(&self, req: Request<EchoRequest>) -> Result<Response<EchoResponse>, Status> {
+        let deadline = deadline(&req)?;
+        let metadata = metadata(&req);
+        let req = req.into_inner();
+
+        delay(&req, deadline).await?;
+        status(&req)?;
+        Ok(Response::new(response(&req, 0, deadline, metadata)))
+    }
+
+    type ServerStreamingStream = ResponseStream;
+
+    async fn server_streaming(
+        &self,
+        req: Request<EchoRequest>,
+    ) -> Result<Response<Self::ServerStreamingStream>, Status> {
+        let deadline = deadline(&req)?;
+        let metadata = metadata(&req);
+        let req = req.into_inner();
+        let count = if req.count == 0 { 10 } else { req.count };
+        if count > MAX_COUNT {
+            return Err(Status::invalid_argument(format!(
+                "the count must not be larger than {MAX_COUNT}"
+            )));
+        }
+
+        // The status ends the stream after the responses, to fail in the middle of a call
+        let responses = stream::unfold(Some(0), move |index| {
+            let req = req.clone();
+            let metadata = metadata.clone();
+            async move {
+                let index = index?;
+                if index == count {
+                    return status(&req).err().map(|status| (Err(status), None));
+                }
+                match delay(&req, deadline).await {
+                    Ok(()) => Some((
+                        Ok(response(&req, index, deadline, metadata)),
+                        Some(index + 1),
+                    )),
+                    Err(status) => Some((Err(status), None)),
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(responses)))
+    }
+
+    async fn client_streaming(
+        &self,
+        req: Request<Streaming<EchoRequest>>,
+    ) -> Result<Response<EchoResponse>, Status> {
+        let deadline = deadline(&req)?;
+        let metadata = metadata(&req);
+        let mut requests = req.into_inner();
+
+        let mut messages = Vec::new();
+        while let Some(req) = next(&mut requests, deadline).await? {
+            delay(&req, deadline).await?;
+            status(&req)?;
+            messages.push(req.message);
+        }
+        let req = EchoRequest {
+            message: messages.join("\n"),
+            ..Default::default()
+        };
+        Ok(Response::new(response(&req, 0, deadline, metadata)))
+    }
+
+    type BidiStreamingStream = ResponseStream;
+
+    async fn bidi_streaming(
+        &self,
+        req: Request<Streaming<EchoRequest>>,
+    ) -> Result<Response<Self::BidiStreamingStream>, Status> {
+        let deadline = deadline(&req)?;
+        let metadata = metadata(&req);
+        let requests = req.into_inner();
+
+        let responses = stream::unfold(Some((requests, 0)), move |state| {
+            let metadata = metadata.clone();
+            async move {
+                let (mut requests, index) = state?;
+                let answer = async {
+                    let Some(req) = next(&mut requests, deadline).await? else {
+                        return Ok(None);
+                    };
+                    delay(&req, deadline).await?;
+                    status(&req)?;
+                    Ok::<_, Status>(Some(response(&req, index, deadline, metadata)))
+                }
+                .await;
+                match answer {
+                    Ok(Some(res)) => Some((Ok(res), Some((requests, index + 1)))),
+                    Ok(None) => None,
+                    Err(status) => Some((Err(status), None)),
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(responses)))
+    }
+}
+
+fn response(
+    req: &EchoRequest,
+    index: u32,
+    deadline: Option<Instant>,
+    metadata: HashMap<String, String>,
+) -> EchoResponse {
+    EchoResponse {
+        message: req.message.clone(),
+        index,
+        metadata,
+        deadline_ms: deadline.map(|deadline| {
+            deadline
+                .saturating_duration_since(Instant::now())
+                .as_millis() as u64
+        }),
+    }
+}
+
+/// The status a request asks to fail with, if any
+#[allow(clippy::result_large_err)]
+fn status(req: &EchoRequest) -> Result<(), Status> {
+    match Code::from_i32(req.status_code) {
+        Code::Ok => Ok(()),
+        code => Err(Status::new(code, req.status_message.clone())),
+    }
+}
+
+/// Wait for the delay of a request, or fail when the deadline passes first
+async fn delay(req: &EchoRequest, deadline: Option<Instant>) -> Result<(), Status> {
+    let until = Instant::now() + Duration::from_millis(req.delay_ms);
+    match deadline {
+        Some(deadline) if deadline < until => {
+            time::sleep_until(deadline).await;
+            Err(deadline_exceeded())
+        }
+        _ => {
+            time::sleep_until(until).await;
+            Ok(())
+        }
+    }
+}
+
+/// The next request of a stream, or fail when the deadline passes first
+async fn next(
+    requests: &mut Streaming<EchoRequest>,
+    deadline: Option<Instant>,
+) -> Result<Option<EchoRequest>, Status> {
+    match deadline {
+        Some(deadline) => time::timeout_at(deadline, requests.message())
+            .await
+            .map_err(|_| deadline_exceeded())?,
+        None => requests.message().await,
+    }
+}
+
+fn deadline_exceeded() -> Status {
+    Status::deadline_exceeded("the deadline was exceeded")
+}
+
+/// The deadline of a call from its `grpc-timeout` header
+///
+/// The router does not enforce it, unlike the tonic server, so each call keeps to it.
+#[allow(clippy::result_large_err)]
+fn deadline<T>(req: &Request<T>) -> Result<Option<Instant>, Status> {
+    let Some(timeout) = req.metadata().get("grpc-timeout") else {
+        return Ok(None);
+    };
+    let timeout = timeout
+        .to_str()
+        .ok()
+        .and_then(parse_timeout)
+        .ok_or_else(|| Status::invalid_argument("the grpc-timeout header is invalid"))?;
+    Ok(Some(Instant::now() + timeout))
+}
+
+/// Parse a `grpc-timeout` value, which is at most 8 digits followed by a unit
+fn parse_timeout(timeout: &str) -> Option<Duration> {
+    if !(2..=9).contains(&timeout.len()) {
+        return None;
+    }
+    let (value, unit) = timeout.split_at(timeout.len() - 1);
+    let value: u64 = value.parse().ok()?;
+    let timeout = match unit {
+        "H" => Duration::from_secs(value * 60 * 60),
+        "M" => Duration::from_secs(value * 60),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    };
+    Some(timeout)
+}
+
+/// The metadata of a request, with binary values left in base64
+fn metadata<T>(req: &Request<T>) -> HashMap<String, String> {
+    req.metadata()
+        .iter()
+        .map(|entry| match entry {
+            KeyAndValueRef::Ascii(key, value) => (
+                key.as_str().to_string(),
+                String::from_utf8_lossy(value.as_encoded_bytes()).into_owned(),
+            ),
+            KeyAndValueRef::Binary(key, value) => (
+                key.as_str().to_string(),
+                String::from_utf8_lossy(value.as_encoded_bytes()).into_owned(),
+            ),
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+use axum::{
+    body,
+    extract::{Request, State},
+    http::{StatusCode, Version},
+    middleware::{self, Next},
+    response::{AppendHeaders, IntoResponse, Response},
+    Router,
+};
+use hyper::server::conn::http2;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    service::TowerToHyperService,
+};
+use tower_http::normalize_path::NormalizePath;
+
+type App = NormalizePath<Router>;
+
+/// Serve `app`, switching HTTP/1.1 connections which ask for it to h2c
+pub fn api(app: App) -> Router {
+    Router::new()
+        .fallback_service(app.clone())
+        .layer(middleware::from_fn_with_state(app, upgrade))
+}
+
+async fn upgrade(State(app): State<App>, mut req: Request, next: Next) -> Response {
+    let path = req.uri().path_and_query().map_or("/", |path| path.as_str());
+    let upgrade = match req.version() {
+        Version::HTTP_11 => httpbin::h2c::Upgrade::new(
+            req.method().as_str(),
+            path,
+            req.headers()
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+        ),
+        _ => None,
+    };
+    let Some(upgrade) = upgrade else {
+        return next.run(req).await;
+    };
+
+    let on_upgrade = hyper::upgrade::on(&mut req);
+    let body = match body::to_bytes(req.into_body(), upgrade.content_length()).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let res = (
+        StatusCode::SWITCHING_PROTOCOLS,
+        AppendHeaders(upgrade.headers()),
+    )
+        .into_response();
+
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            if let Ok(io) = upgrade.accept(TokioIo::new(upgraded), &body).await {
+                let _ = http2::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(io), TowerToHyperService::new(app))
+                    .await;
+            }
+        }
+    });
+
+    res
+}
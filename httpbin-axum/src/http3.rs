@@ -0,0 +1,99 @@
+use std::{io, net::SocketAddr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{Response, Version},
+    Router,
+};
+use bytes::{Buf, Bytes};
+use futures_util::{stream, StreamExt};
+use h3::{error::StreamError, server::RequestStream};
+use h3_quinn::{BidiStream, RecvStream};
+use httpbin::tls::ClientCertificates;
+use quinn::{crypto::rustls::QuicServerConfig, Endpoint, ServerConfig};
+use rustls::pki_types::CertificateDer;
+use tower::ServiceExt;
+use tower_http::normalize_path::NormalizePath;
+
+/// Serve `app` over HTTP/3 on the UDP port of `addr`
+pub async fn serve(
+    addr: SocketAddr,
+    app: NormalizePath<Router>,
+    config: rustls::ServerConfig,
+) -> io::Result<()> {
+    let config = QuicServerConfig::try_from(config)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let endpoint = Endpoint::server(ServerConfig::with_crypto(Arc::new(config)), addr)?;
+
+    while let Some(incoming) = endpoint.accept().await {
+        let app = app.clone();
+
+        tokio::spawn(async move {
+            let Ok(conn) = incoming.await else {
+                return;
+            };
+            let certs = conn
+                .peer_identity()
+                .and_then(|identity| identity.downcast::<Vec<CertificateDer<'static>>>().ok());
+            let certs = ClientCertificates::from_peer(certs.as_deref().map(Vec::as_slice));
+            let Ok(mut conn) = h3::server::Connection::new(h3_quinn::Connection::new(conn)).await
+            else {
+                return;
+            };
+            while let Ok(Some(resolver)) = conn.accept().await {
+                let app = app.clone();
+                let certs = certs.clone();
+                tokio::spawn(async move {
+                    let (req, stream) = resolver.resolve_request().await?;
+                    handle(req, stream, app, certs).await
+                });
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle(
+    req: Request<()>,
+    stream: RequestStream<BidiStream<Bytes>, Bytes>,
+    app: NormalizePath<Router>,
+    certs: ClientCertificates,
+) -> Result<(), StreamError> {
+    let (mut send, recv) = stream.split();
+
+    let (mut parts, ()) = req.into_parts();
+    parts.version = Version::HTTP_3;
+    parts.extensions.insert(certs);
+    let body = Body::from_stream(stream::unfold(recv, read_body));
+    let res = match app.oneshot(Request::from_parts(parts, body)).await {
+        Ok(res) => res,
+        Err(infallible) => match infallible {},
+    };
+
+    let (parts, body) = res.into_parts();
+    send.send_response(Response::from_parts(parts, ())).await?;
+    let mut body = body.into_data_stream();
+    while let Some(chunk) = body.next().await {
+        match chunk {
+            Ok(chunk) => send.send_data(chunk).await?,
+            // Streamed bodies fail to drop the connection, which resets the stream here
+            Err(_) => {
+                send.stop_stream(h3::error::Code::H3_INTERNAL_ERROR);
+                return Ok(());
+            }
+        }
+    }
+    send.finish().await
+}
+
+async fn read_body(
+    mut recv: RequestStream<RecvStream, Bytes>,
+) -> Option<(Result<Bytes, StreamError>, RequestStream<RecvStream, Bytes>)> {
+    match recv.recv_data().await {
+        Ok(Some(mut chunk)) => Some((Ok(chunk.copy_to_bytes(chunk.remaining())), recv)),
+        Ok(None) => None,
+        Err(e) => Some((Err(e), recv)),
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use axum::{
     body::Bytes,
     extract::RawQuery,
-    http::{HeaderMap, Method, Uri},
+    http::{HeaderMap, Method, Uri, Version},
     routing::{delete, get, patch, post, put},
     Json, Router,
 };
@@ -15,6 +15,7 @@ use serde::Serialize;
 struct Http {
     method: String,
     uri: String,
+    protocol: String,
     headers: HashMap<String, String>,
     origin: String,
     query: Option<HashMap<String, String>>,
@@ -47,9 +48,11 @@ pub fn api() -> Router {
         )
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn anything(
     method: Method,
     uri: Uri,
+    version: Version,
     query: RawQuery,
     header_map: HeaderMap,
     content_type: Option<TypedHeader<ContentType>>,
@@ -102,6 +105,7 @@ async fn anything(
     Json(Http {
         method: method.to_string(),
         uri: uri.to_string(),
+        protocol: format!("{version:?}"),
         headers,
         origin: origin.0.to_string(),
         query,
@@ -1,16 +1,36 @@
+use std::{future::IntoFuture, net::SocketAddr};
+
 use anyhow::Result;
-use axum::{extract::Request, Router, ServiceExt};
-use httpbin::cli::Cli;
+use axum::{
+    http::{header::ALT_SVC, HeaderName, HeaderValue},
+    Extension, Router,
+};
+use futures_util::{future, FutureExt};
+use httpbin::{
+    cli::{Cli, ListenerConfig},
+    listener,
+    tls::{Ca, Tls},
+};
+use tokio::net::TcpListener;
 use tower_http::{
-    cors::{AllowOrigin, CorsLayer},
+    cors::{AllowHeaders, AllowOrigin, CorsLayer},
     normalize_path::NormalizePathLayer,
+    set_header::SetResponseHeaderLayer,
     trace::TraceLayer,
 };
 use tower_layer::Layer;
 
 mod data;
+mod grpc;
+mod h2c;
+mod http3;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
+#[cfg(unix)]
+mod unix;
+mod websocket;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -21,18 +41,75 @@ async fn main() -> Result<()> {
     }
     tracing_subscriber::fmt::init();
 
-    let app = ServiceExt::<Request>::into_make_service(
-        NormalizePathLayer::trim_trailing_slash().layer(
-            Router::new()
-                .merge(data::api())
-                .merge(request_inspection::api())
-                .merge(http_method::api())
-                .layer(CorsLayer::new().allow_origin(AllowOrigin::mirror_request()))
-                .layer(TraceLayer::new_for_http()),
-        ),
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    let alt_svc = tls
+        .as_ref()
+        .and_then(|tls| tls.alt_svc(cfg.port))
+        .map(HeaderValue::try_from)
+        .transpose()?;
+
+    let app = NormalizePathLayer::trim_trailing_slash().layer(
+        Router::new()
+            .merge(data::api())
+            .merge(grpc::api()?)
+            .merge(request_inspection::api())
+            .merge(http_method::api())
+            .merge(sse::api())
+            .merge(tls::api())
+            .merge(websocket::api())
+            .layer(Extension(cfg.uuid.clone()))
+            .layer(Extension(cfg.fake.clone()))
+            .layer(Extension(cfg.compress.clone()))
+            .layer(Extension(Ca::from(tls.as_ref())))
+            .layer(SetResponseHeaderLayer::if_not_present(ALT_SVC, alt_svc))
+            .layer(
+                CorsLayer::new()
+                    .allow_origin(AllowOrigin::mirror_request())
+                    .allow_headers(AllowHeaders::mirror_request())
+                    // For gRPC-Web clients in browsers to read the status
+                    .expose_headers([
+                        HeaderName::from_static("grpc-status"),
+                        HeaderName::from_static("grpc-message"),
+                    ]),
+            )
+            .layer(TraceLayer::new_for_http()),
     );
 
-    let listener = tokio::net::TcpListener::bind((cfg.ip, cfg.port)).await?;
+    let mut servers = Vec::new();
+    #[cfg(unix)]
+    let mut sockets = Vec::new();
+    for listener in cfg.listeners() {
+        servers.push(match listener {
+            ListenerConfig::Tcp { ip, port } => {
+                let listener = TcpListener::bind((ip, port)).await?;
+                match &tls {
+                    Some(tls) => tls::serve(listener, app.clone(), tls.server_config()?).boxed(),
+                    None => axum::serve(listener, h2c::api(app.clone()))
+                        .into_future()
+                        .boxed(),
+                }
+            }
+            #[cfg(unix)]
+            ListenerConfig::Unix { path, mode } => {
+                let (listener, socket) = listener::bind_unix(&path, mode)?;
+                sockets.push(socket);
+                listener.set_nonblocking(true)?;
+                unix::serve(tokio::net::UnixListener::from_std(listener)?, app.clone()).boxed()
+            }
+        });
+    }
+    // HTTP/3 is only served on `ip` and `port`, which `Alt-Svc` advertises
+    if let Some(tls) = tls.filter(|tls| tls.http3) {
+        let addr = SocketAddr::from((cfg.ip, cfg.port));
+        servers.push(http3::serve(addr, app, tls.quic_server_config()?).boxed());
+    }
+    // The sockets are removed once the servers return
+    tokio::select! {
+        served = future::try_join_all(servers) => {
+            served?;
+        }
+        signal = listener::shutdown_signal() => signal?,
+    }
 
-    Ok(axum::serve(listener, app).await?)
+    Ok(())
 }
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
-use axum::{http::HeaderMap, routing::get, Json, Router};
+use axum::{
+    body::Bytes,
+    http::{header::CONTENT_TYPE, HeaderMap},
+    routing::{get, post},
+    Json, Router,
+};
 use axum_client_ip::InsecureClientIp;
 use axum_extra::{headers::UserAgent as UserAgentHeader, TypedHeader};
+use httpbin::inspect::MimeInspection;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -28,6 +34,7 @@ pub fn api() -> Router {
         .route("/headers", get(headers))
         .route("/ip", get(ip))
         .route("/user-agent", get(user_agent))
+        .route("/inspect/mime", post(inspect_mime))
 }
 
 async fn headers(headers: HeaderMap) -> Json<Headers> {
@@ -56,3 +63,9 @@ async fn user_agent(TypedHeader(user_agent): TypedHeader<UserAgentHeader>) -> Js
         user_agent: user_agent.to_string(),
     })
 }
+
+async fn inspect_mime(headers: HeaderMap, body: Bytes) -> Json<MimeInspection> {
+    let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+
+    Json(httpbin::inspect::mime(&body, content_type))
+}
@@ -0,0 +1,36 @@
+use axum::{
+    body::Body,
+    extract::Query,
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use httpbin::sse::SseOptions;
+
+pub fn api() -> Router {
+    Router::new().route("/sse", get(sse))
+}
+
+async fn sse(Query(options): Query<SseOptions>, headers: HeaderMap) -> Result<Response, String> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let events = httpbin::sse::events(options, last_event_id).map_err(|e| e.to_string())?;
+
+    Ok(match events {
+        Some(events) => (
+            [
+                (CONTENT_TYPE, "text/event-stream"),
+                (CACHE_CONTROL, "no-cache"),
+            ],
+            Body::from_stream(events),
+        )
+            .into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    })
+}
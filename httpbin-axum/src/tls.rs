@@ -0,0 +1,102 @@
+use std::{io, sync::Arc};
+
+use axum::{
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use httpbin::{
+    fingerprint::{self, ClientHello, Fingerprint, FingerprintError},
+    listener,
+    tls::{Ca, CertificateInfo, ClientCertificates, HANDSHAKE_TIMEOUT},
+};
+use hyper::{body::Incoming, Request};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+    service::TowerToHyperService,
+};
+use rustls::ServerConfig;
+use tokio::{net::TcpListener, time};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+use tower_http::normalize_path::NormalizePath;
+
+pub fn api() -> Router {
+    Router::new()
+        .route("/tls/ca.pem", get(ca_pem))
+        .route("/client-cert", get(client_cert))
+        .route("/tls/fingerprint", get(tls_fingerprint))
+}
+
+async fn ca_pem(Extension(ca): Extension<Ca>) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let pem = ca.0.ok_or((
+        StatusCode::NOT_FOUND,
+        "the certificate was not generated at startup".to_string(),
+    ))?;
+    Ok(([(CONTENT_TYPE, "application/x-pem-file")], pem))
+}
+
+async fn client_cert(
+    certs: Option<Extension<ClientCertificates>>,
+) -> Result<Json<Vec<CertificateInfo>>, String> {
+    let Extension(certs) = certs.unwrap_or_default();
+    certs.inspect().map(Json).map_err(|e| e.to_string())
+}
+
+async fn tls_fingerprint(
+    client_hello: Option<Extension<ClientHello>>,
+) -> Result<Json<Fingerprint>, (StatusCode, String)> {
+    let Extension(client_hello) = client_hello.ok_or((
+        StatusCode::NOT_FOUND,
+        FingerprintError::Unavailable.to_string(),
+    ))?;
+    Ok(Json(client_hello.fingerprint()))
+}
+
+/// Serve `app` over TLS, choosing HTTP/1.1 or HTTP/2 by what the client speaks after the handshake
+pub async fn serve(
+    listener: TcpListener,
+    app: NormalizePath<Router>,
+    config: ServerConfig,
+) -> io::Result<()> {
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+
+        tokio::spawn(async move {
+            let handshake = async {
+                let (stream, client_hello) = fingerprint::read_client_hello(stream).await?;
+                Ok::<_, io::Error>((acceptor.accept(stream).await?, client_hello))
+            };
+            if let Ok(Ok((stream, client_hello))) =
+                time::timeout(HANDSHAKE_TIMEOUT, handshake).await
+            {
+                let certs = ClientCertificates::from_peer(stream.get_ref().1.peer_certificates());
+                let app = app.map_request(move |mut req: Request<Incoming>| {
+                    req.extensions_mut().insert(certs.clone());
+                    if let Some(client_hello) = &client_hello {
+                        req.extensions_mut().insert(client_hello.clone());
+                    }
+                    req
+                });
+                let _ = auto::Builder::new(TokioExecutor::new())
+                    .serve_connection_with_upgrades(
+                        TokioIo::new(stream),
+                        TowerToHyperService::new(app),
+                    )
+                    .await;
+            }
+        });
+    }
+}
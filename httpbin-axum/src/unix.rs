@@ -0,0 +1,36 @@
+use std::io;
+
+use axum::Router;
+use httpbin::listener;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+    service::TowerToHyperService,
+};
+use tokio::net::UnixListener;
+use tower_http::normalize_path::NormalizePath;
+
+use crate::h2c;
+
+/// Serve `app` on a Unix domain socket, over HTTP/1.1 or HTTP/2 with prior knowledge or
+/// `Upgrade: h2c`
+pub async fn serve(listener: UnixListener, app: NormalizePath<Router>) -> io::Result<()> {
+    let app = h2c::api(app);
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        let app = app.clone();
+
+        tokio::spawn(async move {
+            let _ = auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(stream), TowerToHyperService::new(app))
+                .await;
+        });
+    }
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Query, Request},
+    http::{HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use httpbin::websocket::EchoOptions;
+use hyper_util::rt::TokioIo;
+
+pub fn api() -> Router {
+    Router::new().route("/ws/echo", get(echo))
+}
+
+/// The values of a header joined by commas, empty if absent
+fn header_values(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+async fn echo(Query(options): Query<EchoOptions>, mut req: Request) -> Result<Response, String> {
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let handshake = httpbin::websocket::handshake(
+        header("upgrade"),
+        header("sec-websocket-key"),
+        header("sec-websocket-version"),
+        &header_values(headers, "sec-websocket-protocol"),
+        &header_values(headers, "sec-websocket-extensions"),
+        options,
+    )
+    .map_err(|e| e.to_string())?;
+    let res = (
+        StatusCode::SWITCHING_PROTOCOLS,
+        AppendHeaders(handshake.headers()),
+    )
+        .into_response();
+
+    let on_upgrade = hyper::upgrade::on(&mut req);
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            let _ = httpbin::websocket::echo(TokioIo::new(upgraded), handshake).await;
+        }
+    });
+
+    Ok(res)
+}
@@ -1,6 +1,14 @@
 use poem_openapi::{OpenApi, Tags};
 
+pub mod barcode;
 pub mod base64;
+pub mod codec;
+pub mod compress;
+pub mod fake;
+pub mod hmac;
+pub mod ids;
+pub mod random;
+pub mod time;
 pub mod uuid;
 
 #[derive(Tags)]
@@ -10,5 +18,16 @@ enum DataTag {
 }
 
 pub fn api() -> impl OpenApi {
-    (base64::Api, uuid::Api)
+    (
+        barcode::Api,
+        base64::Api,
+        codec::Api,
+        compress::Api,
+        fake::Api,
+        hmac::Api,
+        ids::Api,
+        random::Api,
+        time::Api,
+        uuid::Api,
+    )
 }
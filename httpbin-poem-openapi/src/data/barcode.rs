@@ -0,0 +1,154 @@
+use poem::Result;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Binary, PlainText},
+    ApiResponse, Enum, OpenApi,
+};
+
+use super::DataTag;
+
+/// The format of the image
+///
+/// The default format is `png`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum BarcodeFormat {
+    #[default]
+    Png,
+
+    Svg,
+
+    /// Unicode block characters, two rows of modules per line
+    Text,
+}
+
+impl From<BarcodeFormat> for httpbin::data::barcode::BarcodeFormat {
+    fn from(format: BarcodeFormat) -> Self {
+        match format {
+            BarcodeFormat::Png => httpbin::data::barcode::BarcodeFormat::Png,
+            BarcodeFormat::Svg => httpbin::data::barcode::BarcodeFormat::Svg,
+            BarcodeFormat::Text => httpbin::data::barcode::BarcodeFormat::Text,
+        }
+    }
+}
+
+/// The error correction level of a QR code
+///
+/// - `l` - recovers about 7% of the data
+/// - `m` - recovers about 15% of the data, the default
+/// - `q` - recovers about 25% of the data
+/// - `h` - recovers about 30% of the data
+#[derive(Enum)]
+#[oai(rename_all = "lowercase")]
+enum QrEcc {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl From<QrEcc> for httpbin::data::barcode::QrEcc {
+    fn from(ecc: QrEcc) -> Self {
+        match ecc {
+            QrEcc::L => httpbin::data::barcode::QrEcc::L,
+            QrEcc::M => httpbin::data::barcode::QrEcc::M,
+            QrEcc::Q => httpbin::data::barcode::QrEcc::Q,
+            QrEcc::H => httpbin::data::barcode::QrEcc::H,
+        }
+    }
+}
+
+/// Barcode symbologies
+///
+/// - `code128` - printable ASCII, switching to digit pairs for runs of digits
+/// - `ean13` - 12 digits and a computed check digit, or 13 digits with a valid check digit
+#[derive(Enum)]
+#[oai(rename_all = "lowercase")]
+enum Symbology {
+    Code128,
+    Ean13,
+}
+
+impl From<Symbology> for httpbin::data::barcode::Symbology {
+    fn from(symbology: Symbology) -> Self {
+        match symbology {
+            Symbology::Code128 => httpbin::data::barcode::Symbology::Code128,
+            Symbology::Ean13 => httpbin::data::barcode::Symbology::Ean13,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum BarcodeRes {
+    /// The image
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        /// Content-Type is `image/png`, `image/svg+xml` or `text/plain` by the format
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(tag = "DataTag::Data")]
+impl Api {
+    /// Generate a QR code
+    #[oai(path = "/qrcode", method = "get")]
+    async fn qrcode(
+        &self,
+        /// The data to encode
+        data: Query<String>,
+
+        /// An optional error correction level. If not provided, `m` will be used.
+        ecc: Query<Option<QrEcc>>,
+
+        /// An optional size of a module in pixels, up to 20. If not provided, 8 will be used.
+        size: Query<Option<usize>>,
+
+        /// An optional format of the image. If not provided, PNG will be used.
+        format: Query<Option<BarcodeFormat>>,
+    ) -> Result<BarcodeRes> {
+        let format: httpbin::data::barcode::BarcodeFormat = format.0.unwrap_or_default().into();
+
+        let image = httpbin::data::barcode::qrcode(&data.0, ecc.0.map(Into::into), size.0, format)
+            .map_err(|e| BarcodeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(BarcodeRes::Ok(
+            Binary(image),
+            format.content_type().to_string(),
+        ))
+    }
+
+    /// Generate a barcode
+    #[oai(path = "/barcode/:symbology", method = "get")]
+    async fn barcode(
+        &self,
+        /// The symbology to use
+        symbology: Path<Symbology>,
+
+        /// The data to encode
+        data: Query<String>,
+
+        /// An optional width of a module in pixels, up to 20. If not provided, 2 will be used.
+        size: Query<Option<usize>>,
+
+        /// An optional format of the image. If not provided, PNG will be used.
+        format: Query<Option<BarcodeFormat>>,
+    ) -> Result<BarcodeRes> {
+        let format: httpbin::data::barcode::BarcodeFormat = format.0.unwrap_or_default().into();
+
+        let image = httpbin::data::barcode::barcode(&data.0, symbology.0.into(), size.0, format)
+            .map_err(|e| BarcodeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(BarcodeRes::Ok(
+            Binary(image),
+            format.content_type().to_string(),
+        ))
+    }
+}
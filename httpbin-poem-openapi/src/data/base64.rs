@@ -1,9 +1,10 @@
-use poem::Result;
+use poem::{Body, Result};
 use poem_openapi::{
     param::{Path, Query},
     payload::Binary,
+    payload::Json,
     payload::PlainText,
-    ApiRequest, ApiResponse, Enum, OpenApi,
+    ApiRequest, ApiResponse, Enum, Object, OpenApi,
 };
 
 use super::DataTag;
@@ -13,9 +14,12 @@ use super::DataTag;
 /// `bcrypt` `binhex` `crypt` `imap-mutf7` are not using padding
 ///
 /// The `custom` engine allows you to specify your own alphabet and padding
+///
+/// The `auto` engine detects standard or url-safe, padded or unpadded input and
+/// can only be used for decoding
 #[derive(Debug, Enum)]
 #[oai(rename_all = "snake_case")]
-enum Base64Engine {
+pub(super) enum Base64Engine {
     Standard,
     StandardNoPad,
     UrlSafe,
@@ -25,6 +29,7 @@ enum Base64Engine {
     Crypt,
     ImapMutf7,
     Custom,
+    Auto,
 }
 
 impl From<Base64Engine> for httpbin::data::base64::Base64Engine {
@@ -39,6 +44,89 @@ impl From<Base64Engine> for httpbin::data::base64::Base64Engine {
             Base64Engine::Crypt => httpbin::data::base64::Base64Engine::Crypt,
             Base64Engine::ImapMutf7 => httpbin::data::base64::Base64Engine::ImapMutf7,
             Base64Engine::Custom => httpbin::data::base64::Base64Engine::Custom,
+            Base64Engine::Auto => httpbin::data::base64::Base64Engine::Auto,
+        }
+    }
+}
+
+impl From<httpbin::data::base64::Base64Engine> for Base64Engine {
+    fn from(engine: httpbin::data::base64::Base64Engine) -> Self {
+        match engine {
+            httpbin::data::base64::Base64Engine::Standard => Base64Engine::Standard,
+            httpbin::data::base64::Base64Engine::StandardNoPad => Base64Engine::StandardNoPad,
+            httpbin::data::base64::Base64Engine::UrlSafe => Base64Engine::UrlSafe,
+            httpbin::data::base64::Base64Engine::UrlSafeNoPad => Base64Engine::UrlSafeNoPad,
+            httpbin::data::base64::Base64Engine::Bcrypt => Base64Engine::Bcrypt,
+            httpbin::data::base64::Base64Engine::BinHex => Base64Engine::BinHex,
+            httpbin::data::base64::Base64Engine::Crypt => Base64Engine::Crypt,
+            httpbin::data::base64::Base64Engine::ImapMutf7 => Base64Engine::ImapMutf7,
+            httpbin::data::base64::Base64Engine::Custom => Base64Engine::Custom,
+            httpbin::data::base64::Base64Engine::Auto => Base64Engine::Auto,
+        }
+    }
+}
+
+/// How padding is treated when decoding with the `custom` engine
+///
+/// `indifferent` accepts padded and unpadded input, `required` requires
+/// canonical padding and `forbidden` rejects any padding
+#[derive(Debug, Enum)]
+#[oai(rename_all = "lowercase")]
+enum Base64Padding {
+    Indifferent,
+    Required,
+    Forbidden,
+}
+
+impl From<Base64Padding> for httpbin::data::base64::Base64Padding {
+    fn from(padding: Base64Padding) -> Self {
+        match padding {
+            Base64Padding::Indifferent => httpbin::data::base64::Base64Padding::Indifferent,
+            Base64Padding::Required => httpbin::data::base64::Base64Padding::Required,
+            Base64Padding::Forbidden => httpbin::data::base64::Base64Padding::Forbidden,
+        }
+    }
+}
+
+/// A supported engine
+#[derive(Debug, Object)]
+struct Base64EngineInfo {
+    /// The name of the engine
+    engine: Base64Engine,
+
+    /// The 64 characters of the alphabet
+    alphabet: String,
+
+    /// Whether the engine pads when encoding
+    pad: bool,
+}
+
+impl From<httpbin::data::base64::Base64EngineInfo> for Base64EngineInfo {
+    fn from(info: httpbin::data::base64::Base64EngineInfo) -> Self {
+        Self {
+            engine: info.engine.into(),
+            alphabet: info.alphabet.to_string(),
+            pad: info.pad,
+        }
+    }
+}
+
+/// How to wrap the encoded string into lines
+///
+/// `mime` wraps at 76 columns with CRLF, `pem` wraps at 64 columns with LF and
+/// adds `-----BEGIN {label}-----` and `-----END {label}-----` lines
+#[derive(Debug, Enum)]
+#[oai(rename_all = "lowercase")]
+enum Base64WrapMode {
+    Mime,
+    Pem,
+}
+
+impl From<Base64WrapMode> for httpbin::data::base64::Base64WrapMode {
+    fn from(mode: Base64WrapMode) -> Self {
+        match mode {
+            Base64WrapMode::Mime => httpbin::data::base64::Base64WrapMode::Mime,
+            Base64WrapMode::Pem => httpbin::data::base64::Base64WrapMode::Pem,
         }
     }
 }
@@ -66,12 +154,30 @@ enum Base64Res {
     BadRequest(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum Base64StreamRes {
+    /// The encoded or decoded data, streamed as it is processed
+    #[oai(status = 200)]
+    Ok(
+        Binary<Body>,
+        /// Content-Type is `text/plain` when encoding and
+        /// `application/octet-stream` when decoding
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
 pub struct Api;
 
 #[OpenApi(tag = "DataTag::Data")]
 impl Api {
     /// Encode data to a base64 string
     #[oai(path = "/base64/encode/:engine", method = "post")]
+    #[allow(clippy::too_many_arguments)]
     async fn base64_encode(
         &self,
         /// The data to encode
@@ -91,36 +197,48 @@ impl Api {
 
         /// Whether to use padding
         pad: Query<Option<bool>>,
+
+        /// How padding is treated when decoding, derived from `pad` by default
+        padding: Query<Option<Base64Padding>>,
+
+        /// How to wrap the encoded string into lines
+        ///
+        /// `mime` wraps at 76 columns with CRLF, `pem` wraps at 64 columns with
+        /// LF and adds `-----BEGIN {label}-----` and `-----END {label}-----` lines
+        wrap: Query<Option<Base64WrapMode>>,
+
+        /// The label of the PEM armour, `DATA` by default
+        label: Query<Option<String>>,
     ) -> Result<Base64Res> {
         let config = match engine.0 {
             Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
                 alphabet: alphabet.0.unwrap_or_default(),
                 pad: pad.0.unwrap_or_default(),
+                padding: padding.0.map(Into::into),
             }),
             _ => None,
         };
 
-        match data {
-            Base64Req::Binary(data) => {
-                let encoded = httpbin::data::base64::encode(&data.0, engine.0.into(), config)
-                    .map_err(|e| Base64Res::BadRequest(PlainText(e.to_string())))?;
-
-                Ok(Base64Res::Ok(
-                    Binary(encoded.into_bytes()),
-                    "text/plain; charset=utf-8".to_string(),
-                ))
-            }
-            Base64Req::Text(data) => {
-                let encoded =
-                    httpbin::data::base64::encode(data.0.as_bytes(), engine.0.into(), config)
-                        .map_err(|e| Base64Res::BadRequest(PlainText(e.to_string())))?;
+        let data = match data {
+            Base64Req::Binary(data) => data.0,
+            Base64Req::Text(data) => data.0.into_bytes(),
+        };
 
-                Ok(Base64Res::Ok(
-                    Binary(encoded.into_bytes()),
-                    "text/plain; charset=utf-8".to_string(),
-                ))
-            }
+        let encoded = match wrap.0 {
+            Some(wrap) => httpbin::data::base64::encode_wrapped(
+                &data,
+                engine.0.into(),
+                config,
+                httpbin::data::base64::Base64Wrap::new(wrap.into(), label.0),
+            ),
+            None => httpbin::data::base64::encode(&data, engine.0.into(), config),
         }
+        .map_err(|e| Base64Res::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(Base64Res::Ok(
+            Binary(encoded.into_bytes()),
+            "text/plain; charset=utf-8".to_string(),
+        ))
     }
 
     /// Decode data from a base64 string
@@ -142,11 +260,15 @@ impl Api {
 
         /// Whether to use padding
         pad: Query<Option<bool>>,
+
+        /// How padding is treated when decoding, derived from `pad` by default
+        padding: Query<Option<Base64Padding>>,
     ) -> Result<Base64Res> {
         let config = match engine.0 {
             Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
                 alphabet: alphabet.0.unwrap_or_default(),
                 pad: pad.0.unwrap_or_default(),
+                padding: padding.0.map(Into::into),
             }),
             _ => None,
         };
@@ -171,4 +293,123 @@ impl Api {
             }
         }
     }
+
+    /// Encode a request body stream to a base64 stream
+    ///
+    /// The body is encoded as it arrives, so it can be arbitrarily large
+    #[oai(path = "/base64/stream/encode/:engine", method = "post")]
+    #[allow(clippy::too_many_arguments)]
+    async fn base64_encode_stream(
+        &self,
+        /// The data to encode
+        data: Binary<Body>,
+
+        /// Base64 engines for encoding and decoding
+        ///
+        /// `bcrypt` `binhex` `crypt` `imap-mutf7` are not using padding
+        ///
+        /// The `custom` engine allows you to specify your own alphabet and padding
+        engine: Path<Base64Engine>,
+
+        /// The alphabet to use for encoding
+        alphabet: Query<Option<String>>,
+
+        /// Whether to use padding
+        pad: Query<Option<bool>>,
+
+        /// How padding is treated when decoding, derived from `pad` by default
+        padding: Query<Option<Base64Padding>>,
+
+        /// How to wrap the encoded string into lines
+        ///
+        /// `mime` wraps at 76 columns with CRLF, `pem` wraps at 64 columns with
+        /// LF and adds `-----BEGIN {label}-----` and `-----END {label}-----` lines
+        wrap: Query<Option<Base64WrapMode>>,
+
+        /// The label of the PEM armour, `DATA` by default
+        label: Query<Option<String>>,
+    ) -> Result<Base64StreamRes> {
+        let config = match engine.0 {
+            Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+                alphabet: alphabet.0.unwrap_or_default(),
+                pad: pad.0.unwrap_or_default(),
+                padding: padding.0.map(Into::into),
+            }),
+            _ => None,
+        };
+        let wrap = wrap
+            .0
+            .map(|wrap| httpbin::data::base64::Base64Wrap::new(wrap.into(), label.0));
+
+        let encoded = httpbin::data::base64::encode_stream(
+            data.0.into_bytes_stream(),
+            engine.0.into(),
+            config,
+            wrap,
+        )
+        .map_err(|e| Base64StreamRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(Base64StreamRes::Ok(
+            Binary(Body::from_bytes_stream(encoded)),
+            "text/plain; charset=utf-8".to_string(),
+        ))
+    }
+
+    /// Decode a base64 request body stream
+    ///
+    /// Whitespace and PEM armour lines are ignored
+    #[oai(path = "/base64/stream/decode/:engine", method = "post")]
+    async fn base64_decode_stream(
+        &self,
+        /// The data to decode
+        data: Binary<Body>,
+
+        /// Base64 engines for encoding and decoding
+        ///
+        /// `bcrypt` `binhex` `crypt` `imap-mutf7` are not using padding
+        ///
+        /// The `custom` engine allows you to specify your own alphabet and padding
+        engine: Path<Base64Engine>,
+
+        /// The alphabet to use for decoding
+        alphabet: Query<Option<String>>,
+
+        /// Whether to use padding
+        pad: Query<Option<bool>>,
+
+        /// How padding is treated when decoding, derived from `pad` by default
+        padding: Query<Option<Base64Padding>>,
+    ) -> Result<Base64StreamRes> {
+        let config = match engine.0 {
+            Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+                alphabet: alphabet.0.unwrap_or_default(),
+                pad: pad.0.unwrap_or_default(),
+                padding: padding.0.map(Into::into),
+            }),
+            _ => None,
+        };
+
+        let decoded = httpbin::data::base64::decode_stream(
+            data.0.into_bytes_stream(),
+            engine.0.into(),
+            config,
+        )
+        .map_err(|e| Base64StreamRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(Base64StreamRes::Ok(
+            Binary(Body::from_bytes_stream(decoded)),
+            "application/octet-stream".to_string(),
+        ))
+    }
+
+    /// List the engines with a fixed alphabet
+    #[oai(path = "/base64/engines", method = "get")]
+    async fn base64_engines(&self) -> Json<Vec<Base64EngineInfo>> {
+        Json(
+            httpbin::data::base64::engines()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        )
+    }
 }
@@ -0,0 +1,178 @@
+use poem::Result;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Binary, PlainText},
+    ApiRequest, ApiResponse, Enum, OpenApi,
+};
+
+use super::DataTag;
+
+/// Codecs for encoding and decoding
+///
+/// - `hex` `hex_upper` - base16 in lowercase or uppercase, decoding accepts both
+/// - `base32` `base32_no_pad` - RFC 4648 base32 with or without padding
+/// - `base32_crockford` - Crockford's base32
+/// - `base58` `base58_flickr` - base58 with the Bitcoin or Flickr alphabet
+/// - `base85` - RFC 1924 base85
+/// - `z85` - ZeroMQ base85, the length of the data must be a multiple of 4
+/// - `ascii85` - Adobe base85, decoding accepts the `<~` `~>` delimiters
+/// - `percent` - percent-encoding with the set selected by `set`
+/// - `quoted_printable` - RFC 2045 quoted-printable
+#[derive(Debug, Enum)]
+#[oai(rename_all = "snake_case")]
+enum Codec {
+    Hex,
+    HexUpper,
+    Base32,
+    Base32NoPad,
+    Base32Crockford,
+    Base58,
+    Base58Flickr,
+    Base85,
+    Z85,
+    Ascii85,
+    Percent,
+    QuotedPrintable,
+}
+
+impl From<Codec> for httpbin::data::codec::Codec {
+    fn from(codec: Codec) -> Self {
+        match codec {
+            Codec::Hex => httpbin::data::codec::Codec::Hex,
+            Codec::HexUpper => httpbin::data::codec::Codec::HexUpper,
+            Codec::Base32 => httpbin::data::codec::Codec::Base32,
+            Codec::Base32NoPad => httpbin::data::codec::Codec::Base32NoPad,
+            Codec::Base32Crockford => httpbin::data::codec::Codec::Base32Crockford,
+            Codec::Base58 => httpbin::data::codec::Codec::Base58,
+            Codec::Base58Flickr => httpbin::data::codec::Codec::Base58Flickr,
+            Codec::Base85 => httpbin::data::codec::Codec::Base85,
+            Codec::Z85 => httpbin::data::codec::Codec::Z85,
+            Codec::Ascii85 => httpbin::data::codec::Codec::Ascii85,
+            Codec::Percent => httpbin::data::codec::Codec::Percent,
+            Codec::QuotedPrintable => httpbin::data::codec::Codec::QuotedPrintable,
+        }
+    }
+}
+
+/// The set of bytes to escape when percent-encoding
+///
+/// The sets are defined by the [URL Standard](https://url.spec.whatwg.org/#percent-encoded-bytes).
+/// The default set is `component`.
+#[derive(Debug, Enum, Default)]
+#[oai(rename_all = "snake_case")]
+enum PercentSet {
+    Controls,
+    Fragment,
+    Query,
+    Path,
+    Userinfo,
+    #[default]
+    Component,
+    NonAlphanumeric,
+}
+
+impl From<PercentSet> for httpbin::data::codec::PercentSet {
+    fn from(set: PercentSet) -> Self {
+        match set {
+            PercentSet::Controls => httpbin::data::codec::PercentSet::Controls,
+            PercentSet::Fragment => httpbin::data::codec::PercentSet::Fragment,
+            PercentSet::Query => httpbin::data::codec::PercentSet::Query,
+            PercentSet::Path => httpbin::data::codec::PercentSet::Path,
+            PercentSet::Userinfo => httpbin::data::codec::PercentSet::Userinfo,
+            PercentSet::Component => httpbin::data::codec::PercentSet::Component,
+            PercentSet::NonAlphanumeric => httpbin::data::codec::PercentSet::NonAlphanumeric,
+        }
+    }
+}
+
+#[derive(ApiRequest, Debug)]
+enum CodecReq {
+    Binary(Binary<Vec<u8>>),
+    Text(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum CodecRes {
+    /// The encoded or decoded data
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        /// Content-Type is `application/octet-stream` by default and is set to
+        /// the actual type inferred by [infer](https://crates.io/crates/infer)
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(tag = "DataTag::Data")]
+impl Api {
+    /// Encode data with a codec
+    #[oai(path = "/encode/:codec", method = "post")]
+    async fn codec_encode(
+        &self,
+        /// The data to encode
+        ///
+        /// The data can be binary or text
+        data: CodecReq,
+
+        /// The codec to use
+        codec: Path<Codec>,
+
+        /// The set of bytes to escape when using the `percent` codec
+        set: Query<Option<PercentSet>>,
+    ) -> Result<CodecRes> {
+        let config = httpbin::data::codec::CodecConfig {
+            percent_set: set.0.unwrap_or_default().into(),
+        };
+
+        let data = match data {
+            CodecReq::Binary(data) => data.0,
+            CodecReq::Text(data) => data.0.into_bytes(),
+        };
+
+        let encoded = httpbin::data::codec::encode(&data, codec.0.into(), Some(config))
+            .map_err(|e| CodecRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(CodecRes::Ok(
+            Binary(encoded.into_bytes()),
+            "text/plain; charset=utf-8".to_string(),
+        ))
+    }
+
+    /// Decode data with a codec
+    #[oai(path = "/decode/:codec", method = "post")]
+    async fn codec_decode(
+        &self,
+        /// The string to decode
+        data: PlainText<String>,
+
+        /// The codec to use
+        codec: Path<Codec>,
+    ) -> Result<CodecRes> {
+        let decoded = httpbin::data::codec::decode(&data.0, codec.0.into())
+            .map_err(|e| CodecRes::BadRequest(PlainText(e.to_string())))?;
+
+        match String::from_utf8(decoded.clone()) {
+            Ok(_) => Ok(CodecRes::Ok(
+                Binary(decoded),
+                "text/plain; charset=utf-8".to_string(),
+            )),
+            Err(_) => {
+                let kind = infer::get(&decoded);
+
+                Ok(CodecRes::Ok(
+                    Binary(decoded),
+                    kind.map(|k| k.mime_type())
+                        .unwrap_or("application/octet-stream")
+                        .to_string(),
+                ))
+            }
+        }
+    }
+}
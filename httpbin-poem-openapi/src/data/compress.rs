@@ -0,0 +1,129 @@
+use httpbin::cli::CompressConfig;
+use poem::{web::Data, Result};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Binary, PlainText},
+    ApiResponse, Enum, OpenApi,
+};
+
+use super::DataTag;
+
+/// Compression algorithms
+///
+/// - `gzip` `zlib` - deflate with a gzip or zlib header, levels 0 to 9, 6 by default
+/// - `deflate` - raw deflate, levels 0 to 9, 6 by default
+/// - `brotli` - levels 0 to 11, 11 by default
+/// - `zstd` - levels from the negative fast levels up to 22, 3 by default
+/// - `lz4` - the LZ4 frame format, without levels
+/// - `snappy` - the snappy framing format, without levels
+/// - `snappy_raw` - a raw snappy block, without levels
+#[derive(Debug, Enum, Clone, Copy)]
+#[oai(rename_all = "snake_case")]
+enum Compression {
+    Gzip,
+    Zlib,
+    Deflate,
+    Brotli,
+    Zstd,
+    Lz4,
+    Snappy,
+    SnappyRaw,
+}
+
+impl From<Compression> for httpbin::data::compress::Compression {
+    fn from(algo: Compression) -> Self {
+        match algo {
+            Compression::Gzip => httpbin::data::compress::Compression::Gzip,
+            Compression::Zlib => httpbin::data::compress::Compression::Zlib,
+            Compression::Deflate => httpbin::data::compress::Compression::Deflate,
+            Compression::Brotli => httpbin::data::compress::Compression::Brotli,
+            Compression::Zstd => httpbin::data::compress::Compression::Zstd,
+            Compression::Lz4 => httpbin::data::compress::Compression::Lz4,
+            Compression::Snappy => httpbin::data::compress::Compression::Snappy,
+            Compression::SnappyRaw => httpbin::data::compress::Compression::SnappyRaw,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum CompressRes {
+    /// The compressed or decompressed data
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        /// Content-Type is the type of the algorithm when compressing, and is
+        /// `text/plain` or the type inferred by [infer](https://crates.io/crates/infer)
+        /// when decompressing
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(tag = "DataTag::Data")]
+impl Api {
+    /// Compress data
+    #[oai(path = "/compress/:algo", method = "post")]
+    async fn compress(
+        &self,
+        /// The data to compress
+        data: Binary<Vec<u8>>,
+
+        /// The algorithm to use
+        algo: Path<Compression>,
+
+        /// An optional level to compress at. If not provided, the default level of the algorithm will be used.
+        level: Query<Option<i32>>,
+    ) -> Result<CompressRes> {
+        let algo: httpbin::data::compress::Compression = algo.0.into();
+
+        let compressed = httpbin::data::compress::compress(&data.0, algo, level.0)
+            .map_err(|e| CompressRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(CompressRes::Ok(
+            Binary(compressed),
+            algo.content_type().to_string(),
+        ))
+    }
+
+    /// Decompress data
+    ///
+    /// The size of the decompressed data is limited by the server config.
+    #[oai(path = "/decompress/:algo", method = "post")]
+    async fn decompress(
+        &self,
+        /// The data to decompress
+        data: Binary<Vec<u8>>,
+
+        /// The algorithm to use
+        algo: Path<Compression>,
+
+        cfg: Data<&CompressConfig>,
+    ) -> Result<CompressRes> {
+        let decompressed =
+            httpbin::data::compress::decompress(&data.0, algo.0.into(), cfg.max_size)
+                .map_err(|e| CompressRes::BadRequest(PlainText(e.to_string())))?;
+
+        match String::from_utf8(decompressed.clone()) {
+            Ok(_) => Ok(CompressRes::Ok(
+                Binary(decompressed),
+                "text/plain; charset=utf-8".to_string(),
+            )),
+            Err(_) => {
+                let kind = infer::get(&decompressed);
+
+                Ok(CompressRes::Ok(
+                    Binary(decompressed),
+                    kind.map(|k| k.mime_type())
+                        .unwrap_or("application/octet-stream")
+                        .to_string(),
+                ))
+            }
+        }
+    }
+}
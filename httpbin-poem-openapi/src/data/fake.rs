@@ -0,0 +1,138 @@
+use httpbin::cli::FakeConfig;
+use poem::{web::Data, Result};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Json, PlainText},
+    ApiResponse, Enum, OpenApi,
+};
+
+use super::DataTag;
+
+/// The kind of fake record
+///
+/// - `person` - a name with a username, email, phone number and birthday
+/// - `address` - a street, city, postcode and country
+/// - `company` - a name, industry, domain and email
+/// - `credit_card` - a payment processor test number with a brand, expiry and CVC
+/// - `ip` - an IPv4 and an IPv6 address from the documentation ranges
+/// - `user_agent` - a browser `User-Agent`
+/// - `lorem` - a paragraph of lorem ipsum
+#[derive(Enum)]
+#[oai(rename_all = "snake_case")]
+enum FakeKind {
+    Person,
+    Address,
+    Company,
+    CreditCard,
+    Ip,
+    UserAgent,
+    Lorem,
+}
+
+impl From<FakeKind> for httpbin::data::fake::FakeKind {
+    fn from(kind: FakeKind) -> Self {
+        match kind {
+            FakeKind::Person => httpbin::data::fake::FakeKind::Person,
+            FakeKind::Address => httpbin::data::fake::FakeKind::Address,
+            FakeKind::Company => httpbin::data::fake::FakeKind::Company,
+            FakeKind::CreditCard => httpbin::data::fake::FakeKind::CreditCard,
+            FakeKind::Ip => httpbin::data::fake::FakeKind::Ip,
+            FakeKind::UserAgent => httpbin::data::fake::FakeKind::UserAgent,
+            FakeKind::Lorem => httpbin::data::fake::FakeKind::Lorem,
+        }
+    }
+}
+
+/// The locale of names, addresses and phone numbers
+///
+/// The default locale is `en`.
+#[derive(Enum)]
+#[oai(rename_all = "lowercase")]
+enum FakeLocale {
+    En,
+    De,
+    Fr,
+}
+
+impl From<FakeLocale> for httpbin::data::fake::FakeLocale {
+    fn from(locale: FakeLocale) -> Self {
+        match locale {
+            FakeLocale::En => httpbin::data::fake::FakeLocale::En,
+            FakeLocale::De => httpbin::data::fake::FakeLocale::De,
+            FakeLocale::Fr => httpbin::data::fake::FakeLocale::Fr,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum FakeRes {
+    /// The generated instance or record, or an array of them if `count` is given
+    #[oai(status = 200)]
+    Ok(Json<serde_json::Value>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(prefix_path = "/fake", tag = "DataTag::Data")]
+impl Api {
+    /// Generate instances of a JSON Schema
+    ///
+    /// Types, `const`, `enum`, local `$ref`s, `allOf`/`anyOf`/`oneOf`, numeric
+    /// bounds, `minLength`/`maxLength`, `pattern`, the `uuid`, `email`,
+    /// `date-time`, `date`, `time`, `uri`, `hostname`, `ipv4` and `ipv6`
+    /// formats, `items`/`prefixItems`, `minItems`/`maxItems`/`uniqueItems`,
+    /// `properties` and `required` are understood.
+    #[oai(path = "/schema", method = "post")]
+    async fn fake_schema(
+        &self,
+        /// The JSON Schema
+        schema: Json<serde_json::Value>,
+
+        /// An optional number of instances to generate, limited by the server config. If provided, an array will be returned.
+        count: Query<Option<usize>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+
+        cfg: Data<&FakeConfig>,
+    ) -> Result<FakeRes> {
+        let instances = httpbin::data::fake::schema(&schema, count.0, seed.0, cfg.max_count)
+            .map_err(|e| FakeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(FakeRes::Ok(Json(instances)))
+    }
+
+    /// Generate fake records
+    #[oai(path = "/:kind", method = "get")]
+    async fn fake_records(
+        &self,
+        /// The kind of record
+        kind: Path<FakeKind>,
+
+        /// An optional locale. If not provided, `en` will be used.
+        locale: Query<Option<FakeLocale>>,
+
+        /// An optional number of records to generate, limited by the server config. If provided, an array will be returned.
+        count: Query<Option<usize>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+
+        cfg: Data<&FakeConfig>,
+    ) -> Result<FakeRes> {
+        let records = httpbin::data::fake::records(
+            kind.0.into(),
+            locale.0.map(Into::into),
+            count.0,
+            seed.0,
+            cfg.max_count,
+        )
+        .map_err(|e| FakeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(FakeRes::Ok(Json(records)))
+    }
+}
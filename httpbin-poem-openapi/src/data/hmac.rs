@@ -0,0 +1,238 @@
+use poem::Result;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Binary, Json, PlainText},
+    ApiRequest, ApiResponse, Enum, Object, OpenApi,
+};
+
+use super::{base64::Base64Engine, DataTag};
+
+/// Hash algorithms for HMAC
+#[derive(Debug, Enum)]
+#[oai(rename_all = "lowercase")]
+enum HmacAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl From<HmacAlgorithm> for httpbin::data::hmac::HmacAlgorithm {
+    fn from(algorithm: HmacAlgorithm) -> Self {
+        match algorithm {
+            HmacAlgorithm::Md5 => httpbin::data::hmac::HmacAlgorithm::Md5,
+            HmacAlgorithm::Sha1 => httpbin::data::hmac::HmacAlgorithm::Sha1,
+            HmacAlgorithm::Sha224 => httpbin::data::hmac::HmacAlgorithm::Sha224,
+            HmacAlgorithm::Sha256 => httpbin::data::hmac::HmacAlgorithm::Sha256,
+            HmacAlgorithm::Sha384 => httpbin::data::hmac::HmacAlgorithm::Sha384,
+            HmacAlgorithm::Sha512 => httpbin::data::hmac::HmacAlgorithm::Sha512,
+        }
+    }
+}
+
+/// The encoding of the key
+///
+/// The default encoding is `raw`.
+#[derive(Debug, Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum HmacKeyEncoding {
+    /// The key is used as is
+    #[default]
+    Raw,
+
+    /// The key is a hex string
+    Hex,
+
+    /// The key is a base64 string decoded with `engine`
+    Base64,
+}
+
+impl From<HmacKeyEncoding> for httpbin::data::hmac::HmacKeyEncoding {
+    fn from(encoding: HmacKeyEncoding) -> Self {
+        match encoding {
+            HmacKeyEncoding::Raw => httpbin::data::hmac::HmacKeyEncoding::Raw,
+            HmacKeyEncoding::Hex => httpbin::data::hmac::HmacKeyEncoding::Hex,
+            HmacKeyEncoding::Base64 => httpbin::data::hmac::HmacKeyEncoding::Base64,
+        }
+    }
+}
+
+/// The encoding of the MAC to verify
+///
+/// The default encoding is `hex`.
+#[derive(Debug, Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum HmacMacEncoding {
+    /// The MAC is a hex string
+    #[default]
+    Hex,
+
+    /// The MAC is a standard or url-safe base64 string, padded or not
+    Base64,
+}
+
+impl From<HmacMacEncoding> for httpbin::data::hmac::HmacMacEncoding {
+    fn from(encoding: HmacMacEncoding) -> Self {
+        match encoding {
+            HmacMacEncoding::Hex => httpbin::data::hmac::HmacMacEncoding::Hex,
+            HmacMacEncoding::Base64 => httpbin::data::hmac::HmacMacEncoding::Base64,
+        }
+    }
+}
+
+#[derive(ApiRequest, Debug)]
+enum HmacReq {
+    Binary(Binary<Vec<u8>>),
+    Text(PlainText<String>),
+}
+
+impl HmacReq {
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            HmacReq::Binary(data) => data.0,
+            HmacReq::Text(data) => data.0.into_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+struct HmacMac {
+    /// The MAC as a lowercase hex string
+    hex: String,
+
+    /// The MAC as a standard base64 string
+    base64: String,
+
+    /// The MAC as an unpadded url-safe base64 string
+    base64_url: String,
+}
+
+impl From<httpbin::data::hmac::HmacMac> for HmacMac {
+    fn from(mac: httpbin::data::hmac::HmacMac) -> Self {
+        Self {
+            hex: mac.hex,
+            base64: mac.base64,
+            base64_url: mac.base64_url,
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+struct HmacVerification {
+    /// Whether the MAC is valid, compared in constant time
+    valid: bool,
+
+    /// The expected MAC
+    expected: HmacMac,
+}
+
+#[derive(ApiResponse)]
+enum HmacSignRes {
+    /// The MAC in multiple encodings
+    #[oai(status = 200)]
+    Ok(Json<HmacMac>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum HmacVerifyRes {
+    /// The verification result
+    #[oai(status = 200)]
+    Ok(Json<HmacVerification>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(prefix_path = "/hmac", tag = "DataTag::Data")]
+impl Api {
+    /// Sign data with HMAC
+    #[oai(path = "/:algorithm/sign", method = "post")]
+    async fn hmac_sign(
+        &self,
+        /// The data to sign
+        ///
+        /// The data can be binary or text
+        data: HmacReq,
+
+        /// The hash algorithm to use
+        algorithm: Path<HmacAlgorithm>,
+
+        /// The key to sign with
+        key: Query<String>,
+
+        /// An optional encoding of the key. If not provided, the key is used as is.
+        key_encoding: Query<Option<HmacKeyEncoding>>,
+
+        /// An optional base64 engine to decode the key with. If not provided, `standard` will be used.
+        engine: Query<Option<Base64Engine>>,
+    ) -> Result<HmacSignRes> {
+        let key = httpbin::data::hmac::decode_key(
+            &key.0,
+            key_encoding.0.unwrap_or_default().into(),
+            engine.0.map(Into::into),
+        )
+        .map_err(|e| HmacSignRes::BadRequest(PlainText(e.to_string())))?;
+
+        let mac = httpbin::data::hmac::sign(algorithm.0.into(), &key, &data.into_bytes());
+
+        Ok(HmacSignRes::Ok(Json(
+            httpbin::data::hmac::HmacMac::from(mac.as_slice()).into(),
+        )))
+    }
+
+    /// Verify a HMAC of data
+    #[oai(path = "/:algorithm/verify", method = "post")]
+    #[allow(clippy::too_many_arguments)]
+    async fn hmac_verify(
+        &self,
+        /// The signed data
+        ///
+        /// The data can be binary or text
+        data: HmacReq,
+
+        /// The hash algorithm to use
+        algorithm: Path<HmacAlgorithm>,
+
+        /// The key to verify with
+        key: Query<String>,
+
+        /// An optional encoding of the key. If not provided, the key is used as is.
+        key_encoding: Query<Option<HmacKeyEncoding>>,
+
+        /// An optional base64 engine to decode the key with. If not provided, `standard` will be used.
+        engine: Query<Option<Base64Engine>>,
+
+        /// The MAC to verify
+        mac: Query<String>,
+
+        /// An optional encoding of the MAC. If not provided, `hex` will be used.
+        mac_encoding: Query<Option<HmacMacEncoding>>,
+    ) -> Result<HmacVerifyRes> {
+        let key = httpbin::data::hmac::decode_key(
+            &key.0,
+            key_encoding.0.unwrap_or_default().into(),
+            engine.0.map(Into::into),
+        )
+        .map_err(|e| HmacVerifyRes::BadRequest(PlainText(e.to_string())))?;
+        let mac =
+            httpbin::data::hmac::decode_mac(&mac.0, mac_encoding.0.unwrap_or_default().into())
+                .map_err(|e| HmacVerifyRes::BadRequest(PlainText(e.to_string())))?;
+
+        let verification =
+            httpbin::data::hmac::verify(algorithm.0.into(), &key, &data.into_bytes(), &mac);
+
+        Ok(HmacVerifyRes::Ok(Json(HmacVerification {
+            valid: verification.valid,
+            expected: verification.expected.into(),
+        })))
+    }
+}
@@ -0,0 +1,183 @@
+use poem::Result;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::{Json, PlainText},
+    ApiResponse, Enum, Object, OpenApi,
+};
+
+use super::DataTag;
+
+/// The kind of id
+///
+/// - `ulid` - 48-bit timestamp and 80 random bits in Crockford base32
+/// - `ksuid` - 32-bit timestamp and 128 random bits in base62
+/// - `snowflake` - 41-bit timestamp, 10-bit worker id and 12-bit sequence
+/// - `nanoid` - random characters of an alphabet
+/// - `typeid` - a prefix and a UUIDv7 in Crockford base32
+#[derive(Debug, Enum)]
+#[oai(rename_all = "lowercase")]
+enum IdKind {
+    Ulid,
+    Ksuid,
+    Snowflake,
+    Nanoid,
+    Typeid,
+}
+
+impl From<IdKind> for httpbin::data::ids::IdKind {
+    fn from(kind: IdKind) -> Self {
+        match kind {
+            IdKind::Ulid => httpbin::data::ids::IdKind::Ulid,
+            IdKind::Ksuid => httpbin::data::ids::IdKind::Ksuid,
+            IdKind::Snowflake => httpbin::data::ids::IdKind::Snowflake,
+            IdKind::Nanoid => httpbin::data::ids::IdKind::Nanoid,
+            IdKind::Typeid => httpbin::data::ids::IdKind::Typeid,
+        }
+    }
+}
+
+impl From<httpbin::data::ids::IdKind> for IdKind {
+    fn from(kind: httpbin::data::ids::IdKind) -> Self {
+        match kind {
+            httpbin::data::ids::IdKind::Ulid => IdKind::Ulid,
+            httpbin::data::ids::IdKind::Ksuid => IdKind::Ksuid,
+            httpbin::data::ids::IdKind::Snowflake => IdKind::Snowflake,
+            httpbin::data::ids::IdKind::Nanoid => IdKind::Nanoid,
+            httpbin::data::ids::IdKind::Typeid => IdKind::Typeid,
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+struct IdInspection {
+    /// The kind of id
+    kind: IdKind,
+
+    /// The embedded timestamp in RFC 3339
+    timestamp: Option<String>,
+
+    /// The Snowflake worker id
+    worker_id: Option<u16>,
+
+    /// The Snowflake sequence number
+    sequence: Option<u16>,
+
+    /// The TypeID prefix
+    prefix: Option<String>,
+
+    /// The equivalent UUID of a ULID or TypeID
+    uuid: Option<String>,
+
+    /// The raw bytes as a hex string
+    hex: Option<String>,
+}
+
+impl From<httpbin::data::ids::IdInspection> for IdInspection {
+    fn from(inspection: httpbin::data::ids::IdInspection) -> Self {
+        Self {
+            kind: inspection.kind.into(),
+            timestamp: inspection.timestamp,
+            worker_id: inspection.worker_id,
+            sequence: inspection.sequence,
+            prefix: inspection.prefix,
+            uuid: inspection.uuid,
+            hex: inspection.hex,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum IdRes {
+    /// The generated id
+    #[oai(status = 200)]
+    Ok(PlainText<String>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum IdParseRes {
+    /// The fields of the id
+    #[oai(status = 200)]
+    Ok(Json<IdInspection>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(prefix_path = "/id", tag = "DataTag::Data")]
+impl Api {
+    /// Generate an id
+    #[oai(path = "/:kind", method = "get")]
+    #[allow(clippy::too_many_arguments)]
+    async fn id_generate(
+        &self,
+        /// The kind of id
+        kind: Path<IdKind>,
+
+        /// An optional Unix timestamp in milliseconds. If not provided, the current time will be used.
+        timestamp: Query<Option<u64>>,
+
+        /// An optional Snowflake epoch as a Unix timestamp in milliseconds. If not provided, the Twitter epoch will be used.
+        epoch: Query<Option<u64>>,
+
+        /// An optional Snowflake worker id less than 1024. If not provided, 0 will be used.
+        worker_id: Query<Option<u16>>,
+
+        /// An optional NanoID alphabet. If not provided, the url-safe alphabet will be used.
+        alphabet: Query<Option<String>>,
+
+        /// An optional NanoID length. If not provided, 21 will be used.
+        length: Query<Option<usize>>,
+
+        /// An optional TypeID prefix of lowercase letters and underscores.
+        prefix: Query<Option<String>>,
+    ) -> Result<IdRes> {
+        let options = httpbin::data::ids::IdOptions {
+            timestamp: timestamp.0,
+            epoch: epoch.0,
+            worker_id: worker_id.0,
+            alphabet: alphabet.0,
+            length: length.0,
+            prefix: prefix.0,
+        };
+
+        let id = httpbin::data::ids::generate(kind.0.into(), options)
+            .map_err(|e| IdRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(IdRes::Ok(PlainText(id)))
+    }
+
+    /// Parse an id
+    #[oai(path = "/:kind/parse", method = "get")]
+    async fn id_parse(
+        &self,
+        /// The kind of id
+        kind: Path<IdKind>,
+
+        /// The id to parse
+        id: Query<String>,
+
+        /// An optional Snowflake epoch as a Unix timestamp in milliseconds. If not provided, the Twitter epoch will be used.
+        epoch: Query<Option<u64>>,
+
+        /// An optional NanoID alphabet to validate against. If not provided, the url-safe alphabet will be used.
+        alphabet: Query<Option<String>>,
+    ) -> Result<IdParseRes> {
+        let options = httpbin::data::ids::IdOptions {
+            epoch: epoch.0,
+            alphabet: alphabet.0,
+            ..Default::default()
+        };
+
+        let inspection = httpbin::data::ids::parse(kind.0.into(), &id, options)
+            .map_err(|e| IdParseRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(IdParseRes::Ok(Json(inspection.into())))
+    }
+}
@@ -0,0 +1,133 @@
+use poem::Result;
+use poem_openapi::{
+    param::Query,
+    payload::{Json, PlainText},
+    ApiResponse, NewType, OpenApi,
+};
+
+use super::DataTag;
+
+/// The characters of a random string
+///
+/// The following named sets are supported:
+///
+/// - `alphanumeric` - `A-Z`, `a-z` and `0-9`
+/// - `alpha` - `A-Z` and `a-z`
+/// - `numeric` - `0-9`
+/// - `hex` - `0-9` and `a-f`
+/// - `lower` - `a-z`
+/// - `upper` - `A-Z`
+/// - custom - any other string is used as the characters themselves
+#[derive(NewType)]
+struct RandomCharset(String);
+
+impl From<RandomCharset> for httpbin::data::random::RandomCharset {
+    fn from(charset: RandomCharset) -> Self {
+        // This is safe because any string is a valid charset
+        charset.0.parse().unwrap()
+    }
+}
+
+#[derive(ApiResponse)]
+enum RandomRes {
+    /// The random value
+    #[oai(status = 200)]
+    Ok(PlainText<String>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(prefix_path = "/random", tag = "DataTag::Data")]
+impl Api {
+    /// Generate a random integer
+    #[oai(path = "/int", method = "get")]
+    async fn random_int(
+        &self,
+        /// An optional inclusive lower bound. If not provided, 0 will be used.
+        min: Query<Option<i64>>,
+
+        /// An optional inclusive upper bound. If not provided, 100 will be used.
+        max: Query<Option<i64>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+    ) -> Result<RandomRes> {
+        let int = httpbin::data::random::int(min.0, max.0, seed.0)
+            .map_err(|e| RandomRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(RandomRes::Ok(PlainText(int.to_string())))
+    }
+
+    /// Generate a random float
+    #[oai(path = "/float", method = "get")]
+    async fn random_float(
+        &self,
+        /// An optional inclusive lower bound. If not provided, 0 will be used.
+        min: Query<Option<f64>>,
+
+        /// An optional exclusive upper bound. If not provided, 1 will be used.
+        max: Query<Option<f64>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+    ) -> Result<RandomRes> {
+        let float = httpbin::data::random::float(min.0, max.0, seed.0)
+            .map_err(|e| RandomRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(RandomRes::Ok(PlainText(float.to_string())))
+    }
+
+    /// Generate a random string
+    #[oai(path = "/string", method = "get")]
+    async fn random_string(
+        &self,
+        /// An optional charset. If not provided, `alphanumeric` will be used.
+        charset: Query<Option<RandomCharset>>,
+
+        /// An optional length up to 4096. If not provided, 16 will be used.
+        length: Query<Option<usize>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+    ) -> Result<RandomRes> {
+        let string = httpbin::data::random::string(charset.0.map(Into::into), length.0, seed.0)
+            .map_err(|e| RandomRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(RandomRes::Ok(PlainText(string)))
+    }
+
+    /// Choose a random item
+    #[oai(path = "/choice", method = "get")]
+    async fn random_choice(
+        &self,
+        /// The comma-separated items to choose from
+        #[oai(explode = false)]
+        items: Query<Vec<String>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+    ) -> Result<RandomRes> {
+        let choice = httpbin::data::random::choice(items.0.into(), seed.0)
+            .map_err(|e| RandomRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(RandomRes::Ok(PlainText(choice)))
+    }
+
+    /// Shuffle the items
+    #[oai(path = "/shuffle", method = "get")]
+    async fn random_shuffle(
+        &self,
+        /// The comma-separated items to shuffle
+        #[oai(explode = false)]
+        items: Query<Vec<String>>,
+
+        /// An optional seed. The same seed always gives the same output.
+        seed: Query<Option<u64>>,
+    ) -> Json<Vec<String>> {
+        Json(httpbin::data::random::shuffle(items.0.into(), seed.0))
+    }
+}
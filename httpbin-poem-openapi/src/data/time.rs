@@ -0,0 +1,235 @@
+use poem::Result;
+use poem_openapi::{
+    param::Query,
+    payload::{Json, PlainText},
+    ApiResponse, Enum, Object, OpenApi,
+};
+
+use super::DataTag;
+
+/// The format to use for the time
+///
+/// The default format is `rfc3339`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "snake_case")]
+enum TimeFormat {
+    /// `2006-01-02T15:04:05Z`
+    #[default]
+    Rfc3339,
+
+    /// `Mon, 2 Jan 2006 15:04:05 +0000`
+    Rfc2822,
+
+    /// `Mon, 02 Jan 2006 15:04:05 GMT`, always in GMT
+    HttpDate,
+
+    /// Seconds since the Unix epoch
+    Unix,
+
+    /// Milliseconds since the Unix epoch
+    UnixMs,
+
+    /// Nanoseconds since the Unix epoch
+    UnixNs,
+}
+
+impl From<TimeFormat> for httpbin::data::time::TimeFormat {
+    fn from(format: TimeFormat) -> Self {
+        match format {
+            TimeFormat::Rfc3339 => httpbin::data::time::TimeFormat::Rfc3339,
+            TimeFormat::Rfc2822 => httpbin::data::time::TimeFormat::Rfc2822,
+            TimeFormat::HttpDate => httpbin::data::time::TimeFormat::HttpDate,
+            TimeFormat::Unix => httpbin::data::time::TimeFormat::Unix,
+            TimeFormat::UnixMs => httpbin::data::time::TimeFormat::UnixMs,
+            TimeFormat::UnixNs => httpbin::data::time::TimeFormat::UnixNs,
+        }
+    }
+}
+
+/// The format a time was recognized as
+#[derive(Debug, Enum)]
+#[oai(rename_all = "snake_case")]
+enum TimeInput {
+    /// `2006-01-02T15:04:05Z`
+    Rfc3339,
+
+    /// `Mon, 2 Jan 2006 15:04:05 +0000`
+    Rfc2822,
+
+    /// `Mon, 02 Jan 2006 15:04:05 GMT`
+    HttpDate,
+
+    /// `Monday, 02-Jan-06 15:04:05 GMT`, the obsolete RFC 850 form of HTTP-date
+    Rfc850,
+
+    /// `Mon Jan  2 15:04:05 2006`, the obsolete asctime form of HTTP-date
+    Asctime,
+
+    /// Seconds since the Unix epoch, with an optional fraction
+    Unix,
+
+    /// Milliseconds since the Unix epoch
+    UnixMs,
+
+    /// Nanoseconds since the Unix epoch
+    UnixNs,
+
+    /// `2006-01-02T15:04:05`, `2006-01-02T15:04` or `2006-01-02` without an offset, in the source time zone
+    Local,
+}
+
+impl From<httpbin::data::time::TimeInput> for TimeInput {
+    fn from(input: httpbin::data::time::TimeInput) -> Self {
+        match input {
+            httpbin::data::time::TimeInput::Rfc3339 => TimeInput::Rfc3339,
+            httpbin::data::time::TimeInput::Rfc2822 => TimeInput::Rfc2822,
+            httpbin::data::time::TimeInput::HttpDate => TimeInput::HttpDate,
+            httpbin::data::time::TimeInput::Rfc850 => TimeInput::Rfc850,
+            httpbin::data::time::TimeInput::Asctime => TimeInput::Asctime,
+            httpbin::data::time::TimeInput::Unix => TimeInput::Unix,
+            httpbin::data::time::TimeInput::UnixMs => TimeInput::UnixMs,
+            httpbin::data::time::TimeInput::UnixNs => TimeInput::UnixNs,
+            httpbin::data::time::TimeInput::Local => TimeInput::Local,
+        }
+    }
+}
+
+/// A time in all supported formats
+#[derive(Debug, Object)]
+struct TimeInspection {
+    /// The format the input was recognized as
+    input: TimeInput,
+
+    /// The time zone the time is reported in
+    timezone: String,
+
+    /// The UTC offset of the time zone at the time
+    offset: String,
+
+    /// The abbreviation of the time zone at the time, e.g. `CET`
+    abbreviation: String,
+
+    /// `2006-01-02T15:04:05Z`
+    rfc3339: String,
+
+    /// `Mon, 2 Jan 2006 15:04:05 +0000`
+    rfc2822: String,
+
+    /// `Mon, 02 Jan 2006 15:04:05 GMT`
+    http_date: String,
+
+    /// Seconds since the Unix epoch
+    unix: i64,
+
+    /// Milliseconds since the Unix epoch
+    unix_ms: i64,
+
+    /// Nanoseconds since the Unix epoch, absent outside of 1677 to 2262
+    unix_ns: Option<i64>,
+}
+
+impl From<httpbin::data::time::TimeInspection> for TimeInspection {
+    fn from(inspection: httpbin::data::time::TimeInspection) -> Self {
+        Self {
+            input: inspection.input.into(),
+            timezone: inspection.timezone,
+            offset: inspection.offset,
+            abbreviation: inspection.abbreviation,
+            rfc3339: inspection.rfc3339,
+            rfc2822: inspection.rfc2822,
+            http_date: inspection.http_date,
+            unix: inspection.unix,
+            unix_ms: inspection.unix_ms,
+            unix_ns: inspection.unix_ns,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum TimeRes {
+    /// The formatted time
+    #[oai(status = 200)]
+    Ok(PlainText<String>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+#[derive(ApiResponse)]
+enum TimeParseRes {
+    /// The time in all supported formats
+    #[oai(status = 200)]
+    Ok(Json<TimeInspection>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(prefix_path = "/time", tag = "DataTag::Data")]
+impl Api {
+    /// Get the current time
+    #[oai(path = "/now", method = "get")]
+    async fn time_now(
+        &self,
+        /// An optional format to use for the time. If not provided, RFC 3339 will be used.
+        format: Query<Option<TimeFormat>>,
+
+        /// An optional IANA time zone, e.g. `Europe/Berlin`. If not provided, UTC will be used.
+        tz: Query<Option<String>>,
+    ) -> Result<TimeRes> {
+        let now = httpbin::data::time::now(format.0.unwrap_or_default().into(), tz.0.as_deref())
+            .map_err(|e| TimeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(TimeRes::Ok(PlainText(now)))
+    }
+
+    /// Parse a time in any supported format
+    #[oai(path = "/parse", method = "get")]
+    async fn time_parse(
+        &self,
+        /// The time in RFC 3339, RFC 2822, any HTTP-date form, Unix seconds, milliseconds or nanoseconds, or a local `2006-01-02T15:04:05` without an offset
+        time: Query<String>,
+
+        /// An optional IANA time zone of a local time. If not provided, UTC will be used.
+        from: Query<Option<String>>,
+
+        /// An optional IANA time zone to report the time in. If not provided, UTC will be used.
+        tz: Query<Option<String>>,
+    ) -> Result<TimeParseRes> {
+        let inspection = httpbin::data::time::parse(&time.0, from.0.as_deref(), tz.0.as_deref())
+            .map_err(|e| TimeParseRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(TimeParseRes::Ok(Json(inspection.into())))
+    }
+
+    /// Convert a time to another time zone and format
+    #[oai(path = "/convert", method = "get")]
+    async fn time_convert(
+        &self,
+        /// The time in RFC 3339, RFC 2822, any HTTP-date form, Unix seconds, milliseconds or nanoseconds, or a local `2006-01-02T15:04:05` without an offset
+        time: Query<String>,
+
+        /// An optional IANA time zone of a local time. If not provided, UTC will be used.
+        from: Query<Option<String>>,
+
+        /// An optional IANA time zone to convert to. If not provided, UTC will be used.
+        tz: Query<Option<String>>,
+
+        /// An optional format to use for the time. If not provided, RFC 3339 will be used.
+        format: Query<Option<TimeFormat>>,
+    ) -> Result<TimeRes> {
+        let time = httpbin::data::time::convert(
+            &time.0,
+            from.0.as_deref(),
+            tz.0.as_deref(),
+            format.0.unwrap_or_default().into(),
+        )
+        .map_err(|e| TimeRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(TimeRes::Ok(PlainText(time)))
+    }
+}
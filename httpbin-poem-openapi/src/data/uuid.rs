@@ -1,7 +1,12 @@
-use poem::Result;
-use poem_openapi::{param::Query, payload::PlainText, ApiResponse, Enum, NewType, OpenApi};
+use httpbin::cli::UuidConfig;
+use poem::{web::Data, Result};
+use poem_openapi::{
+    param::Query,
+    payload::{Binary, Json, PlainText},
+    ApiResponse, Enum, NewType, Object, OpenApi,
+};
 
-use super::DataTag;
+use super::{base64::Base64Engine, DataTag};
 
 /// The namespace to use for the UUID
 ///
@@ -60,6 +65,160 @@ impl From<UuidFormat> for httpbin::data::uuid::UuidFormat {
     }
 }
 
+/// How to render a batch of UUIDs
+///
+/// The default output is `text`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum UuidOutput {
+    /// One UUID per line
+    #[default]
+    Text,
+
+    /// A JSON array
+    Json,
+
+    /// One JSON string per line
+    Ndjson,
+}
+
+impl From<UuidOutput> for httpbin::data::uuid::UuidOutput {
+    fn from(output: UuidOutput) -> Self {
+        match output {
+            UuidOutput::Text => httpbin::data::uuid::UuidOutput::Text,
+            UuidOutput::Json => httpbin::data::uuid::UuidOutput::Json,
+            UuidOutput::Ndjson => httpbin::data::uuid::UuidOutput::Ndjson,
+        }
+    }
+}
+
+/// The version to reorder a v1 or v6 UUID into
+#[derive(Enum)]
+#[oai(rename_all = "lowercase")]
+enum UuidConvertVersion {
+    V1,
+    V6,
+}
+
+impl From<UuidConvertVersion> for httpbin::data::uuid::UuidConvertVersion {
+    fn from(version: UuidConvertVersion) -> Self {
+        match version {
+            UuidConvertVersion::V1 => httpbin::data::uuid::UuidConvertVersion::V1,
+            UuidConvertVersion::V6 => httpbin::data::uuid::UuidConvertVersion::V6,
+        }
+    }
+}
+
+/// The representation of a converted UUID
+///
+/// The default representation is `hyphenated`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum UuidRepresentation {
+    /// 8-4-4-4-12
+    #[default]
+    Hyphenated,
+
+    /// 32 hex digits
+    Simple,
+
+    /// urn:uuid:8-4-4-4-12
+    Urn,
+
+    /// {8-4-4-4-12}
+    Braced,
+
+    /// The raw 16 bytes
+    Bytes,
+
+    /// The 16 bytes in base64
+    Base64,
+
+    /// A 128-bit unsigned integer in decimal
+    Integer,
+}
+
+impl From<UuidRepresentation> for httpbin::data::uuid::UuidRepresentation {
+    fn from(to: UuidRepresentation) -> Self {
+        match to {
+            UuidRepresentation::Hyphenated => httpbin::data::uuid::UuidRepresentation::Hyphenated,
+            UuidRepresentation::Simple => httpbin::data::uuid::UuidRepresentation::Simple,
+            UuidRepresentation::Urn => httpbin::data::uuid::UuidRepresentation::Urn,
+            UuidRepresentation::Braced => httpbin::data::uuid::UuidRepresentation::Braced,
+            UuidRepresentation::Bytes => httpbin::data::uuid::UuidRepresentation::Bytes,
+            UuidRepresentation::Base64 => httpbin::data::uuid::UuidRepresentation::Base64,
+            UuidRepresentation::Integer => httpbin::data::uuid::UuidRepresentation::Integer,
+        }
+    }
+}
+
+/// The unit of a numeric timestamp
+///
+/// The default unit is `ticks`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum UuidTimestampUnit {
+    /// 100-nanosecond intervals since 1582-10-15
+    #[default]
+    Ticks,
+
+    /// Milliseconds since the Unix epoch
+    Ms,
+}
+
+impl From<UuidTimestampUnit> for httpbin::data::uuid::UuidTimestampUnit {
+    fn from(unit: UuidTimestampUnit) -> Self {
+        match unit {
+            UuidTimestampUnit::Ticks => httpbin::data::uuid::UuidTimestampUnit::Ticks,
+            UuidTimestampUnit::Ms => httpbin::data::uuid::UuidTimestampUnit::Ms,
+        }
+    }
+}
+
+/// How to fill the 12 `rand_a` bits of a v7 UUID
+///
+/// The default method is `random`.
+#[derive(Enum, Default)]
+#[oai(rename_all = "lowercase")]
+enum UuidV7Method {
+    /// Random bits
+    #[default]
+    Random,
+
+    /// The sub-millisecond fraction of the timestamp
+    Precision,
+
+    /// A counter incremented within the same millisecond
+    Counter,
+}
+
+impl From<UuidV7Method> for httpbin::data::uuid::UuidV7Method {
+    fn from(method: UuidV7Method) -> Self {
+        match method {
+            UuidV7Method::Random => httpbin::data::uuid::UuidV7Method::Random,
+            UuidV7Method::Precision => httpbin::data::uuid::UuidV7Method::Precision,
+            UuidV7Method::Counter => httpbin::data::uuid::UuidV7Method::Counter,
+        }
+    }
+}
+
+/// Convert the timestamp query to RFC 4122 ticks and a counter
+fn resolve_timestamp(
+    timestamp: Option<String>,
+    unit: Option<UuidTimestampUnit>,
+    counter: Option<u16>,
+) -> Result<Option<(u64, u16)>, httpbin::data::uuid::UuidError> {
+    timestamp
+        .map(|timestamp| {
+            let ticks = timestamp
+                .parse::<httpbin::data::uuid::UuidTimestamp>()?
+                .to_ticks(unit.unwrap_or_default().into())?;
+
+            Ok((ticks, counter.unwrap_or(0)))
+        })
+        .transpose()
+}
+
 #[derive(ApiResponse)]
 enum UuidRes {
     /// The generated UUID
@@ -71,36 +230,191 @@ enum UuidRes {
     BadRequest(PlainText<String>),
 }
 
+#[derive(ApiResponse)]
+enum UuidBatchRes {
+    /// The generated UUID, or the batch of UUIDs if `count` or `output` is given
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        /// Content-Type is `text/plain` for a single UUID and depends on
+        /// `output` for a batch
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+impl UuidBatchRes {
+    fn single(uuid: String) -> Self {
+        Self::Ok(
+            Binary(uuid.into_bytes()),
+            "text/plain; charset=utf-8".to_string(),
+        )
+    }
+
+    fn batch(uuids: &[String], output: httpbin::data::uuid::UuidOutput) -> Self {
+        Self::Ok(
+            Binary(httpbin::data::uuid::render(uuids, output).into_bytes()),
+            output.content_type().to_string(),
+        )
+    }
+}
+
+#[derive(ApiResponse)]
+enum UuidConvertRes {
+    /// The converted UUID
+    #[oai(status = 200)]
+    Ok(
+        Binary<Vec<u8>>,
+        /// Content-Type is `application/octet-stream` for `bytes` and
+        /// `text/plain` otherwise
+        #[oai(header = "Content-Type")]
+        String,
+    ),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+/// A UUID in all supported formats
+#[derive(Debug, Object)]
+struct UuidFormats {
+    /// 8-4-4-4-12
+    hyphenated: String,
+
+    /// 32 hex digits
+    simple: String,
+
+    /// urn:uuid:8-4-4-4-12
+    urn: String,
+
+    /// {8-4-4-4-12}
+    braced: String,
+}
+
+impl From<httpbin::data::uuid::UuidFormats> for UuidFormats {
+    fn from(formats: httpbin::data::uuid::UuidFormats) -> Self {
+        Self {
+            hyphenated: formats.hyphenated,
+            simple: formats.simple,
+            urn: formats.urn,
+            braced: formats.braced,
+        }
+    }
+}
+
+#[derive(Debug, Object)]
+struct UuidInspection {
+    /// The version number, `0` for the nil UUID and `15` for the max UUID
+    version: usize,
+
+    /// The variant, one of `ncs`, `rfc4122`, `microsoft` or `future`
+    variant: String,
+
+    /// The embedded timestamp in RFC 3339 for v1, v6 and v7 UUIDs
+    timestamp: Option<String>,
+
+    /// The clock sequence for v1 and v6 UUIDs
+    clock_seq: Option<u16>,
+
+    /// The node id as a MAC address for v1 and v6 UUIDs
+    node_id: Option<String>,
+
+    /// The UUID in all supported formats
+    formats: UuidFormats,
+}
+
+impl From<httpbin::data::uuid::UuidInspection> for UuidInspection {
+    fn from(inspection: httpbin::data::uuid::UuidInspection) -> Self {
+        Self {
+            version: inspection.version,
+            variant: inspection.variant.to_string(),
+            timestamp: inspection.timestamp,
+            clock_seq: inspection.clock_seq,
+            node_id: inspection.node_id,
+            formats: inspection.formats.into(),
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum UuidInspectRes {
+    /// The fields of the UUID
+    #[oai(status = 200)]
+    Ok(Json<UuidInspection>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
 pub struct Api;
 
 #[OpenApi(prefix_path = "/uuid", tag = "DataTag::Data")]
 impl Api {
     /// Generate a v1 UUID
     #[oai(path = "/v1", method = "get")]
+    #[allow(clippy::too_many_arguments)]
     async fn uuid_v1(
         &self,
-        /// An optional timestamp to use for the UUID. If not provided, the current time will be used.
-        timestamp: Query<Option<u64>>,
+        /// An optional timestamp to use for the UUID, as a number or in RFC 3339. If not provided, the current time will be used.
+        timestamp: Query<Option<String>>,
+
+        /// An optional unit of a numeric timestamp. If not provided, 100-nanosecond ticks since 1582-10-15 will be used.
+        timestamp_unit: Query<Option<UuidTimestampUnit>>,
 
         /// An optional counter to use for the UUID. If not provided, 0 will be used.
         counter: Query<Option<u16>>,
 
-        /// The node ID to use for the UUID. The length must be 6.
-        #[oai(explode = false)]
-        node_id: Query<Vec<u8>>,
+        /// An optional node ID to use for the UUID, as `aa:bb:cc:dd:ee:ff` or 6 comma-separated decimal bytes. If not provided, a random one will be used.
+        node_id: Query<Option<String>>,
 
         /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
         format: Query<Option<UuidFormat>>,
-    ) -> Result<UuidRes> {
-        let timestamp = timestamp.and_then(|timestamp| {
-            counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-        });
+
+        /// An optional number of UUIDs to generate, limited by the server config.
+        count: Query<Option<usize>>,
+
+        /// An optional output of a batch. If not provided, one UUID per line will be returned.
+        output: Query<Option<UuidOutput>>,
+
+        cfg: Data<&UuidConfig>,
+    ) -> Result<UuidBatchRes> {
+        let timestamp = resolve_timestamp(timestamp.0, timestamp_unit.0, counter.0)
+            .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+        let node_id = node_id
+            .0
+            .map(|node_id| node_id.parse())
+            .transpose()
+            .map_err(|e: httpbin::data::uuid::UuidError| {
+                UuidBatchRes::BadRequest(PlainText(e.to_string()))
+            })?;
         let format = format.0.unwrap_or_default();
 
-        let uuid = httpbin::data::uuid::new_v1(timestamp, node_id.0.into(), format.into())
-            .map_err(|e| UuidRes::BadRequest(PlainText(e.to_string())))?;
+        if count.is_none() && output.is_none() {
+            let uuid = httpbin::data::uuid::new_v1(timestamp, node_id, format.into())
+                .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
 
-        Ok(UuidRes::Ok(PlainText(uuid)))
+            return Ok(UuidBatchRes::single(uuid));
+        }
+
+        let uuids = httpbin::data::uuid::new_v1_batch(
+            timestamp,
+            node_id,
+            format.into(),
+            count.0.unwrap_or(1),
+            cfg.max_count,
+        )
+        .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidBatchRes::batch(
+            &uuids,
+            output.0.unwrap_or_default().into(),
+        ))
     }
 
     /// Generate a v3 UUID
@@ -138,12 +452,31 @@ impl Api {
         &self,
         /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
         format: Query<Option<UuidFormat>>,
-    ) -> Result<UuidRes> {
+
+        /// An optional number of UUIDs to generate, limited by the server config.
+        count: Query<Option<usize>>,
+
+        /// An optional output of a batch. If not provided, one UUID per line will be returned.
+        output: Query<Option<UuidOutput>>,
+
+        cfg: Data<&UuidConfig>,
+    ) -> Result<UuidBatchRes> {
         let format = format.0.unwrap_or_default();
 
-        let uuid = httpbin::data::uuid::new_v4(format.into());
+        if count.is_none() && output.is_none() {
+            let uuid = httpbin::data::uuid::new_v4(format.into());
 
-        Ok(UuidRes::Ok(PlainText(uuid)))
+            return Ok(UuidBatchRes::single(uuid));
+        }
+
+        let uuids =
+            httpbin::data::uuid::new_v4_batch(format.into(), count.0.unwrap_or(1), cfg.max_count)
+                .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidBatchRes::batch(
+            &uuids,
+            output.0.unwrap_or_default().into(),
+        ))
     }
 
     /// Generate a v5 UUID
@@ -177,53 +510,117 @@ impl Api {
 
     /// Generate a v6 UUID
     #[oai(path = "/v6", method = "get")]
+    #[allow(clippy::too_many_arguments)]
     async fn uuid_v6(
         &self,
-        /// An optional timestamp to use for the UUID. If not provided, the current time will be used.
-        timestamp: Query<Option<u64>>,
+        /// An optional timestamp to use for the UUID, as a number or in RFC 3339. If not provided, the current time will be used.
+        timestamp: Query<Option<String>>,
+
+        /// An optional unit of a numeric timestamp. If not provided, 100-nanosecond ticks since 1582-10-15 will be used.
+        timestamp_unit: Query<Option<UuidTimestampUnit>>,
 
         /// An optional counter to use for the UUID. If not provided, 0 will be used.
         counter: Query<Option<u16>>,
 
-        /// The node ID to use for the UUID. The length must be 6.
-        #[oai(explode = false)]
-        node_id: Query<Vec<u8>>,
+        /// An optional node ID to use for the UUID, as `aa:bb:cc:dd:ee:ff` or 6 comma-separated decimal bytes. If not provided, a random one will be used.
+        node_id: Query<Option<String>>,
 
         /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
         format: Query<Option<UuidFormat>>,
-    ) -> Result<UuidRes> {
-        let timestamp = timestamp.and_then(|timestamp| {
-            counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-        });
+
+        /// An optional number of UUIDs to generate, limited by the server config.
+        count: Query<Option<usize>>,
+
+        /// An optional output of a batch. If not provided, one UUID per line will be returned.
+        output: Query<Option<UuidOutput>>,
+
+        cfg: Data<&UuidConfig>,
+    ) -> Result<UuidBatchRes> {
+        let timestamp = resolve_timestamp(timestamp.0, timestamp_unit.0, counter.0)
+            .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+        let node_id = node_id
+            .0
+            .map(|node_id| node_id.parse())
+            .transpose()
+            .map_err(|e: httpbin::data::uuid::UuidError| {
+                UuidBatchRes::BadRequest(PlainText(e.to_string()))
+            })?;
         let format = format.0.unwrap_or_default();
 
-        let uuid = httpbin::data::uuid::new_v6(timestamp, node_id.0.into(), format.into())
-            .map_err(|e| UuidRes::BadRequest(PlainText(e.to_string())))?;
+        if count.is_none() && output.is_none() {
+            let uuid = httpbin::data::uuid::new_v6(timestamp, node_id, format.into())
+                .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
 
-        Ok(UuidRes::Ok(PlainText(uuid)))
+            return Ok(UuidBatchRes::single(uuid));
+        }
+
+        let uuids = httpbin::data::uuid::new_v6_batch(
+            timestamp,
+            node_id,
+            format.into(),
+            count.0.unwrap_or(1),
+            cfg.max_count,
+        )
+        .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidBatchRes::batch(
+            &uuids,
+            output.0.unwrap_or_default().into(),
+        ))
     }
 
     /// Generate a v7 UUID
     #[oai(path = "/v7", method = "get")]
+    #[allow(clippy::too_many_arguments)]
     async fn uuid_v7(
         &self,
-        /// An optional timestamp to use for the UUID. If not provided, the current time will be used.
-        timestamp: Query<Option<u64>>,
+        /// An optional timestamp to use for the UUID, as a number or in RFC 3339. If not provided, the current time will be used.
+        timestamp: Query<Option<String>>,
+
+        /// An optional unit of a numeric timestamp. If not provided, 100-nanosecond ticks since 1582-10-15 will be used.
+        timestamp_unit: Query<Option<UuidTimestampUnit>>,
 
         /// An optional counter to use for the UUID. If not provided, 0 will be used.
         counter: Query<Option<u16>>,
 
+        /// An optional method to fill the 12 `rand_a` bits. If not provided, random bits will be used.
+        method: Query<Option<UuidV7Method>>,
+
         /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
         format: Query<Option<UuidFormat>>,
-    ) -> Result<UuidRes> {
-        let timestamp = timestamp.and_then(|timestamp| {
-            counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-        });
+
+        /// An optional number of UUIDs to generate, limited by the server config.
+        count: Query<Option<usize>>,
+
+        /// An optional output of a batch. If not provided, one UUID per line will be returned.
+        output: Query<Option<UuidOutput>>,
+
+        cfg: Data<&UuidConfig>,
+    ) -> Result<UuidBatchRes> {
+        let timestamp = resolve_timestamp(timestamp.0, timestamp_unit.0, counter.0)
+            .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+        let method = method.0.unwrap_or_default().into();
         let format = format.0.unwrap_or_default();
 
-        let uuid = httpbin::data::uuid::new_v7(timestamp, format.into());
+        if count.is_none() && output.is_none() {
+            let uuid = httpbin::data::uuid::new_v7(timestamp, method, format.into());
 
-        Ok(UuidRes::Ok(PlainText(uuid)))
+            return Ok(UuidBatchRes::single(uuid));
+        }
+
+        let uuids = httpbin::data::uuid::new_v7_batch(
+            timestamp,
+            method,
+            format.into(),
+            count.0.unwrap_or(1),
+            cfg.max_count,
+        )
+        .map_err(|e| UuidBatchRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidBatchRes::batch(
+            &uuids,
+            output.0.unwrap_or_default().into(),
+        ))
     }
 
     /// Generate a v8 UUID
@@ -244,4 +641,73 @@ impl Api {
 
         Ok(UuidRes::Ok(PlainText(uuid)))
     }
+
+    /// Generate the nil UUID
+    #[oai(path = "/nil", method = "get")]
+    async fn uuid_nil(
+        &self,
+        /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
+        format: Query<Option<UuidFormat>>,
+    ) -> PlainText<String> {
+        PlainText(httpbin::data::uuid::new_nil(
+            format.0.unwrap_or_default().into(),
+        ))
+    }
+
+    /// Generate the max UUID
+    #[oai(path = "/max", method = "get")]
+    async fn uuid_max(
+        &self,
+        /// An optional format to use for the UUID. If not provided, the default format (hyphenated) will be used.
+        format: Query<Option<UuidFormat>>,
+    ) -> PlainText<String> {
+        PlainText(httpbin::data::uuid::new_max(
+            format.0.unwrap_or_default().into(),
+        ))
+    }
+
+    /// Inspect a UUID
+    #[oai(path = "/inspect", method = "get")]
+    async fn uuid_inspect(
+        &self,
+        /// The UUID to inspect in any of the supported formats, or `nil` or `max`
+        uuid: Query<String>,
+    ) -> Result<UuidInspectRes> {
+        let inspection = httpbin::data::uuid::inspect(&uuid)
+            .map_err(|e| UuidInspectRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidInspectRes::Ok(Json(inspection.into())))
+    }
+
+    /// Convert a UUID
+    #[oai(path = "/convert", method = "get")]
+    async fn uuid_convert(
+        &self,
+        /// The UUID to convert in any of the supported formats, or `nil` or `max`
+        uuid: Query<String>,
+
+        /// An optional version to reorder a v1 or v6 UUID into.
+        version: Query<Option<UuidConvertVersion>>,
+
+        /// An optional representation of the result. If not provided, the default format (hyphenated) will be used.
+        to: Query<Option<UuidRepresentation>>,
+
+        /// An optional engine for the `base64` representation. If not provided, the standard engine will be used.
+        engine: Query<Option<Base64Engine>>,
+    ) -> Result<UuidConvertRes> {
+        let to: httpbin::data::uuid::UuidRepresentation = to.0.unwrap_or_default().into();
+
+        let uuid = httpbin::data::uuid::convert(
+            &uuid,
+            version.0.map(Into::into),
+            to,
+            engine.0.map(Into::into),
+        )
+        .map_err(|e| UuidConvertRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(UuidConvertRes::Ok(
+            Binary(uuid),
+            to.content_type().to_string(),
+        ))
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use poem::{
-    http::{HeaderMap, Method, Uri},
+    http::{HeaderMap, Method, Uri, Version},
     web::{
         headers::{ContentType, HeaderMapExt},
         RealIp,
@@ -23,6 +23,10 @@ enum HttpMethodTag {
 struct Http {
     method: String,
     uri: String,
+
+    /// The HTTP version of the request, such as `HTTP/1.1` or `HTTP/2.0`
+    protocol: String,
+
     headers: HashMap<String, String>,
     origin: Option<String>,
     query: Option<HashMap<String, String>>,
@@ -40,11 +44,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// The request's POST parameters
@@ -53,11 +59,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// The request's PUT parameters
@@ -66,11 +74,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// The request's DELETE parameters
@@ -79,11 +89,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// The request's PATCH parameters
@@ -92,11 +104,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// Returns anything passed in request data.
@@ -113,11 +127,13 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
     ) -> Json<Http> {
-        self.anything(method, uri, header_map, origin, body).await
+        self.anything(method, uri, version, header_map, origin, body)
+            .await
     }
 
     /// Returns anything passed in request data.
@@ -134,6 +150,7 @@ impl Api {
         &self,
         method: Method,
         uri: &Uri,
+        version: Version,
         header_map: &HeaderMap,
         origin: RealIp,
         body: Vec<u8>,
@@ -186,6 +203,7 @@ impl Api {
         Json(Http {
             method: method.to_string(),
             uri: uri.to_string(),
+            protocol: format!("{version:?}"),
             headers,
             origin: origin.0.map(|origin| origin.to_string()),
             query,
@@ -1,11 +1,17 @@
-use anyhow::Result;
-use httpbin::cli::Cli;
-use poem::{listener::TcpListener, middleware, EndpointExt, Route, Server};
+use anyhow::{bail, Result};
+use httpbin::{
+    cli::Cli,
+    tls::{Ca, Tls},
+};
+use poem::{middleware, EndpointExt, Route};
 use poem_openapi::{ContactObject, ExternalDocumentObject, OpenApiService, ServerObject};
 
 mod data;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
+mod websocket;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -16,8 +22,21 @@ async fn main() -> Result<()> {
     }
     tracing_subscriber::fmt::init();
 
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    if tls.as_ref().is_some_and(|tls| tls.http3) {
+        bail!("poem-openapi cannot serve HTTP/3, so tls.http3 cannot be set");
+    }
+    let tls_config = tls.as_ref().map(Tls::server_config).transpose()?;
+    let listeners = cfg.listeners();
+
     let mut api_service = OpenApiService::new(
-        (http_method::Api, request_inspection::Api, data::api()),
+        (
+            http_method::Api,
+            request_inspection::Api,
+            data::api(),
+            sse::Api,
+            tls::Api,
+        ),
         "httpbin-rs",
         env!("CARGO_PKG_VERSION"),
     )
@@ -46,21 +65,25 @@ async fn main() -> Result<()> {
     let spec_json = api_service.spec_endpoint();
     let spec_yaml = api_service.spec_endpoint_yaml();
 
-    Ok(Server::new(TcpListener::bind((cfg.ip, cfg.port)))
-        .run(
-            Route::new()
-                .nest("/", api_service)
-                .nest("/swagger", swagger)
-                .nest("/rapidoc", rapidoc)
-                .nest("/redoc", redoc)
-                .nest("/openapi-explorer", openapi_explorer)
-                .nest("/spec/json", spec_json)
-                .nest("/spec/yaml", spec_yaml)
-                .with(middleware::Cors::new().allow_origins_fn(|_| true))
-                .with(middleware::NormalizePath::new(
-                    middleware::TrailingSlash::Trim,
-                ))
-                .with(middleware::Tracing),
-        )
-        .await?)
+    let app = websocket::api(Route::new())
+        .nest("/", api_service)
+        .nest("/swagger", swagger)
+        .nest("/rapidoc", rapidoc)
+        .nest("/redoc", redoc)
+        .nest("/openapi-explorer", openapi_explorer)
+        .nest("/spec/json", spec_json)
+        .nest("/spec/yaml", spec_yaml)
+        .data(cfg.uuid)
+        .data(cfg.fake)
+        .data(cfg.compress)
+        .data(Ca::from(tls.as_ref()))
+        .with(middleware::Cors::new().allow_origins_fn(|_| true))
+        .with(middleware::NormalizePath::new(
+            middleware::TrailingSlash::Trim,
+        ))
+        .with(middleware::Tracing);
+
+    httpbin::poem::serve(listeners, tls_config, app).await?;
+
+    Ok(())
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use poem::{
-    http::HeaderMap,
+    http::{header::CONTENT_TYPE, HeaderMap},
     web::{
         headers::{self, HeaderMapExt},
         RealIp,
@@ -10,7 +10,7 @@ use poem::{
 use poem_openapi::{
     payload::{Json, PlainText},
     types::Example,
-    ApiResponse, Object, OpenApi, Tags,
+    ApiResponse, Enum, Object, OpenApi, Tags,
 };
 
 #[derive(Tags)]
@@ -89,6 +89,89 @@ enum UserAgentRes {
     BadRequest(PlainText<String>),
 }
 
+/// The category of a sniffed MIME type
+#[derive(Debug, Clone, Copy, Enum)]
+#[oai(rename_all = "lowercase")]
+enum MimeCategory {
+    App,
+    Archive,
+    Audio,
+    Book,
+    Doc,
+    Font,
+    Image,
+    Text,
+    Video,
+    Custom,
+}
+
+impl From<httpbin::inspect::MimeCategory> for MimeCategory {
+    fn from(category: httpbin::inspect::MimeCategory) -> Self {
+        match category {
+            httpbin::inspect::MimeCategory::App => MimeCategory::App,
+            httpbin::inspect::MimeCategory::Archive => MimeCategory::Archive,
+            httpbin::inspect::MimeCategory::Audio => MimeCategory::Audio,
+            httpbin::inspect::MimeCategory::Book => MimeCategory::Book,
+            httpbin::inspect::MimeCategory::Doc => MimeCategory::Doc,
+            httpbin::inspect::MimeCategory::Font => MimeCategory::Font,
+            httpbin::inspect::MimeCategory::Image => MimeCategory::Image,
+            httpbin::inspect::MimeCategory::Text => MimeCategory::Text,
+            httpbin::inspect::MimeCategory::Video => MimeCategory::Video,
+            httpbin::inspect::MimeCategory::Custom => MimeCategory::Custom,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Object)]
+#[oai(example)]
+struct MimeInspection {
+    /// The sniffed MIME type, `text/plain` for unrecognized UTF-8 and
+    /// `application/octet-stream` for other unrecognized data
+    mime_type: String,
+
+    /// The usual file extension of the sniffed type, if recognized
+    extension: Option<String>,
+
+    /// The category of the sniffed type, if recognized
+    category: Option<MimeCategory>,
+
+    /// Whether the data is valid UTF-8
+    utf8: bool,
+
+    /// The declared MIME type without parameters, if any
+    declared: Option<String>,
+
+    /// Whether the declared MIME type differs from the sniffed one, if declared,
+    /// where any textual type agrees with unrecognized UTF-8
+    mismatch: Option<bool>,
+}
+
+impl Example for MimeInspection {
+    fn example() -> Self {
+        Self {
+            mime_type: "image/png".to_string(),
+            extension: Some("png".to_string()),
+            category: Some(MimeCategory::Image),
+            utf8: false,
+            declared: Some("image/jpeg".to_string()),
+            mismatch: Some(true),
+        }
+    }
+}
+
+impl From<httpbin::inspect::MimeInspection> for MimeInspection {
+    fn from(inspection: httpbin::inspect::MimeInspection) -> Self {
+        Self {
+            mime_type: inspection.mime_type.to_string(),
+            extension: inspection.extension.map(str::to_string),
+            category: inspection.category.map(Into::into),
+            utf8: inspection.utf8,
+            declared: inspection.declared,
+            mismatch: inspection.mismatch,
+        }
+    }
+}
+
 pub struct Api;
 
 #[OpenApi(tag = "ReqInspTag::RequestInspection")]
@@ -135,4 +218,14 @@ impl Api {
             )),
         }
     }
+
+    /// Sniff the MIME type of the request body.
+    ///
+    /// The body can be of any type and is compared to its `Content-Type` header.
+    #[oai(path = "/inspect/mime", method = "post")]
+    async fn inspect_mime(&self, headers: &HeaderMap, body: Vec<u8>) -> Json<MimeInspection> {
+        let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+
+        Json(httpbin::inspect::mime(&body, content_type).into())
+    }
 }
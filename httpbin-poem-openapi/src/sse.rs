@@ -0,0 +1,80 @@
+use httpbin::sse::SseOptions;
+use poem::{Body, Result};
+use poem_openapi::{
+    param::{Header, Query},
+    payload::{Binary, PlainText},
+    ApiResponse, OpenApi, Tags,
+};
+
+#[derive(Tags)]
+enum SseTag {
+    /// Stream Server-Sent Events
+    #[oai(rename = "Server-Sent Events")]
+    Sse,
+}
+
+#[derive(ApiResponse)]
+enum SseRes {
+    /// The events, each with an `id`, the `tick` event type and JSON `data` of its id, the count
+    /// and the time it was sent. A `retry` field comes first if requested, and `: heartbeat`
+    /// comments are sent between events if requested.
+    #[oai(status = 200, content_type = "text/event-stream")]
+    Ok(Binary<Body>, #[oai(header = "Cache-Control")] String),
+
+    /// All events were sent before, which stops the client from reconnecting
+    #[oai(status = 204)]
+    NoContent,
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(tag = "SseTag::Sse")]
+impl Api {
+    /// Stream numbered events
+    #[oai(path = "/sse", method = "get")]
+    async fn sse(
+        &self,
+        /// An optional number of events, up to 10000. If not provided, 10 will be used.
+        count: Query<Option<u64>>,
+
+        /// An optional number of milliseconds between events. If not provided, 1000 will be used.
+        interval: Query<Option<u64>>,
+
+        /// An optional number of milliseconds for the client to wait before reconnecting.
+        retry: Query<Option<u64>>,
+
+        /// An optional number of milliseconds between comment heartbeats. If not provided, no heartbeats will be sent.
+        heartbeat: Query<Option<u64>>,
+
+        /// An optional number of events to send before dropping the connection without ending the stream.
+        drop_after: Query<Option<u64>>,
+
+        /// The id of the last event received, to resume after it
+        #[oai(name = "Last-Event-ID")]
+        last_event_id: Header<Option<String>>,
+    ) -> Result<SseRes> {
+        let events = httpbin::sse::events(
+            SseOptions {
+                count: count.0,
+                interval: interval.0,
+                retry: retry.0,
+                heartbeat: heartbeat.0,
+                drop_after: drop_after.0,
+            },
+            last_event_id.0.as_deref(),
+        )
+        .map_err(|e| SseRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(match events {
+            Some(events) => SseRes::Ok(
+                Binary(Body::from_bytes_stream(events)),
+                "no-cache".to_string(),
+            ),
+            None => SseRes::NoContent,
+        })
+    }
+}
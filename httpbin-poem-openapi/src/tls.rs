@@ -0,0 +1,99 @@
+use httpbin::tls::{Ca, ClientCertificates};
+use poem::{web::Data, Request, Result};
+use poem_openapi::{
+    payload::{Json, PlainText},
+    ApiResponse, Object, OpenApi, Tags,
+};
+
+#[derive(Tags)]
+enum TlsTag {
+    /// Inspect the TLS setup of the server
+    #[oai(rename = "TLS")]
+    Tls,
+}
+
+#[derive(ApiResponse)]
+enum CaRes {
+    /// The CA certificate in PEM
+    #[oai(status = 200, content_type = "application/x-pem-file")]
+    Ok(PlainText<String>),
+
+    /// The certificate was read from the config instead of generated at startup
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+}
+
+/// A certificate presented by a client
+#[derive(Object)]
+struct CertificateInfo {
+    subject: String,
+    issuer: String,
+    subject_alt_names: Vec<String>,
+
+    /// The serial number in colon separated hex
+    serial: String,
+
+    not_before: String,
+    not_after: String,
+
+    /// The SHA-256 fingerprint of the DER certificate in hex
+    sha256_fingerprint: String,
+}
+
+impl From<httpbin::tls::CertificateInfo> for CertificateInfo {
+    fn from(info: httpbin::tls::CertificateInfo) -> Self {
+        Self {
+            subject: info.subject,
+            issuer: info.issuer,
+            subject_alt_names: info.subject_alt_names,
+            serial: info.serial,
+            not_before: info.not_before,
+            not_after: info.not_after,
+            sha256_fingerprint: info.sha256_fingerprint,
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum ClientCertRes {
+    /// The chain the client presented, leaf first
+    #[oai(status = 200)]
+    Ok(Json<Vec<CertificateInfo>>),
+
+    /// Bad request
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+}
+
+pub struct Api;
+
+#[OpenApi(tag = "TlsTag::Tls")]
+impl Api {
+    /// The CA which signed the self-signed certificate generated at startup
+    #[oai(path = "/tls/ca.pem", method = "get")]
+    async fn ca_pem(&self, ca: Data<&Ca>) -> CaRes {
+        let Data(Ca(pem)) = ca;
+        match pem {
+            Some(pem) => CaRes::Ok(PlainText(pem.clone())),
+            None => CaRes::NotFound(PlainText(
+                "the certificate was not generated at startup".to_string(),
+            )),
+        }
+    }
+    /// The certificates the client presented over mutual TLS, empty if it presented none
+    #[oai(path = "/client-cert", method = "get")]
+    async fn client_cert(&self, req: &Request) -> Result<ClientCertRes> {
+        let certs = req
+            .extensions()
+            .get::<ClientCertificates>()
+            .cloned()
+            .unwrap_or_default();
+        let certs = certs
+            .inspect()
+            .map_err(|e| ClientCertRes::BadRequest(PlainText(e.to_string())))?;
+
+        Ok(ClientCertRes::Ok(Json(
+            certs.into_iter().map(Into::into).collect(),
+        )))
+    }
+}
@@ -0,0 +1,58 @@
+use httpbin::websocket::EchoOptions;
+use poem::{
+    get, handler,
+    http::{HeaderMap, StatusCode},
+    web::Query,
+    IntoResponse, Request, Response, Route,
+};
+
+/// WebSocket endpoints cannot be described by OpenAPI, so they are served next to it
+pub fn api(route: Route) -> Route {
+    route.at("/ws/echo", get(echo))
+}
+
+/// The values of a header joined by commas, empty if absent
+fn header_values(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Echo text and binary messages
+#[handler]
+fn echo(req: &Request, Query(options): Query<EchoOptions>) -> Response {
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let handshake = match httpbin::websocket::handshake(
+        header("upgrade"),
+        header("sec-websocket-key"),
+        header("sec-websocket-version"),
+        &header_values(headers, "sec-websocket-protocol"),
+        &header_values(headers, "sec-websocket-extensions"),
+        options,
+    ) {
+        Ok(handshake) => handshake,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let mut res = Response::builder().status(StatusCode::SWITCHING_PROTOCOLS);
+    for (name, value) in handshake.headers() {
+        res = res.header(name, value);
+    }
+
+    let on_upgrade = match req.take_upgrade() {
+        Ok(on_upgrade) => on_upgrade,
+        Err(e) => return poem::Error::from(e).into_response(),
+    };
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            let _ = httpbin::websocket::echo(upgraded, handshake).await;
+        }
+    });
+
+    res.finish()
+}
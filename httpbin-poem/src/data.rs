@@ -2,9 +2,27 @@ use poem::Route;
 
 use crate::utils::RouteExt;
 
+mod barcode;
 mod base64;
+mod codec;
+mod compress;
+mod fake;
+mod hmac;
+mod ids;
+mod random;
+mod time;
 mod uuid;
 
 pub fn api(route: Route) -> Route {
-    route.attach(base64::api).attach(uuid::api)
+    route
+        .attach(barcode::api)
+        .attach(base64::api)
+        .attach(codec::api)
+        .attach(compress::api)
+        .attach(fake::api)
+        .attach(hmac::api)
+        .attach(ids::api)
+        .attach(random::api)
+        .attach(time::api)
+        .attach(uuid::api)
 }
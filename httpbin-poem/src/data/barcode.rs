@@ -0,0 +1,55 @@
+use anyhow::anyhow;
+use httpbin::data::barcode::{BarcodeFormat, QrEcc, Symbology};
+use poem::{
+    get, handler,
+    web::{Path, Query},
+    Response, Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Code {
+    data: String,
+    size: Option<usize>,
+    format: Option<BarcodeFormat>,
+}
+
+#[derive(Deserialize)]
+struct Ecc {
+    ecc: Option<QrEcc>,
+}
+
+pub fn api(route: Route) -> Route {
+    route
+        .at("/qrcode", get(qrcode))
+        .at("/barcode/:symbology", get(barcode))
+}
+
+#[handler]
+fn qrcode(
+    Query(Code { data, size, format }): Query<Code>,
+    Query(Ecc { ecc }): Query<Ecc>,
+) -> Result<Response> {
+    let format = format.unwrap_or_default();
+
+    let data = httpbin::data::barcode::qrcode(&data, ecc, size, format).map_err(|e| anyhow!(e))?;
+
+    Ok(Response::builder()
+        .header("Content-Type", format.content_type())
+        .body(data))
+}
+
+#[handler]
+fn barcode(
+    Path(symbology): Path<Symbology>,
+    Query(Code { data, size, format }): Query<Code>,
+) -> Result<Response> {
+    let format = format.unwrap_or_default();
+
+    let data =
+        httpbin::data::barcode::barcode(&data, symbology, size, format).map_err(|e| anyhow!(e))?;
+
+    Ok(Response::builder()
+        .header("Content-Type", format.content_type())
+        .body(data))
+}
@@ -1,9 +1,11 @@
 use anyhow::anyhow;
-use httpbin::data::base64::Base64Engine;
+use httpbin::data::base64::{
+    Base64Engine, Base64EngineInfo, Base64Padding, Base64Wrap, Base64WrapMode,
+};
 use poem::{
-    handler, post,
-    web::{Path, Query},
-    IntoResponse, Result, Route,
+    get, handler, post,
+    web::{Json, Path, Query},
+    Body, IntoResponse, Result, Route,
 };
 use serde::Deserialize;
 
@@ -11,6 +13,13 @@ use serde::Deserialize;
 struct Base64Config {
     alphabet: Option<String>,
     pad: Option<bool>,
+    padding: Option<Base64Padding>,
+}
+
+#[derive(Deserialize)]
+struct Wrap {
+    wrap: Option<Base64WrapMode>,
+    label: Option<String>,
 }
 
 enum Base64Res {
@@ -34,7 +43,10 @@ pub fn api(route: Route) -> Route {
         "/base64",
         Route::new()
             .at("/encode/:engine", post(base64_encode))
-            .at("/decode/:engine", post(base64_decode)),
+            .at("/decode/:engine", post(base64_decode))
+            .at("/stream/encode/:engine", post(base64_encode_stream))
+            .at("/stream/decode/:engine", post(base64_decode_stream))
+            .at("/engines", get(base64_engines)),
     )
 }
 
@@ -42,17 +54,32 @@ pub fn api(route: Route) -> Route {
 fn base64_encode(
     data: Vec<u8>,
     Path(engine): Path<Base64Engine>,
-    Query(Base64Config { alphabet, pad }): Query<Base64Config>,
+    Query(Base64Config {
+        alphabet,
+        pad,
+        padding,
+    }): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
 ) -> Result<Base64Res> {
     let config = match engine {
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: alphabet.unwrap_or_default(),
             pad: pad.unwrap_or_default(),
+            padding,
         }),
         _ => None,
     };
 
-    let encoded = httpbin::data::base64::encode(&data, engine, config).map_err(|e| anyhow!(e))?;
+    let encoded = match wrap {
+        Some(wrap) => httpbin::data::base64::encode_wrapped(
+            &data,
+            engine,
+            config,
+            Base64Wrap::new(wrap, label),
+        ),
+        None => httpbin::data::base64::encode(&data, engine, config),
+    }
+    .map_err(|e| anyhow!(e))?;
 
     Ok(Base64Res::OkText(encoded))
 }
@@ -61,12 +88,17 @@ fn base64_encode(
 fn base64_decode(
     data: String,
     Path(engine): Path<Base64Engine>,
-    Query(Base64Config { alphabet, pad }): Query<Base64Config>,
+    Query(Base64Config {
+        alphabet,
+        pad,
+        padding,
+    }): Query<Base64Config>,
 ) -> Result<Base64Res> {
     let config = match engine {
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: alphabet.unwrap_or_default(),
             pad: pad.unwrap_or_default(),
+            padding,
         }),
         _ => None,
     };
@@ -87,3 +119,65 @@ fn base64_decode(
         }
     }
 }
+
+#[handler]
+fn base64_encode_stream(
+    data: Body,
+    Path(engine): Path<Base64Engine>,
+    Query(Base64Config {
+        alphabet,
+        pad,
+        padding,
+    }): Query<Base64Config>,
+    Query(Wrap { wrap, label }): Query<Wrap>,
+) -> Result<poem::Response> {
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: alphabet.unwrap_or_default(),
+            pad: pad.unwrap_or_default(),
+            padding,
+        }),
+        _ => None,
+    };
+    let wrap = wrap.map(|wrap| Base64Wrap::new(wrap, label));
+
+    let encoded =
+        httpbin::data::base64::encode_stream(data.into_bytes_stream(), engine, config, wrap)
+            .map_err(|e| anyhow!(e))?;
+
+    Ok(poem::Response::builder()
+        .header("Content-Type", "text/plain; charset=utf-8")
+        .body(Body::from_bytes_stream(encoded)))
+}
+
+#[handler]
+fn base64_decode_stream(
+    data: Body,
+    Path(engine): Path<Base64Engine>,
+    Query(Base64Config {
+        alphabet,
+        pad,
+        padding,
+    }): Query<Base64Config>,
+) -> Result<poem::Response> {
+    let config = match engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: alphabet.unwrap_or_default(),
+            pad: pad.unwrap_or_default(),
+            padding,
+        }),
+        _ => None,
+    };
+
+    let decoded = httpbin::data::base64::decode_stream(data.into_bytes_stream(), engine, config)
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(poem::Response::builder()
+        .header("Content-Type", "application/octet-stream")
+        .body(Body::from_bytes_stream(decoded)))
+}
+
+#[handler]
+fn base64_engines() -> Json<Vec<Base64EngineInfo>> {
+    Json(httpbin::data::base64::engines())
+}
@@ -0,0 +1,70 @@
+use anyhow::anyhow;
+use httpbin::data::codec::{Codec, CodecConfig, PercentSet};
+use poem::{
+    handler, post,
+    web::{Path, Query},
+    IntoResponse, Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Config {
+    set: Option<PercentSet>,
+}
+
+enum CodecRes {
+    OkText(String),
+    OkBinary(Vec<u8>, String),
+}
+
+impl IntoResponse for CodecRes {
+    fn into_response(self) -> poem::Response {
+        match self {
+            CodecRes::OkText(s) => s.into_response(),
+            CodecRes::OkBinary(b, content_type) => poem::Response::builder()
+                .header("Content-Type", content_type)
+                .body(b),
+        }
+    }
+}
+
+pub fn api(route: Route) -> Route {
+    route
+        .at("/encode/:codec", post(codec_encode))
+        .at("/decode/:codec", post(codec_decode))
+}
+
+#[handler]
+fn codec_encode(
+    data: Vec<u8>,
+    Path(codec): Path<Codec>,
+    Query(Config { set }): Query<Config>,
+) -> Result<CodecRes> {
+    let config = CodecConfig {
+        percent_set: set.unwrap_or_default(),
+    };
+
+    let encoded =
+        httpbin::data::codec::encode(&data, codec, Some(config)).map_err(|e| anyhow!(e))?;
+
+    Ok(CodecRes::OkText(encoded))
+}
+
+#[handler]
+fn codec_decode(data: String, Path(codec): Path<Codec>) -> Result<CodecRes> {
+    let decoded = httpbin::data::codec::decode(&data, codec).map_err(|e| anyhow!(e))?;
+
+    match String::from_utf8(decoded.clone()) {
+        Ok(s) => Ok(CodecRes::OkText(s)),
+        Err(_) => {
+            let kind = infer::get(&decoded);
+
+            Ok(CodecRes::OkBinary(
+                decoded,
+                kind.map(|k| k.mime_type())
+                    .unwrap_or("application/octet-stream")
+                    .to_string(),
+            ))
+        }
+    }
+}
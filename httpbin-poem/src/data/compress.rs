@@ -0,0 +1,74 @@
+use anyhow::anyhow;
+use httpbin::{cli::CompressConfig, data::compress::Compression};
+use poem::{
+    handler, post,
+    web::{Data, Path, Query},
+    IntoResponse, Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Level {
+    level: Option<i32>,
+}
+
+enum CompressRes {
+    OkText(String),
+    OkBinary(Vec<u8>, String),
+}
+
+impl IntoResponse for CompressRes {
+    fn into_response(self) -> poem::Response {
+        match self {
+            CompressRes::OkText(s) => s.into_response(),
+            CompressRes::OkBinary(b, content_type) => poem::Response::builder()
+                .header("Content-Type", content_type)
+                .body(b),
+        }
+    }
+}
+
+pub fn api(route: Route) -> Route {
+    route
+        .at("/compress/:algo", post(compress))
+        .at("/decompress/:algo", post(decompress))
+}
+
+#[handler]
+fn compress(
+    data: Vec<u8>,
+    Path(algo): Path<Compression>,
+    Query(Level { level }): Query<Level>,
+) -> Result<CompressRes> {
+    let compressed =
+        httpbin::data::compress::compress(&data, algo, level).map_err(|e| anyhow!(e))?;
+
+    Ok(CompressRes::OkBinary(
+        compressed,
+        algo.content_type().to_string(),
+    ))
+}
+
+#[handler]
+fn decompress(
+    data: Vec<u8>,
+    Path(algo): Path<Compression>,
+    Data(cfg): Data<&CompressConfig>,
+) -> Result<CompressRes> {
+    let decompressed =
+        httpbin::data::compress::decompress(&data, algo, cfg.max_size).map_err(|e| anyhow!(e))?;
+
+    match String::from_utf8(decompressed.clone()) {
+        Ok(s) => Ok(CompressRes::OkText(s)),
+        Err(_) => {
+            let kind = infer::get(&decompressed);
+
+            Ok(CompressRes::OkBinary(
+                decompressed,
+                kind.map(|k| k.mime_type())
+                    .unwrap_or("application/octet-stream")
+                    .to_string(),
+            ))
+        }
+    }
+}
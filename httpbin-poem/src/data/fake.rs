@@ -0,0 +1,57 @@
+use anyhow::anyhow;
+use httpbin::{
+    cli::FakeConfig,
+    data::fake::{FakeKind, FakeLocale},
+};
+use poem::{
+    get, handler, post,
+    web::{Data, Json, Path, Query},
+    Result, Route,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct CountSeed {
+    count: Option<usize>,
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Locale {
+    locale: Option<FakeLocale>,
+}
+
+pub fn api(route: Route) -> Route {
+    route.nest(
+        "/fake",
+        Route::new()
+            .at("/schema", post(fake_schema))
+            .at("/:kind", get(fake_records)),
+    )
+}
+
+#[handler]
+fn fake_schema(
+    Data(cfg): Data<&FakeConfig>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+    Json(schema): Json<Value>,
+) -> Result<Json<Value>> {
+    let instances =
+        httpbin::data::fake::schema(&schema, count, seed, cfg.max_count).map_err(|e| anyhow!(e))?;
+
+    Ok(Json(instances))
+}
+
+#[handler]
+fn fake_records(
+    Data(cfg): Data<&FakeConfig>,
+    Path(kind): Path<FakeKind>,
+    Query(Locale { locale }): Query<Locale>,
+    Query(CountSeed { count, seed }): Query<CountSeed>,
+) -> Result<Json<Value>> {
+    let records = httpbin::data::fake::records(kind, locale, count, seed, cfg.max_count)
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(Json(records))
+}
@@ -0,0 +1,72 @@
+use anyhow::anyhow;
+use httpbin::data::{
+    base64::Base64Engine,
+    hmac::{HmacAlgorithm, HmacKeyEncoding, HmacMac, HmacMacEncoding, HmacVerification},
+};
+use poem::{
+    handler, post,
+    web::{Json, Path, Query},
+    Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Key {
+    key: String,
+    key_encoding: Option<HmacKeyEncoding>,
+    engine: Option<Base64Engine>,
+}
+
+#[derive(Deserialize)]
+struct Mac {
+    mac: String,
+    mac_encoding: Option<HmacMacEncoding>,
+}
+
+pub fn api(route: Route) -> Route {
+    route.nest(
+        "/hmac",
+        Route::new()
+            .at("/:algorithm/sign", post(hmac_sign))
+            .at("/:algorithm/verify", post(hmac_verify)),
+    )
+}
+
+#[handler]
+fn hmac_sign(
+    data: Vec<u8>,
+    Path(algorithm): Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+) -> Result<Json<HmacMac>> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(|e| anyhow!(e))?;
+
+    let mac = httpbin::data::hmac::sign(algorithm, &key, &data);
+
+    Ok(Json(mac.as_slice().into()))
+}
+
+#[handler]
+fn hmac_verify(
+    data: Vec<u8>,
+    Path(algorithm): Path<HmacAlgorithm>,
+    Query(Key {
+        key,
+        key_encoding,
+        engine,
+    }): Query<Key>,
+    Query(Mac { mac, mac_encoding }): Query<Mac>,
+) -> Result<Json<HmacVerification>> {
+    let key = httpbin::data::hmac::decode_key(&key, key_encoding.unwrap_or_default(), engine)
+        .map_err(|e| anyhow!(e))?;
+    let mac = httpbin::data::hmac::decode_mac(&mac, mac_encoding.unwrap_or_default())
+        .map_err(|e| anyhow!(e))?;
+
+    let verification = httpbin::data::hmac::verify(algorithm, &key, &data, &mac);
+
+    Ok(Json(verification))
+}
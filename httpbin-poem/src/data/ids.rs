@@ -0,0 +1,40 @@
+use anyhow::anyhow;
+use httpbin::data::ids::{IdInspection, IdKind, IdOptions};
+use poem::{
+    get, handler,
+    web::{Json, Path, Query},
+    Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Id {
+    id: String,
+}
+
+pub fn api(route: Route) -> Route {
+    route.nest(
+        "/id",
+        Route::new()
+            .at("/:kind", get(id_generate))
+            .at("/:kind/parse", get(id_parse)),
+    )
+}
+
+#[handler]
+fn id_generate(Path(kind): Path<IdKind>, Query(options): Query<IdOptions>) -> Result<String> {
+    let id = httpbin::data::ids::generate(kind, options).map_err(|e| anyhow!(e))?;
+
+    Ok(id)
+}
+
+#[handler]
+fn id_parse(
+    Path(kind): Path<IdKind>,
+    Query(Id { id }): Query<Id>,
+    Query(options): Query<IdOptions>,
+) -> Result<Json<IdInspection>> {
+    let inspection = httpbin::data::ids::parse(kind, &id, options).map_err(|e| anyhow!(e))?;
+
+    Ok(Json(inspection))
+}
@@ -0,0 +1,90 @@
+use anyhow::anyhow;
+use httpbin::data::random::{RandomCharset, RandomItems};
+use poem::{
+    get, handler,
+    web::{Json, Query},
+    Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Seed {
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Range<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct Charset {
+    charset: Option<RandomCharset>,
+    length: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Items {
+    items: RandomItems,
+}
+
+pub fn api(route: Route) -> Route {
+    route.nest(
+        "/random",
+        Route::new()
+            .at("/int", get(random_int))
+            .at("/float", get(random_float))
+            .at("/string", get(random_string))
+            .at("/choice", get(random_choice))
+            .at("/shuffle", get(random_shuffle)),
+    )
+}
+
+#[handler]
+fn random_int(
+    Query(Range { min, max }): Query<Range<i64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String> {
+    let int = httpbin::data::random::int(min, max, seed).map_err(|e| anyhow!(e))?;
+
+    Ok(int.to_string())
+}
+
+#[handler]
+fn random_float(
+    Query(Range { min, max }): Query<Range<f64>>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String> {
+    let float = httpbin::data::random::float(min, max, seed).map_err(|e| anyhow!(e))?;
+
+    Ok(float.to_string())
+}
+
+#[handler]
+fn random_string(
+    Query(Charset { charset, length }): Query<Charset>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String> {
+    let string = httpbin::data::random::string(charset, length, seed).map_err(|e| anyhow!(e))?;
+
+    Ok(string)
+}
+
+#[handler]
+fn random_choice(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Result<String> {
+    let choice = httpbin::data::random::choice(items, seed).map_err(|e| anyhow!(e))?;
+
+    Ok(choice)
+}
+
+#[handler]
+fn random_shuffle(
+    Query(Items { items }): Query<Items>,
+    Query(Seed { seed }): Query<Seed>,
+) -> Json<Vec<String>> {
+    Json(httpbin::data::random::shuffle(items, seed))
+}
@@ -0,0 +1,68 @@
+use anyhow::anyhow;
+use httpbin::data::time::{TimeFormat, TimeInspection};
+use poem::{
+    get, handler,
+    web::{Json, Query},
+    Result, Route,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Now {
+    format: Option<TimeFormat>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Parse {
+    time: String,
+    from: Option<String>,
+    tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Format {
+    format: Option<TimeFormat>,
+}
+
+pub fn api(route: Route) -> Route {
+    route.nest(
+        "/time",
+        Route::new()
+            .at("/now", get(time_now))
+            .at("/parse", get(time_parse))
+            .at("/convert", get(time_convert)),
+    )
+}
+
+#[handler]
+fn time_now(Query(Now { format, tz }): Query<Now>) -> Result<String> {
+    let now = httpbin::data::time::now(format.unwrap_or_default(), tz.as_deref())
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(now)
+}
+
+#[handler]
+fn time_parse(Query(Parse { time, from, tz }): Query<Parse>) -> Result<Json<TimeInspection>> {
+    let inspection = httpbin::data::time::parse(&time, from.as_deref(), tz.as_deref())
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(Json(inspection))
+}
+
+#[handler]
+fn time_convert(
+    Query(Parse { time, from, tz }): Query<Parse>,
+    Query(Format { format }): Query<Format>,
+) -> Result<String> {
+    let time = httpbin::data::time::convert(
+        &time,
+        from.as_deref(),
+        tz.as_deref(),
+        format.unwrap_or_default(),
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(time)
+}
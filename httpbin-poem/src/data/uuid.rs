@@ -1,6 +1,19 @@
 use anyhow::anyhow;
-use httpbin::data::uuid::{UuidBuffer, UuidFormat, UuidNamespace, UuidNodeId};
-use poem::{get, handler, web::Query, Result, Route};
+use httpbin::{
+    cli::UuidConfig,
+    data::{
+        base64::Base64Engine,
+        uuid::{
+            UuidBuffer, UuidConvertVersion, UuidFormat, UuidInspection, UuidNamespace, UuidNodeId,
+            UuidOutput, UuidRepresentation, UuidTimestamp, UuidTimestampUnit, UuidV7Method,
+        },
+    },
+};
+use poem::{
+    get, handler,
+    web::{Data, Json, Query},
+    IntoResponse, Response, Result, Route,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -8,15 +21,41 @@ struct Format {
     format: Option<UuidFormat>,
 }
 
+#[derive(Deserialize)]
+struct Batch {
+    count: Option<usize>,
+    output: Option<UuidOutput>,
+}
+
 #[derive(Deserialize)]
 struct TimestampCounter {
-    timestamp: Option<u64>,
+    timestamp: Option<UuidTimestamp>,
+    timestamp_unit: Option<UuidTimestampUnit>,
     counter: Option<u16>,
 }
 
+impl TimestampCounter {
+    fn resolve(self) -> Result<Option<(u64, u16)>> {
+        self.timestamp
+            .map(|timestamp| {
+                let ticks = timestamp
+                    .to_ticks(self.timestamp_unit.unwrap_or_default())
+                    .map_err(|e| anyhow!(e))?;
+
+                Ok((ticks, self.counter.unwrap_or(0)))
+            })
+            .transpose()
+    }
+}
+
 #[derive(Deserialize)]
 struct NodeId {
-    node_id: UuidNodeId,
+    node_id: Option<UuidNodeId>,
+}
+
+#[derive(Deserialize)]
+struct Method {
+    method: Option<UuidV7Method>,
 }
 
 #[derive(Deserialize)]
@@ -30,6 +69,19 @@ struct Buf {
     buf: UuidBuffer,
 }
 
+#[derive(Deserialize)]
+struct Inspect {
+    uuid: String,
+}
+
+#[derive(Deserialize)]
+struct Convert {
+    uuid: String,
+    version: Option<UuidConvertVersion>,
+    to: Option<UuidRepresentation>,
+    engine: Option<Base64Engine>,
+}
+
 pub fn api(route: Route) -> Route {
     route.nest(
         "/uuid",
@@ -40,24 +92,42 @@ pub fn api(route: Route) -> Route {
             .at("/v5", get(uuid_v5))
             .at("/v6", get(uuid_v6))
             .at("/v7", get(uuid_v7))
-            .at("/v8", get(uuid_v8)),
+            .at("/v8", get(uuid_v8))
+            .at("/nil", get(uuid_nil))
+            .at("/max", get(uuid_max))
+            .at("/inspect", get(uuid_inspect))
+            .at("/convert", get(uuid_convert)),
     )
 }
 
 #[handler]
 fn uuid_v1(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Data(cfg): Data<&UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(|e| anyhow!(e))?;
+    if count.is_none() && output.is_none() {
+        let uuid =
+            httpbin::data::uuid::new_v1(timestamp, node_id, format).map_err(|e| anyhow!(e))?;
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v1_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 #[handler]
@@ -73,12 +143,23 @@ fn uuid_v3(
 }
 
 #[handler]
-fn uuid_v4(Query(Format { format }): Query<Format>) -> Result<String> {
+fn uuid_v4(
+    Data(cfg): Data<&UuidConfig>,
+    Query(Format { format }): Query<Format>,
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response> {
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v4(format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v4(format);
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v4_batch(format, count.unwrap_or(1), cfg.max_count)
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 #[handler]
@@ -95,33 +176,62 @@ fn uuid_v5(
 
 #[handler]
 fn uuid_v6(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Data(cfg): Data<&UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
     Query(NodeId { node_id }): Query<NodeId>,
     Query(Format { format }): Query<Format>,
-) -> Result<String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response> {
+    let timestamp = timestamp.resolve()?;
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(|e| anyhow!(e))?;
+    if count.is_none() && output.is_none() {
+        let uuid =
+            httpbin::data::uuid::new_v6(timestamp, node_id, format).map_err(|e| anyhow!(e))?;
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v6_batch(
+        timestamp,
+        node_id,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 #[handler]
 fn uuid_v7(
-    Query(TimestampCounter { timestamp, counter }): Query<TimestampCounter>,
+    Data(cfg): Data<&UuidConfig>,
+    Query(timestamp): Query<TimestampCounter>,
+    Query(Method { method }): Query<Method>,
     Query(Format { format }): Query<Format>,
-) -> Result<String> {
-    let timestamp = timestamp.and_then(|timestamp| {
-        counter.map_or(Some((timestamp, 0)), |counter| Some((timestamp, counter)))
-    });
+    Query(Batch { count, output }): Query<Batch>,
+) -> Result<Response> {
+    let timestamp = timestamp.resolve()?;
+    let method = method.unwrap_or_default();
     let format = format.unwrap_or_default();
 
-    let uuid = httpbin::data::uuid::new_v7(timestamp, format);
+    if count.is_none() && output.is_none() {
+        let uuid = httpbin::data::uuid::new_v7(timestamp, method, format);
 
-    Ok(uuid)
+        return Ok(uuid.into_response());
+    }
+
+    let uuids = httpbin::data::uuid::new_v7_batch(
+        timestamp,
+        method,
+        format,
+        count.unwrap_or(1),
+        cfg.max_count,
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    Ok(batch(&uuids, output.unwrap_or_default()))
 }
 
 #[handler]
@@ -135,3 +245,44 @@ fn uuid_v8(
 
     Ok(uuid)
 }
+
+#[handler]
+fn uuid_nil(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_nil(format.unwrap_or_default())
+}
+
+#[handler]
+fn uuid_max(Query(Format { format }): Query<Format>) -> String {
+    httpbin::data::uuid::new_max(format.unwrap_or_default())
+}
+
+#[handler]
+fn uuid_inspect(Query(Inspect { uuid }): Query<Inspect>) -> Result<Json<UuidInspection>> {
+    let inspection = httpbin::data::uuid::inspect(&uuid).map_err(|e| anyhow!(e))?;
+
+    Ok(Json(inspection))
+}
+
+#[handler]
+fn uuid_convert(
+    Query(Convert {
+        uuid,
+        version,
+        to,
+        engine,
+    }): Query<Convert>,
+) -> Result<Response> {
+    let to = to.unwrap_or_default();
+
+    let uuid = httpbin::data::uuid::convert(&uuid, version, to, engine).map_err(|e| anyhow!(e))?;
+
+    Ok(Response::builder()
+        .header("Content-Type", to.content_type())
+        .body(uuid))
+}
+
+fn batch(uuids: &[String], output: UuidOutput) -> Response {
+    Response::builder()
+        .header("Content-Type", output.content_type())
+        .body(httpbin::data::uuid::render(uuids, output))
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use poem::{
     delete, get, handler,
-    http::{HeaderMap, Method, Uri},
+    http::{HeaderMap, Method, Uri, Version},
     patch, post, put,
     web::{
         headers::{ContentType, HeaderMapExt},
@@ -16,6 +16,7 @@ use serde::Serialize;
 struct Http {
     method: String,
     uri: String,
+    protocol: String,
     headers: HashMap<String, String>,
     origin: Option<String>,
     query: Option<HashMap<String, String>>,
@@ -52,6 +53,7 @@ pub fn api(route: Route) -> Route {
 fn anything(
     method: Method,
     uri: &Uri,
+    version: Version,
     header_map: &HeaderMap,
     origin: RealIp,
     body: Vec<u8>,
@@ -104,6 +106,7 @@ fn anything(
     Json(Http {
         method: method.to_string(),
         uri: uri.to_string(),
+        protocol: format!("{version:?}"),
         headers,
         origin: origin.0.map(|origin| origin.to_string()),
         query,
@@ -1,11 +1,20 @@
-use anyhow::Result;
-use httpbin::cli::Cli;
-use poem::{listener::TcpListener, middleware, EndpointExt, Route, Server};
+// Handlers return `poem::Result`, whose error is large by design
+#![allow(clippy::result_large_err)]
+
+use anyhow::{bail, Result};
+use httpbin::{
+    cli::Cli,
+    tls::{Ca, Tls},
+};
+use poem::{middleware, EndpointExt, Route};
 
 mod data;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
 mod utils;
+mod websocket;
 
 use utils::*;
 
@@ -18,17 +27,30 @@ async fn main() -> Result<()> {
     }
     tracing_subscriber::fmt::init();
 
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    if tls.as_ref().is_some_and(|tls| tls.http3) {
+        bail!("poem cannot serve HTTP/3, so tls.http3 cannot be set");
+    }
+    let tls_config = tls.as_ref().map(Tls::server_config).transpose()?;
+
     let app = Route::new()
         .attach(data::api)
         .attach(request_inspection::api)
         .attach(http_method::api)
+        .attach(sse::api)
+        .attach(tls::api)
+        .attach(websocket::api)
+        .data(cfg.uuid.clone())
+        .data(cfg.fake.clone())
+        .data(cfg.compress.clone())
+        .data(Ca::from(tls.as_ref()))
         .with(middleware::Cors::new().allow_origins_fn(|_| true))
         .with(middleware::NormalizePath::new(
             middleware::TrailingSlash::Trim,
         ))
         .with(middleware::Tracing);
 
-    Ok(Server::new(TcpListener::bind((cfg.ip, cfg.port)))
-        .run(app)
-        .await?)
+    httpbin::poem::serve(cfg.listeners(), tls_config, app).await?;
+
+    Ok(())
 }
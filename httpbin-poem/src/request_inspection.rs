@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
 use anyhow::anyhow;
+use httpbin::inspect::MimeInspection;
 use poem::{
     get, handler,
-    http::HeaderMap,
+    http::{header::CONTENT_TYPE, HeaderMap},
+    post,
     web::{Json, RealIp, TypedHeader},
     Result, Route,
 };
@@ -32,6 +34,7 @@ pub fn api(route: Route) -> Route {
         .at("/headers", get(headers))
         .at("/ip", get(ip))
         .at("/user-agent", get(user_agent))
+        .at("/inspect/mime", post(inspect_mime))
 }
 
 #[handler]
@@ -71,3 +74,10 @@ fn user_agent(
         user_agent: user_agent.to_string(),
     })
 }
+
+#[handler]
+fn inspect_mime(header_map: &HeaderMap, body: Vec<u8>) -> Json<MimeInspection> {
+    let content_type = header_map.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+
+    Json(httpbin::inspect::mime(&body, content_type))
+}
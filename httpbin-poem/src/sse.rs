@@ -0,0 +1,32 @@
+use anyhow::anyhow;
+use httpbin::sse::SseOptions;
+use poem::{
+    get, handler,
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    web::Query,
+    Body, Response, Result, Route,
+};
+
+pub fn api(route: Route) -> Route {
+    route.at("/sse", get(sse))
+}
+
+#[handler]
+fn sse(Query(options): Query<SseOptions>, headers: &HeaderMap) -> Result<Response> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let events = httpbin::sse::events(options, last_event_id).map_err(|e| anyhow!(e))?;
+
+    Ok(match events {
+        Some(events) => Response::builder()
+            .header(CONTENT_TYPE, "text/event-stream")
+            .header(CACHE_CONTROL, "no-cache")
+            .body(Body::from_bytes_stream(events)),
+        None => Response::builder().status(StatusCode::NO_CONTENT).finish(),
+    })
+}
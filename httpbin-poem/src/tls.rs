@@ -0,0 +1,39 @@
+use anyhow::anyhow;
+use httpbin::tls::{Ca, CertificateInfo, ClientCertificates};
+use poem::{
+    get, handler,
+    http::{header::CONTENT_TYPE, StatusCode},
+    web::{Data, Json},
+    Error, Request, Response, Result, Route,
+};
+
+pub fn api(route: Route) -> Route {
+    route
+        .at("/tls/ca.pem", get(ca_pem))
+        .at("/client-cert", get(client_cert))
+}
+
+#[handler]
+fn ca_pem(Data(ca): Data<&Ca>) -> Result<Response> {
+    let pem = ca.0.clone().ok_or_else(|| {
+        Error::from_string(
+            "the certificate was not generated at startup",
+            StatusCode::NOT_FOUND,
+        )
+    })?;
+
+    Ok(Response::builder()
+        .header(CONTENT_TYPE, "application/x-pem-file")
+        .body(pem))
+}
+
+#[handler]
+fn client_cert(req: &Request) -> Result<Json<Vec<CertificateInfo>>> {
+    let certs = req
+        .extensions()
+        .get::<ClientCertificates>()
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Json(certs.inspect().map_err(|e| anyhow!(e))?))
+}
@@ -0,0 +1,52 @@
+use anyhow::anyhow;
+use httpbin::websocket::EchoOptions;
+use poem::{
+    get, handler,
+    http::{HeaderMap, StatusCode},
+    web::Query,
+    Request, Response, Result, Route,
+};
+
+pub fn api(route: Route) -> Route {
+    route.at("/ws/echo", get(echo))
+}
+
+/// The values of a header joined by commas, empty if absent
+fn header_values(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[handler]
+fn echo(req: &Request, Query(options): Query<EchoOptions>) -> Result<Response> {
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let handshake = httpbin::websocket::handshake(
+        header("upgrade"),
+        header("sec-websocket-key"),
+        header("sec-websocket-version"),
+        &header_values(headers, "sec-websocket-protocol"),
+        &header_values(headers, "sec-websocket-extensions"),
+        options,
+    )
+    .map_err(|e| anyhow!(e))?;
+
+    let mut res = Response::builder().status(StatusCode::SWITCHING_PROTOCOLS);
+    for (name, value) in handshake.headers() {
+        res = res.header(name, value);
+    }
+
+    let on_upgrade = req.take_upgrade()?;
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            let _ = httpbin::websocket::echo(upgraded, handshake).await;
+        }
+    });
+
+    Ok(res.finish())
+}
@@ -1,7 +1,24 @@
 use rocket::{fairing::AdHoc, Build, Rocket};
 
+mod barcode;
 mod base64;
+mod codec;
+mod compress;
+mod fake;
+mod hmac;
+mod ids;
+mod random;
+mod time;
 
 pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
-    rocket.attach(AdHoc::on_ignite("mount_data_base64", base64::api))
+    rocket
+        .attach(AdHoc::on_ignite("mount_data_barcode", barcode::api))
+        .attach(AdHoc::on_ignite("mount_data_base64", base64::api))
+        .attach(AdHoc::on_ignite("mount_data_codec", codec::api))
+        .attach(AdHoc::on_ignite("mount_data_compress", compress::api))
+        .attach(AdHoc::on_ignite("mount_data_fake", fake::api))
+        .attach(AdHoc::on_ignite("mount_data_hmac", hmac::api))
+        .attach(AdHoc::on_ignite("mount_data_ids", ids::api))
+        .attach(AdHoc::on_ignite("mount_data_random", random::api))
+        .attach(AdHoc::on_ignite("mount_data_time", time::api))
 }
@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use rocket::{
+    get,
+    http::{ContentType, Header},
+    request::FromParam,
+    routes, Build, FromFormField, Responder, Rocket,
+};
+
+struct Symbology(httpbin::data::barcode::Symbology);
+
+impl<'r> FromParam<'r> for Symbology {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "code128" => Ok(Symbology(httpbin::data::barcode::Symbology::Code128)),
+            "ean13" => Ok(Symbology(httpbin::data::barcode::Symbology::Ean13)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum BarcodeFormat {
+    Png,
+    Svg,
+    Text,
+}
+
+impl From<BarcodeFormat> for httpbin::data::barcode::BarcodeFormat {
+    fn from(format: BarcodeFormat) -> Self {
+        match format {
+            BarcodeFormat::Png => httpbin::data::barcode::BarcodeFormat::Png,
+            BarcodeFormat::Svg => httpbin::data::barcode::BarcodeFormat::Svg,
+            BarcodeFormat::Text => httpbin::data::barcode::BarcodeFormat::Text,
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum QrEcc {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl From<QrEcc> for httpbin::data::barcode::QrEcc {
+    fn from(ecc: QrEcc) -> Self {
+        match ecc {
+            QrEcc::L => httpbin::data::barcode::QrEcc::L,
+            QrEcc::M => httpbin::data::barcode::QrEcc::M,
+            QrEcc::Q => httpbin::data::barcode::QrEcc::Q,
+            QrEcc::H => httpbin::data::barcode::QrEcc::H,
+        }
+    }
+}
+
+struct ContentTypeHeader(Box<ContentType>);
+
+impl<'r> From<ContentTypeHeader> for Header<'r> {
+    fn from(content_type: ContentTypeHeader) -> Self {
+        Header::new("Content-Type", content_type.0.to_string())
+    }
+}
+
+#[derive(Responder)]
+struct BarcodeRes(Vec<u8>, ContentTypeHeader);
+
+impl BarcodeRes {
+    fn new(data: Vec<u8>, format: httpbin::data::barcode::BarcodeFormat) -> Self {
+        // The content types of the formats are always valid
+        let content_type = ContentType::from_str(format.content_type()).unwrap();
+
+        BarcodeRes(data, ContentTypeHeader(Box::new(content_type)))
+    }
+}
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/", routes![qrcode, barcode])
+}
+
+#[get("/qrcode?<data>&<ecc>&<size>&<format>")]
+fn qrcode(
+    data: &str,
+    ecc: Option<QrEcc>,
+    size: Option<usize>,
+    format: Option<BarcodeFormat>,
+) -> Result<BarcodeRes, String> {
+    let format = format.map(Into::into).unwrap_or_default();
+
+    let data = httpbin::data::barcode::qrcode(data, ecc.map(Into::into), size, format)
+        .map_err(|e| e.to_string())?;
+
+    Ok(BarcodeRes::new(data, format))
+}
+
+#[get("/barcode/<symbology>?<data>&<size>&<format>")]
+fn barcode(
+    symbology: Symbology,
+    data: &str,
+    size: Option<usize>,
+    format: Option<BarcodeFormat>,
+) -> Result<BarcodeRes, String> {
+    let format = format.map(Into::into).unwrap_or_default();
+
+    let data = httpbin::data::barcode::barcode(data, symbology.0, size, format)
+        .map_err(|e| e.to_string())?;
+
+    Ok(BarcodeRes::new(data, format))
+}
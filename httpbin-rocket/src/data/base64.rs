@@ -1,13 +1,24 @@
-use std::str::FromStr;
+use std::{io::Cursor, str::FromStr};
 
+use futures_util::TryStreamExt;
+use httpbin::data::base64::Base64Wrap;
 use rocket::{
+    data::ByteUnit,
+    form::{self, FromFormField, ValueField},
+    get,
     http::{ContentType, Header},
     post,
     request::FromParam,
-    routes, Build, FromForm, Responder, Rocket,
+    response::stream::{One, ReaderStream},
+    routes,
+    serde::json::Json,
+    tokio::io::AsyncRead,
+    Build, Data, FromForm, Responder, Rocket,
 };
+use serde::Serialize;
+use tokio_util::io::StreamReader;
 
-struct Base64Engine(httpbin::data::base64::Base64Engine);
+pub(super) struct Base64Engine(pub httpbin::data::base64::Base64Engine);
 
 impl<'r> FromParam<'r> for Base64Engine {
     type Error = &'r str;
@@ -27,15 +38,81 @@ impl<'r> FromParam<'r> for Base64Engine {
             "crypt" => Ok(Base64Engine(httpbin::data::base64::Base64Engine::Crypt)),
             "imap_mutf7" => Ok(Base64Engine(httpbin::data::base64::Base64Engine::ImapMutf7)),
             "custom" => Ok(Base64Engine(httpbin::data::base64::Base64Engine::Custom)),
+            "auto" => Ok(Base64Engine(httpbin::data::base64::Base64Engine::Auto)),
             _ => Err(param),
         }
     }
 }
 
+impl Base64Engine {
+    fn name(&self) -> &'static str {
+        match self.0 {
+            httpbin::data::base64::Base64Engine::Standard => "standard",
+            httpbin::data::base64::Base64Engine::StandardNoPad => "standard_no_pad",
+            httpbin::data::base64::Base64Engine::UrlSafe => "url_safe",
+            httpbin::data::base64::Base64Engine::UrlSafeNoPad => "url_safe_no_pad",
+            httpbin::data::base64::Base64Engine::Bcrypt => "bcrypt",
+            httpbin::data::base64::Base64Engine::BinHex => "bin_hex",
+            httpbin::data::base64::Base64Engine::Crypt => "crypt",
+            httpbin::data::base64::Base64Engine::ImapMutf7 => "imap_mutf7",
+            httpbin::data::base64::Base64Engine::Custom => "custom",
+            httpbin::data::base64::Base64Engine::Auto => "auto",
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'v> FromFormField<'v> for Base64Engine {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Self::from_param(field.value)
+            .map_err(|engine| form::Error::validation(format!("unknown engine: {}", engine)).into())
+    }
+}
+
 #[derive(FromForm)]
 struct Base64Config {
     pub alphabet: Option<String>,
     pub pad: Option<bool>,
+    pub padding: Option<Base64Padding>,
+}
+
+#[derive(FromFormField)]
+enum Base64Padding {
+    Indifferent,
+    Required,
+    Forbidden,
+}
+
+impl From<Base64Padding> for httpbin::data::base64::Base64Padding {
+    fn from(padding: Base64Padding) -> Self {
+        match padding {
+            Base64Padding::Indifferent => httpbin::data::base64::Base64Padding::Indifferent,
+            Base64Padding::Required => httpbin::data::base64::Base64Padding::Required,
+            Base64Padding::Forbidden => httpbin::data::base64::Base64Padding::Forbidden,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Base64EngineInfo {
+    engine: &'static str,
+    alphabet: &'static str,
+    pad: bool,
+}
+
+#[derive(FromFormField)]
+enum Base64WrapMode {
+    Mime,
+    Pem,
+}
+
+impl From<Base64WrapMode> for httpbin::data::base64::Base64WrapMode {
+    fn from(mode: Base64WrapMode) -> Self {
+        match mode {
+            Base64WrapMode::Mime => httpbin::data::base64::Base64WrapMode::Mime,
+            Base64WrapMode::Pem => httpbin::data::base64::Base64WrapMode::Pem,
+        }
+    }
 }
 
 struct ContentTypeHeader(Box<ContentType>);
@@ -53,13 +130,24 @@ enum Base64Res {
 }
 
 pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
-    rocket.mount("/base64", routes![base64_encode, base64_decode])
+    rocket.mount(
+        "/base64",
+        routes![
+            base64_encode,
+            base64_decode,
+            base64_encode_stream,
+            base64_decode_stream,
+            base64_engines
+        ],
+    )
 }
 
-#[post("/encode/<engine>?<config>", data = "<data>")]
+#[post("/encode/<engine>?<config>&<wrap>&<label>", data = "<data>")]
 fn base64_encode(
     engine: Base64Engine,
     config: Base64Config,
+    wrap: Option<Base64WrapMode>,
+    label: Option<String>,
     data: Vec<u8>,
 ) -> Result<Base64Res, String> {
     let config = match engine {
@@ -67,13 +155,22 @@ fn base64_encode(
             Some(httpbin::data::base64::Base64Config {
                 alphabet: config.alphabet.unwrap_or_default(),
                 pad: config.pad.unwrap_or_default(),
+                padding: config.padding.map(Into::into),
             })
         }
         _ => None,
     };
 
-    let encoded =
-        httpbin::data::base64::encode(&data, engine.0, config).map_err(|e| e.to_string())?;
+    let encoded = match wrap {
+        Some(wrap) => httpbin::data::base64::encode_wrapped(
+            &data,
+            engine.0,
+            config,
+            Base64Wrap::new(wrap.into(), label),
+        ),
+        None => httpbin::data::base64::encode(&data, engine.0, config),
+    }
+    .map_err(|e| e.to_string())?;
 
     Ok(Base64Res::OkText(encoded))
 }
@@ -89,6 +186,7 @@ fn base64_decode(
             Some(httpbin::data::base64::Base64Config {
                 alphabet: config.alphabet.unwrap_or_default(),
                 pad: config.pad.unwrap_or_default(),
+                padding: config.padding.map(Into::into),
             })
         }
         _ => None,
@@ -115,3 +213,86 @@ fn base64_decode(
         }
     }
 }
+
+#[post("/stream/encode/<engine>?<config>&<wrap>&<label>", data = "<data>")]
+fn base64_encode_stream(
+    engine: Base64Engine,
+    config: Base64Config,
+    wrap: Option<Base64WrapMode>,
+    label: Option<String>,
+    data: Data<'_>,
+) -> Result<
+    (
+        ContentType,
+        ReaderStream<One<impl AsyncRead + Unpin + Send + '_>>,
+    ),
+    String,
+> {
+    let config = match engine {
+        Base64Engine(httpbin::data::base64::Base64Engine::Custom) => {
+            Some(httpbin::data::base64::Base64Config {
+                alphabet: config.alphabet.unwrap_or_default(),
+                pad: config.pad.unwrap_or_default(),
+                padding: config.padding.map(Into::into),
+            })
+        }
+        _ => None,
+    };
+    let wrap = wrap.map(|wrap| Base64Wrap::new(wrap.into(), label));
+
+    let data = tokio_util::io::ReaderStream::new(data.open(ByteUnit::max_value()));
+    let encoded = httpbin::data::base64::encode_stream(data, engine.0, config, wrap)
+        .map_err(|e| e.to_string())?;
+
+    Ok((
+        ContentType::Plain,
+        ReaderStream::one(StreamReader::new(Box::pin(encoded.map_ok(Cursor::new)))),
+    ))
+}
+
+#[post("/stream/decode/<engine>?<config>", data = "<data>")]
+fn base64_decode_stream(
+    engine: Base64Engine,
+    config: Base64Config,
+    data: Data<'_>,
+) -> Result<
+    (
+        ContentType,
+        ReaderStream<One<impl AsyncRead + Unpin + Send + '_>>,
+    ),
+    String,
+> {
+    let config = match engine {
+        Base64Engine(httpbin::data::base64::Base64Engine::Custom) => {
+            Some(httpbin::data::base64::Base64Config {
+                alphabet: config.alphabet.unwrap_or_default(),
+                pad: config.pad.unwrap_or_default(),
+                padding: config.padding.map(Into::into),
+            })
+        }
+        _ => None,
+    };
+
+    let data = tokio_util::io::ReaderStream::new(data.open(ByteUnit::max_value()));
+    let decoded =
+        httpbin::data::base64::decode_stream(data, engine.0, config).map_err(|e| e.to_string())?;
+
+    Ok((
+        ContentType::Binary,
+        ReaderStream::one(StreamReader::new(Box::pin(decoded.map_ok(Cursor::new)))),
+    ))
+}
+
+#[get("/engines")]
+fn base64_engines() -> Json<Vec<Base64EngineInfo>> {
+    Json(
+        httpbin::data::base64::engines()
+            .into_iter()
+            .map(|info| Base64EngineInfo {
+                engine: Base64Engine(info.engine).name(),
+                alphabet: info.alphabet,
+                pad: info.pad,
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,111 @@
+use std::str::FromStr;
+
+use rocket::{
+    http::{ContentType, Header},
+    post,
+    request::FromParam,
+    routes, Build, FromFormField, Responder, Rocket,
+};
+
+struct Codec(httpbin::data::codec::Codec);
+
+impl<'r> FromParam<'r> for Codec {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "hex" => Ok(Codec(httpbin::data::codec::Codec::Hex)),
+            "hex_upper" => Ok(Codec(httpbin::data::codec::Codec::HexUpper)),
+            "base32" => Ok(Codec(httpbin::data::codec::Codec::Base32)),
+            "base32_no_pad" => Ok(Codec(httpbin::data::codec::Codec::Base32NoPad)),
+            "base32_crockford" => Ok(Codec(httpbin::data::codec::Codec::Base32Crockford)),
+            "base58" => Ok(Codec(httpbin::data::codec::Codec::Base58)),
+            "base58_flickr" => Ok(Codec(httpbin::data::codec::Codec::Base58Flickr)),
+            "base85" => Ok(Codec(httpbin::data::codec::Codec::Base85)),
+            "z85" => Ok(Codec(httpbin::data::codec::Codec::Z85)),
+            "ascii85" => Ok(Codec(httpbin::data::codec::Codec::Ascii85)),
+            "percent" => Ok(Codec(httpbin::data::codec::Codec::Percent)),
+            "quoted_printable" => Ok(Codec(httpbin::data::codec::Codec::QuotedPrintable)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum PercentSet {
+    Controls,
+    Fragment,
+    Query,
+    Path,
+    Userinfo,
+    Component,
+    #[field(value = "non_alphanumeric")]
+    NonAlphanumeric,
+}
+
+impl From<PercentSet> for httpbin::data::codec::PercentSet {
+    fn from(set: PercentSet) -> Self {
+        match set {
+            PercentSet::Controls => httpbin::data::codec::PercentSet::Controls,
+            PercentSet::Fragment => httpbin::data::codec::PercentSet::Fragment,
+            PercentSet::Query => httpbin::data::codec::PercentSet::Query,
+            PercentSet::Path => httpbin::data::codec::PercentSet::Path,
+            PercentSet::Userinfo => httpbin::data::codec::PercentSet::Userinfo,
+            PercentSet::Component => httpbin::data::codec::PercentSet::Component,
+            PercentSet::NonAlphanumeric => httpbin::data::codec::PercentSet::NonAlphanumeric,
+        }
+    }
+}
+
+struct ContentTypeHeader(Box<ContentType>);
+
+impl<'r> From<ContentTypeHeader> for Header<'r> {
+    fn from(content_type: ContentTypeHeader) -> Self {
+        Header::new("Content-Type", content_type.0.to_string())
+    }
+}
+
+#[derive(Responder)]
+enum CodecRes {
+    OkText(String),
+    OkBinary(Vec<u8>, ContentTypeHeader),
+}
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/", routes![codec_encode, codec_decode])
+}
+
+#[post("/encode/<codec>?<set>", data = "<data>")]
+fn codec_encode(codec: Codec, set: Option<PercentSet>, data: Vec<u8>) -> Result<CodecRes, String> {
+    let config = httpbin::data::codec::CodecConfig {
+        percent_set: set.map(Into::into).unwrap_or_default(),
+    };
+
+    let encoded =
+        httpbin::data::codec::encode(&data, codec.0, Some(config)).map_err(|e| e.to_string())?;
+
+    Ok(CodecRes::OkText(encoded))
+}
+
+#[post("/decode/<codec>", data = "<data>")]
+fn codec_decode(codec: Codec, data: String) -> Result<CodecRes, String> {
+    let decoded = httpbin::data::codec::decode(&data, codec.0).map_err(|e| e.to_string())?;
+
+    match String::from_utf8(decoded.clone()) {
+        Ok(decoded) => Ok(CodecRes::OkText(decoded)),
+        Err(_) => {
+            let kind = infer::get(&decoded);
+
+            Ok(CodecRes::OkBinary(
+                decoded,
+                ContentTypeHeader(Box::new(
+                    ContentType::from_str(
+                        kind.map(|k| k.mime_type())
+                            .unwrap_or("application/octet-stream"),
+                    )
+                    .unwrap_or(ContentType::Binary),
+                )),
+            ))
+        }
+    }
+}
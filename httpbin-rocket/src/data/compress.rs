@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use httpbin::cli::CompressConfig;
+use rocket::{
+    data::ToByteUnit, http::ContentType, post, request::FromParam, routes, Build, Data, Responder,
+    Rocket, State,
+};
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/", routes![compress, decompress])
+}
+
+struct Compression(httpbin::data::compress::Compression);
+
+impl<'r> FromParam<'r> for Compression {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "gzip" => Ok(Compression(httpbin::data::compress::Compression::Gzip)),
+            "zlib" => Ok(Compression(httpbin::data::compress::Compression::Zlib)),
+            "deflate" => Ok(Compression(httpbin::data::compress::Compression::Deflate)),
+            "brotli" => Ok(Compression(httpbin::data::compress::Compression::Brotli)),
+            "zstd" => Ok(Compression(httpbin::data::compress::Compression::Zstd)),
+            "lz4" => Ok(Compression(httpbin::data::compress::Compression::Lz4)),
+            "snappy" => Ok(Compression(httpbin::data::compress::Compression::Snappy)),
+            "snappy_raw" => Ok(Compression(httpbin::data::compress::Compression::SnappyRaw)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[derive(Responder)]
+enum CompressRes {
+    Text(String),
+    Binary(Vec<u8>, ContentType),
+}
+
+impl CompressRes {
+    fn binary(data: Vec<u8>, content_type: &str) -> Self {
+        // The content types of the algorithms and of infer are always valid
+        CompressRes::Binary(data, ContentType::from_str(content_type).unwrap())
+    }
+}
+
+/// Read all of `data`, which must not be larger than the decompressed size limit either
+async fn read(data: Data<'_>, cfg: &CompressConfig) -> Result<Vec<u8>, String> {
+    let data = data
+        .open(cfg.max_size.bytes())
+        .into_bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !data.is_complete() {
+        return Err(format!("the data is larger than {} bytes", cfg.max_size));
+    }
+
+    Ok(data.into_inner())
+}
+
+#[post("/compress/<algo>?<level>", data = "<data>")]
+async fn compress(
+    cfg: &State<CompressConfig>,
+    algo: Compression,
+    level: Option<i32>,
+    data: Data<'_>,
+) -> Result<CompressRes, String> {
+    let data = read(data, cfg).await?;
+
+    let compressed =
+        httpbin::data::compress::compress(&data, algo.0, level).map_err(|e| e.to_string())?;
+
+    Ok(CompressRes::binary(compressed, algo.0.content_type()))
+}
+
+#[post("/decompress/<algo>", data = "<data>")]
+async fn decompress(
+    cfg: &State<CompressConfig>,
+    algo: Compression,
+    data: Data<'_>,
+) -> Result<CompressRes, String> {
+    let data = read(data, cfg).await?;
+
+    let decompressed = httpbin::data::compress::decompress(&data, algo.0, cfg.max_size)
+        .map_err(|e| e.to_string())?;
+
+    match String::from_utf8(decompressed) {
+        Ok(s) => Ok(CompressRes::Text(s)),
+        Err(e) => {
+            let decompressed = e.into_bytes();
+            let content_type = infer::get(&decompressed)
+                .map(|kind| kind.mime_type())
+                .unwrap_or("application/octet-stream");
+
+            Ok(CompressRes::binary(decompressed, content_type))
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use httpbin::cli::FakeConfig;
+use rocket::{
+    get, post, request::FromParam, routes, serde::json::Json, Build, FromFormField, Rocket, State,
+};
+use serde_json::Value;
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/fake", routes![fake_schema, fake_records])
+}
+
+struct FakeKind(httpbin::data::fake::FakeKind);
+
+impl<'r> FromParam<'r> for FakeKind {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "person" => Ok(FakeKind(httpbin::data::fake::FakeKind::Person)),
+            "address" => Ok(FakeKind(httpbin::data::fake::FakeKind::Address)),
+            "company" => Ok(FakeKind(httpbin::data::fake::FakeKind::Company)),
+            "credit_card" => Ok(FakeKind(httpbin::data::fake::FakeKind::CreditCard)),
+            "ip" => Ok(FakeKind(httpbin::data::fake::FakeKind::Ip)),
+            "user_agent" => Ok(FakeKind(httpbin::data::fake::FakeKind::UserAgent)),
+            "lorem" => Ok(FakeKind(httpbin::data::fake::FakeKind::Lorem)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum FakeLocale {
+    En,
+    De,
+    Fr,
+}
+
+impl From<FakeLocale> for httpbin::data::fake::FakeLocale {
+    fn from(locale: FakeLocale) -> Self {
+        match locale {
+            FakeLocale::En => httpbin::data::fake::FakeLocale::En,
+            FakeLocale::De => httpbin::data::fake::FakeLocale::De,
+            FakeLocale::Fr => httpbin::data::fake::FakeLocale::Fr,
+        }
+    }
+}
+
+#[post("/schema?<count>&<seed>", data = "<schema>")]
+fn fake_schema(
+    cfg: &State<FakeConfig>,
+    count: Option<usize>,
+    seed: Option<u64>,
+    schema: Json<Value>,
+) -> Result<Json<Value>, String> {
+    let instances = httpbin::data::fake::schema(&schema, count, seed, cfg.max_count)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Json(instances))
+}
+
+#[get("/<kind>?<locale>&<count>&<seed>")]
+fn fake_records(
+    cfg: &State<FakeConfig>,
+    kind: FakeKind,
+    locale: Option<FakeLocale>,
+    count: Option<usize>,
+    seed: Option<u64>,
+) -> Result<Json<Value>, String> {
+    let records =
+        httpbin::data::fake::records(kind.0, locale.map(Into::into), count, seed, cfg.max_count)
+            .map_err(|e| e.to_string())?;
+
+    Ok(Json(records))
+}
@@ -0,0 +1,105 @@
+use rocket::{post, request::FromParam, routes, serde::json::Json, Build, FromFormField, Rocket};
+
+use super::base64::Base64Engine;
+
+struct HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm);
+
+impl<'r> FromParam<'r> for HmacAlgorithm {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "md5" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Md5)),
+            "sha1" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Sha1)),
+            "sha224" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Sha224)),
+            "sha256" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Sha256)),
+            "sha384" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Sha384)),
+            "sha512" => Ok(HmacAlgorithm(httpbin::data::hmac::HmacAlgorithm::Sha512)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum HmacKeyEncoding {
+    Raw,
+    Hex,
+    Base64,
+}
+
+impl From<HmacKeyEncoding> for httpbin::data::hmac::HmacKeyEncoding {
+    fn from(encoding: HmacKeyEncoding) -> Self {
+        match encoding {
+            HmacKeyEncoding::Raw => httpbin::data::hmac::HmacKeyEncoding::Raw,
+            HmacKeyEncoding::Hex => httpbin::data::hmac::HmacKeyEncoding::Hex,
+            HmacKeyEncoding::Base64 => httpbin::data::hmac::HmacKeyEncoding::Base64,
+        }
+    }
+}
+
+#[derive(FromFormField)]
+enum HmacMacEncoding {
+    Hex,
+    Base64,
+}
+
+impl From<HmacMacEncoding> for httpbin::data::hmac::HmacMacEncoding {
+    fn from(encoding: HmacMacEncoding) -> Self {
+        match encoding {
+            HmacMacEncoding::Hex => httpbin::data::hmac::HmacMacEncoding::Hex,
+            HmacMacEncoding::Base64 => httpbin::data::hmac::HmacMacEncoding::Base64,
+        }
+    }
+}
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/hmac", routes![hmac_sign, hmac_verify])
+}
+
+#[post("/<algorithm>/sign?<key>&<key_encoding>&<engine>", data = "<data>")]
+fn hmac_sign(
+    algorithm: HmacAlgorithm,
+    key: &str,
+    key_encoding: Option<HmacKeyEncoding>,
+    engine: Option<Base64Engine>,
+    data: Vec<u8>,
+) -> Result<Json<httpbin::data::hmac::HmacMac>, String> {
+    let key = httpbin::data::hmac::decode_key(
+        key,
+        key_encoding.map(Into::into).unwrap_or_default(),
+        engine.map(|engine| engine.0),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mac = httpbin::data::hmac::sign(algorithm.0, &key, &data);
+
+    Ok(Json(mac.as_slice().into()))
+}
+
+#[post(
+    "/<algorithm>/verify?<key>&<key_encoding>&<engine>&<mac>&<mac_encoding>",
+    data = "<data>"
+)]
+fn hmac_verify(
+    algorithm: HmacAlgorithm,
+    key: &str,
+    key_encoding: Option<HmacKeyEncoding>,
+    engine: Option<Base64Engine>,
+    mac: &str,
+    mac_encoding: Option<HmacMacEncoding>,
+    data: Vec<u8>,
+) -> Result<Json<httpbin::data::hmac::HmacVerification>, String> {
+    let key = httpbin::data::hmac::decode_key(
+        key,
+        key_encoding.map(Into::into).unwrap_or_default(),
+        engine.map(|engine| engine.0),
+    )
+    .map_err(|e| e.to_string())?;
+    let mac =
+        httpbin::data::hmac::decode_mac(mac, mac_encoding.map(Into::into).unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+
+    let verification = httpbin::data::hmac::verify(algorithm.0, &key, &data, &mac);
+
+    Ok(Json(verification))
+}
@@ -0,0 +1,63 @@
+use httpbin::data::ids::{IdInspection, IdOptions};
+use rocket::{get, request::FromParam, routes, serde::json::Json, Build, Rocket};
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/id", routes![id_generate, id_parse])
+}
+
+struct IdKind(httpbin::data::ids::IdKind);
+
+impl<'r> FromParam<'r> for IdKind {
+    type Error = &'r str;
+
+    fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+        match param {
+            "ulid" => Ok(IdKind(httpbin::data::ids::IdKind::Ulid)),
+            "ksuid" => Ok(IdKind(httpbin::data::ids::IdKind::Ksuid)),
+            "snowflake" => Ok(IdKind(httpbin::data::ids::IdKind::Snowflake)),
+            "nanoid" => Ok(IdKind(httpbin::data::ids::IdKind::Nanoid)),
+            "typeid" => Ok(IdKind(httpbin::data::ids::IdKind::Typeid)),
+            _ => Err(param),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/<kind>?<timestamp>&<epoch>&<worker_id>&<alphabet>&<length>&<prefix>")]
+fn id_generate(
+    kind: IdKind,
+    timestamp: Option<u64>,
+    epoch: Option<u64>,
+    worker_id: Option<u16>,
+    alphabet: Option<String>,
+    length: Option<usize>,
+    prefix: Option<String>,
+) -> Result<String, String> {
+    let options = IdOptions {
+        timestamp,
+        epoch,
+        worker_id,
+        alphabet,
+        length,
+        prefix,
+    };
+
+    httpbin::data::ids::generate(kind.0, options).map_err(|e| e.to_string())
+}
+
+#[get("/<kind>/parse?<id>&<epoch>&<alphabet>")]
+fn id_parse(
+    kind: IdKind,
+    id: &str,
+    epoch: Option<u64>,
+    alphabet: Option<String>,
+) -> Result<Json<IdInspection>, String> {
+    let options = IdOptions {
+        epoch,
+        alphabet,
+        ..Default::default()
+    };
+    let inspection = httpbin::data::ids::parse(kind.0, id, options).map_err(|e| e.to_string())?;
+
+    Ok(Json(inspection))
+}
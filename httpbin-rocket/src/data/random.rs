@@ -0,0 +1,60 @@
+use rocket::{get, routes, serde::json::Json, Build, Rocket};
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount(
+        "/random",
+        routes![
+            random_int,
+            random_float,
+            random_string,
+            random_choice,
+            random_shuffle
+        ],
+    )
+}
+
+#[get("/int?<min>&<max>&<seed>")]
+fn random_int(min: Option<i64>, max: Option<i64>, seed: Option<u64>) -> Result<String, String> {
+    let int = httpbin::data::random::int(min, max, seed).map_err(|e| e.to_string())?;
+
+    Ok(int.to_string())
+}
+
+#[get("/float?<min>&<max>&<seed>")]
+fn random_float(min: Option<f64>, max: Option<f64>, seed: Option<u64>) -> Result<String, String> {
+    let float = httpbin::data::random::float(min, max, seed).map_err(|e| e.to_string())?;
+
+    Ok(float.to_string())
+}
+
+#[get("/string?<charset>&<length>&<seed>")]
+fn random_string(
+    charset: Option<&str>,
+    length: Option<usize>,
+    seed: Option<u64>,
+) -> Result<String, String> {
+    // Parsing a charset never fails
+    let charset = charset.map(|charset| charset.parse().unwrap());
+
+    let string = httpbin::data::random::string(charset, length, seed).map_err(|e| e.to_string())?;
+
+    Ok(string)
+}
+
+#[get("/choice?<items>&<seed>")]
+fn random_choice(items: &str, seed: Option<u64>) -> Result<String, String> {
+    // Parsing items never fails
+    let items = items.parse().unwrap();
+
+    let choice = httpbin::data::random::choice(items, seed).map_err(|e| e.to_string())?;
+
+    Ok(choice)
+}
+
+#[get("/shuffle?<items>&<seed>")]
+fn random_shuffle(items: &str, seed: Option<u64>) -> Json<Vec<String>> {
+    // Parsing items never fails
+    let items = items.parse().unwrap();
+
+    Json(httpbin::data::random::shuffle(items, seed))
+}
@@ -0,0 +1,62 @@
+use httpbin::data::time::TimeInspection;
+use rocket::{get, routes, serde::json::Json, Build, FromFormField, Rocket};
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/time", routes![time_now, time_parse, time_convert])
+}
+
+#[derive(FromFormField)]
+enum TimeFormat {
+    Rfc3339,
+    Rfc2822,
+    #[field(value = "http_date")]
+    HttpDate,
+    Unix,
+    #[field(value = "unix_ms")]
+    UnixMs,
+    #[field(value = "unix_ns")]
+    UnixNs,
+}
+
+impl From<TimeFormat> for httpbin::data::time::TimeFormat {
+    fn from(format: TimeFormat) -> Self {
+        match format {
+            TimeFormat::Rfc3339 => httpbin::data::time::TimeFormat::Rfc3339,
+            TimeFormat::Rfc2822 => httpbin::data::time::TimeFormat::Rfc2822,
+            TimeFormat::HttpDate => httpbin::data::time::TimeFormat::HttpDate,
+            TimeFormat::Unix => httpbin::data::time::TimeFormat::Unix,
+            TimeFormat::UnixMs => httpbin::data::time::TimeFormat::UnixMs,
+            TimeFormat::UnixNs => httpbin::data::time::TimeFormat::UnixNs,
+        }
+    }
+}
+
+#[get("/now?<format>&<tz>")]
+fn time_now(format: Option<TimeFormat>, tz: Option<&str>) -> Result<String, String> {
+    let format = format.map(Into::into).unwrap_or_default();
+
+    httpbin::data::time::now(format, tz).map_err(|e| e.to_string())
+}
+
+#[get("/parse?<time>&<from>&<tz>")]
+fn time_parse(
+    time: &str,
+    from: Option<&str>,
+    tz: Option<&str>,
+) -> Result<Json<TimeInspection>, String> {
+    let inspection = httpbin::data::time::parse(time, from, tz).map_err(|e| e.to_string())?;
+
+    Ok(Json(inspection))
+}
+
+#[get("/convert?<time>&<from>&<tz>&<format>")]
+fn time_convert(
+    time: &str,
+    from: Option<&str>,
+    tz: Option<&str>,
+    format: Option<TimeFormat>,
+) -> Result<String, String> {
+    let format = format.map(Into::into).unwrap_or_default();
+
+    httpbin::data::time::convert(time, from, tz, format).map_err(|e| e.to_string())
+}
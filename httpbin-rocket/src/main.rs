@@ -1,26 +1,91 @@
-use anyhow::Result;
-use httpbin::cli::Cli;
-use rocket::{fairing::AdHoc, Config};
+use anyhow::{bail, Result};
+use futures_util::future;
+use httpbin::{
+    cli::{self, Cli, ClientAuth, ListenerConfig, TlsVersion},
+    tls::{Ca, Tls},
+};
+use rocket::{
+    config::{CipherSuite, MutualTls, TlsConfig},
+    fairing::AdHoc,
+    Build, Config, Rocket,
+};
 
 mod data;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
+mod websocket;
 
 #[rocket::main]
 async fn main() -> Result<()> {
     let cfg = Cli::parse().load_config();
 
-    let rocket_config = Config {
-        address: cfg.ip,
-        port: cfg.port,
-        ..Config::default()
-    };
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    if tls.as_ref().is_some_and(|tls| tls.http3) {
+        bail!("rocket cannot serve HTTP/3, so tls.http3 cannot be set");
+    }
 
+    // Rocket always offers h2 and http/1.1, and can only require TLS 1.3 by its cipher suites
+    if tls
+        .as_ref()
+        .is_some_and(|tls| tls.alpn != ["h2", "http/1.1"])
+    {
+        bail!("rocket always offers h2 and http/1.1, so tls.alpn cannot be changed");
+    }
+    let tls_config = tls.as_ref().map(|tls| {
+        let tls_config = TlsConfig::from_bytes(tls.cert_pem.as_bytes(), tls.key_pem.as_bytes());
+        let tls_config = match tls.min_version {
+            TlsVersion::Tls12 => tls_config,
+            TlsVersion::Tls13 => tls_config.with_ciphers(CipherSuite::TLS_V13_SET),
+        };
+        match &tls.client_ca_pem {
+            Some(client_ca) => tls_config.with_mutual(
+                MutualTls::from_bytes(client_ca.as_bytes())
+                    .mandatory(tls.client_auth == ClientAuth::Require),
+            ),
+            None => tls_config,
+        }
+    });
+
+    // Each TCP listener gets its own instance, as rocket only binds one address
+    let mut instances = Vec::new();
+    for listener in cfg.listeners() {
+        match listener {
+            ListenerConfig::Tcp { ip, port } => {
+                let rocket_config = Config {
+                    address: ip,
+                    port,
+                    tls: tls_config.clone(),
+                    ..Config::default()
+                };
+                let rocket = rocket(rocket_config, &cfg, Ca::from(tls.as_ref()))?;
+                instances.push(rocket.launch());
+            }
+            #[cfg(unix)]
+            ListenerConfig::Unix { path, .. } => {
+                bail!(
+                    "rocket can only listen on TCP, so {} cannot be served",
+                    path.display()
+                );
+            }
+        }
+    }
+    // Rocket returns on Ctrl+C and SIGTERM
+    future::try_join_all(instances).await?;
+
+    Ok(())
+}
+
+fn rocket(config: Config, cfg: &cli::Config, ca: Ca) -> Result<Rocket<Build>> {
     let cors = rocket_cors::CorsOptions::default()
         .allowed_origins(rocket_cors::AllowedOrigins::all())
         .to_cors()?;
 
-    let _ = rocket::custom(rocket_config)
+    Ok(rocket::custom(config)
+        .manage(cfg.fake.clone())
+        .manage(cfg.compress.clone())
+        .manage(ca)
         .attach(cors)
         .attach(AdHoc::on_ignite("mount_data", data::api))
         .attach(AdHoc::on_ignite("mount_http_method", http_method::api))
@@ -28,8 +93,7 @@ async fn main() -> Result<()> {
             "mount_request_inspection",
             request_inspection::api,
         ))
-        .launch()
-        .await?;
-
-    Ok(())
+        .attach(AdHoc::on_ignite("mount_sse", sse::api))
+        .attach(AdHoc::on_ignite("mount_tls", tls::api))
+        .attach(AdHoc::on_ignite("mount_websocket", websocket::api)))
 }
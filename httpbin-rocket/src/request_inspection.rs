@@ -1,9 +1,10 @@
 use std::{collections::HashMap, net::IpAddr, ops::Deref};
 
-use rocket::{get, request::FromRequest, routes, serde::json::Json, Build, Request, Rocket};
+use httpbin::inspect::MimeInspection;
+use rocket::{get, post, request::FromRequest, routes, serde::json::Json, Build, Request, Rocket};
 use serde::Serialize;
 
-struct HeaderMap<'r>(&'r rocket::http::HeaderMap<'r>);
+pub(crate) struct HeaderMap<'r>(&'r rocket::http::HeaderMap<'r>);
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for HeaderMap<'r> {
@@ -58,7 +59,7 @@ struct UserAgent {
 }
 
 pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
-    rocket.mount("/", routes![headers, ip, user_agent])
+    rocket.mount("/", routes![headers, ip, user_agent, inspect_mime])
 }
 
 #[get("/headers")]
@@ -83,3 +84,11 @@ fn user_agent(user_agent: UserAgentHeader) -> Json<UserAgent> {
         user_agent: user_agent.0.to_string(),
     })
 }
+
+#[post("/inspect/mime", data = "<data>")]
+fn inspect_mime(headers: HeaderMap, data: Vec<u8>) -> Json<MimeInspection> {
+    Json(httpbin::inspect::mime(
+        &data,
+        headers.get_one("Content-Type"),
+    ))
+}
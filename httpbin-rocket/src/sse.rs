@@ -0,0 +1,60 @@
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use httpbin::sse::SseOptions;
+use rocket::{
+    get,
+    http::{ContentType, Status},
+    response::{self, Responder},
+    routes, Build, Request, Response, Rocket,
+};
+use tokio_util::io::StreamReader;
+
+use crate::request_inspection::HeaderMap;
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/", routes![sse])
+}
+
+/// The events, or `204 No Content` once all events have been sent
+struct SseRes<S>(Option<S>);
+
+impl<'r, S> Responder<'r, 'static> for SseRes<S>
+where
+    S: Stream<Item = std::io::Result<String>> + Send + 'static,
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        match self.0 {
+            // Rocket cannot abort a response, so a dropped connection ends the body early instead
+            Some(events) => Response::build()
+                .header(ContentType::EventStream)
+                .raw_header("Cache-Control", "no-cache")
+                .streamed_body(StreamReader::new(Box::pin(events.map_ok(Bytes::from))))
+                .ok(),
+            None => Response::build().status(Status::NoContent).ok(),
+        }
+    }
+}
+
+#[get("/sse?<count>&<interval>&<retry>&<heartbeat>&<drop_after>")]
+fn sse(
+    headers: HeaderMap<'_>,
+    count: Option<u64>,
+    interval: Option<u64>,
+    retry: Option<u64>,
+    heartbeat: Option<u64>,
+    drop_after: Option<u64>,
+) -> Result<SseRes<impl Stream<Item = std::io::Result<String>>>, String> {
+    let events = httpbin::sse::events(
+        SseOptions {
+            count,
+            interval,
+            retry,
+            heartbeat,
+            drop_after,
+        },
+        headers.get_one("Last-Event-ID"),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(SseRes(events))
+}
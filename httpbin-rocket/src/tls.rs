@@ -0,0 +1,34 @@
+use httpbin::tls::{Ca, CertificateInfo, ClientCertificates};
+use rocket::{
+    get, http::ContentType, mtls::Certificate, response::status::NotFound, routes,
+    serde::json::Json, Build, Rocket, State,
+};
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket
+        .mount("/tls", routes![ca_pem])
+        .mount("/", routes![client_cert])
+}
+
+#[get("/ca.pem")]
+fn ca_pem(ca: &State<Ca>) -> Result<(ContentType, String), NotFound<&'static str>> {
+    let pem =
+        ca.0.clone()
+            .ok_or(NotFound("the certificate was not generated at startup"))?;
+
+    Ok((ContentType::new("application", "x-pem-file"), pem))
+}
+
+/// The certificate a client presented over mutual TLS
+///
+/// Rocket keeps the chain with the connection but only hands its leaf to handlers, through the
+/// `Certificate` guard, so the intermediates are never described here.
+#[get("/client-cert")]
+fn client_cert(cert: Option<Certificate<'_>>) -> Result<Json<Vec<CertificateInfo>>, String> {
+    let certs = ClientCertificates(
+        cert.map(|cert| cert.as_bytes().to_vec())
+            .into_iter()
+            .collect(),
+    );
+    certs.inspect().map(Json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,68 @@
+use std::{io, pin::Pin};
+
+use httpbin::websocket::{EchoOptions, Handshake};
+use rocket::{
+    data::{IoHandler, IoStream},
+    get,
+    response::{self, Responder},
+    routes, Build, Request, Response, Rocket,
+};
+
+use crate::request_inspection::HeaderMap;
+
+pub async fn api(rocket: Rocket<Build>) -> Rocket<Build> {
+    rocket.mount("/ws", routes![echo])
+}
+
+struct Echo(Handshake);
+
+impl<'r> Responder<'r, 'static> for Echo {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut res = Response::build();
+        for (name, value) in self.0.headers() {
+            res.raw_header(name, value);
+        }
+
+        // Rocket switches protocols and sets `Connection` and `Upgrade` itself
+        Ok(res.upgrade("websocket", self).finalize())
+    }
+}
+
+#[rocket::async_trait]
+impl IoHandler for Echo {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        httpbin::websocket::echo(io, Pin::into_inner(self).0).await
+    }
+}
+
+#[get("/echo?<delay>&<ping>&<close_after>&<code>&<reason>&<drop_after>")]
+fn echo(
+    headers: HeaderMap<'_>,
+    delay: Option<u64>,
+    ping: Option<u64>,
+    close_after: Option<usize>,
+    code: Option<u16>,
+    reason: Option<String>,
+    drop_after: Option<usize>,
+) -> Result<Echo, String> {
+    let header_values = |name| headers.get(name).collect::<Vec<_>>().join(",");
+
+    let handshake = httpbin::websocket::handshake(
+        headers.get_one("Upgrade"),
+        headers.get_one("Sec-WebSocket-Key"),
+        headers.get_one("Sec-WebSocket-Version"),
+        &header_values("Sec-WebSocket-Protocol"),
+        &header_values("Sec-WebSocket-Extensions"),
+        EchoOptions {
+            delay,
+            ping,
+            close_after,
+            code,
+            reason,
+            drop_after,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Echo(handshake))
+}
@@ -1,7 +1,25 @@
+use httpbin::cli::Config;
 use salvo::Router;
 
+mod barcode;
 mod base64;
+mod codec;
+mod compress;
+mod fake;
+mod hmac;
+mod ids;
+mod random;
+mod time;
 
-pub fn api() -> Router {
-    Router::new().push(Router::with_path("/base64").push(base64::api()))
+pub fn api(cfg: &Config) -> Router {
+    Router::new()
+        .push(barcode::api())
+        .push(Router::with_path("/base64").push(base64::api()))
+        .push(codec::api())
+        .push(compress::api(cfg.compress.clone()))
+        .push(Router::with_path("/fake").push(fake::api(cfg.fake.clone())))
+        .push(Router::with_path("/hmac").push(hmac::api()))
+        .push(Router::with_path("/id").push(ids::api()))
+        .push(Router::with_path("/random").push(random::api()))
+        .push(Router::with_path("/time").push(time::api()))
 }
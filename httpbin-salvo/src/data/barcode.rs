@@ -0,0 +1,84 @@
+use anyhow::Result;
+use httpbin::data::barcode::{BarcodeFormat, QrEcc, Symbology};
+use salvo::{
+    hyper::{header::CONTENT_TYPE, Body},
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct BarcodeReq {
+    #[extract(source(from = "param"))]
+    pub symbology: Option<Symbology>,
+
+    #[extract(source(from = "query"))]
+    pub data: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub ecc: Option<QrEcc>,
+
+    #[extract(source(from = "query"))]
+    pub size: Option<usize>,
+
+    #[extract(source(from = "query"))]
+    pub format: Option<BarcodeFormat>,
+}
+
+struct BarcodeRes {
+    data: Vec<u8>,
+    content_type: &'static str,
+}
+
+impl Piece for BarcodeRes {
+    fn render(self, res: &mut Response) {
+        res.set_status_code(StatusCode::OK);
+        // The content types of the formats are always valid
+        let _ = res.add_header(CONTENT_TYPE, self.content_type, true);
+        res.set_body(Body::from(self.data).into());
+    }
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .push(Router::with_path("/qrcode").get(qrcode))
+        .push(Router::with_path("/barcode/<symbology>").get(barcode))
+}
+
+#[handler]
+async fn qrcode(req: &mut Request) -> Result<BarcodeRes> {
+    let req = req.extract::<BarcodeReq>().await?;
+    let format = req.format.unwrap_or_default();
+
+    let data = httpbin::data::barcode::qrcode(
+        req.data.as_deref().unwrap_or_default(),
+        req.ecc,
+        req.size,
+        format,
+    )?;
+
+    Ok(BarcodeRes {
+        data,
+        content_type: format.content_type(),
+    })
+}
+
+#[handler]
+async fn barcode(req: &mut Request) -> Result<BarcodeRes> {
+    let req = req.extract::<BarcodeReq>().await?;
+    let format = req.format.unwrap_or_default();
+
+    let symbology = req
+        .symbology
+        .ok_or_else(|| anyhow::anyhow!("the symbology is missing"))?;
+    let data = httpbin::data::barcode::barcode(
+        req.data.as_deref().unwrap_or_default(),
+        symbology,
+        req.size,
+        format,
+    )?;
+
+    Ok(BarcodeRes {
+        data,
+        content_type: format.content_type(),
+    })
+}
@@ -1,5 +1,7 @@
 use anyhow::Result;
-use httpbin::data::base64::Base64Engine;
+use httpbin::data::base64::{
+    Base64Engine, Base64EngineInfo, Base64Padding, Base64Wrap, Base64WrapMode,
+};
 use salvo::{
     hyper::Body,
     hyper::{body::to_bytes, header::CONTENT_TYPE},
@@ -17,6 +19,15 @@ struct Base64Req {
 
     #[extract(source(from = "query"))]
     pub pad: Option<bool>,
+
+    #[extract(source(from = "query"))]
+    pub padding: Option<Base64Padding>,
+
+    #[extract(source(from = "query"))]
+    pub wrap: Option<Base64WrapMode>,
+
+    #[extract(source(from = "query"))]
+    pub label: Option<String>,
 }
 
 enum Base64Res {
@@ -45,6 +56,9 @@ pub fn api() -> Router {
     Router::new()
         .push(Router::with_path("/encode/<engine>").post(base64_encode))
         .push(Router::with_path("/decode/<engine>").post(base64_decode))
+        .push(Router::with_path("/stream/encode/<engine>").post(base64_encode_stream))
+        .push(Router::with_path("/stream/decode/<engine>").post(base64_decode_stream))
+        .push(Router::with_path("/engines").get(base64_engines))
 }
 
 #[handler]
@@ -56,11 +70,20 @@ async fn base64_encode(req: &mut Request) -> Result<Base64Res> {
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: req.alphabet.unwrap_or_default(),
             pad: req.pad.unwrap_or_default(),
+            padding: req.padding,
         }),
         _ => None,
     };
 
-    let encoded = httpbin::data::base64::encode(&data, req.engine, config)?;
+    let encoded = match req.wrap {
+        Some(wrap) => httpbin::data::base64::encode_wrapped(
+            &data,
+            req.engine,
+            config,
+            Base64Wrap::new(wrap, req.label),
+        )?,
+        None => httpbin::data::base64::encode(&data, req.engine, config)?,
+    };
 
     Ok(Base64Res::OkText(encoded))
 }
@@ -80,6 +103,7 @@ async fn base64_decode(req: &mut Request) -> Result<Base64Res> {
         Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
             alphabet: req.alphabet.unwrap_or_default(),
             pad: req.pad.unwrap_or_default(),
+            padding: req.padding,
         }),
         _ => None,
     };
@@ -101,3 +125,53 @@ async fn base64_decode(req: &mut Request) -> Result<Base64Res> {
         }
     }
 }
+
+#[handler]
+async fn base64_encode_stream(req: &mut Request, res: &mut Response) -> Result<()> {
+    let body = req.take_body().unwrap_or_default();
+    let req = req.extract::<Base64Req>().await?;
+
+    let config = match req.engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: req.alphabet.unwrap_or_default(),
+            pad: req.pad.unwrap_or_default(),
+            padding: req.padding,
+        }),
+        _ => None,
+    };
+    let wrap = req.wrap.map(|wrap| Base64Wrap::new(wrap, req.label));
+
+    let encoded = httpbin::data::base64::encode_stream(body, req.engine, config, wrap)?;
+
+    res.add_header(CONTENT_TYPE, "text/plain; charset=utf-8", true)?;
+    res.streaming(encoded)?;
+
+    Ok(())
+}
+
+#[handler]
+async fn base64_decode_stream(req: &mut Request, res: &mut Response) -> Result<()> {
+    let body = req.take_body().unwrap_or_default();
+    let req = req.extract::<Base64Req>().await?;
+
+    let config = match req.engine {
+        Base64Engine::Custom => Some(httpbin::data::base64::Base64Config {
+            alphabet: req.alphabet.unwrap_or_default(),
+            pad: req.pad.unwrap_or_default(),
+            padding: req.padding,
+        }),
+        _ => None,
+    };
+
+    let decoded = httpbin::data::base64::decode_stream(body, req.engine, config)?;
+
+    res.add_header(CONTENT_TYPE, "application/octet-stream", true)?;
+    res.streaming(decoded)?;
+
+    Ok(())
+}
+
+#[handler]
+async fn base64_engines() -> Json<Vec<Base64EngineInfo>> {
+    Json(httpbin::data::base64::engines())
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use httpbin::data::codec::{Codec, CodecConfig, PercentSet};
+use salvo::{
+    hyper::Body,
+    hyper::{body::to_bytes, header::CONTENT_TYPE},
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct CodecReq {
+    #[extract(source(from = "param"))]
+    pub codec: Codec,
+
+    #[extract(source(from = "query"))]
+    pub set: Option<PercentSet>,
+}
+
+enum CodecRes {
+    OkText(String),
+    OkBinary { data: Vec<u8>, content_type: String },
+}
+
+impl Piece for CodecRes {
+    fn render(self, res: &mut Response) {
+        match self {
+            CodecRes::OkText(text) => {
+                res.set_status_code(StatusCode::OK);
+                res.render(text);
+            }
+            CodecRes::OkBinary { data, content_type } => {
+                res.set_status_code(StatusCode::OK);
+                // TODO: how to handle the error?
+                let _ = res.add_header(CONTENT_TYPE, content_type, true);
+                res.set_body(Body::from(data).into());
+            }
+        }
+    }
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .push(Router::with_path("/encode/<codec>").post(codec_encode))
+        .push(Router::with_path("/decode/<codec>").post(codec_decode))
+}
+
+#[handler]
+async fn codec_encode(req: &mut Request) -> Result<CodecRes> {
+    let data = req.payload().await?.clone();
+    let req = req.extract::<CodecReq>().await?;
+
+    let config = CodecConfig {
+        percent_set: req.set.unwrap_or_default(),
+    };
+
+    let encoded = httpbin::data::codec::encode(&data, req.codec, Some(config))?;
+
+    Ok(CodecRes::OkText(encoded))
+}
+
+#[handler]
+async fn codec_decode(req: &mut Request) -> Result<CodecRes> {
+    let data = match req.take_body() {
+        Some(body) => {
+            let bytes = to_bytes(body).await?;
+            String::from_utf8(bytes.to_vec())?
+        }
+        None => "".to_string(),
+    };
+    let req = req.extract::<CodecReq>().await?;
+
+    let decoded = httpbin::data::codec::decode(&data, req.codec)?;
+
+    match String::from_utf8(decoded.clone()) {
+        Ok(text) => Ok(CodecRes::OkText(text)),
+        Err(_) => {
+            let kind = infer::get(&decoded);
+
+            Ok(CodecRes::OkBinary {
+                data: decoded,
+                content_type: kind
+                    .map(|k| k.mime_type())
+                    .unwrap_or("application/octet-stream")
+                    .to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use httpbin::{cli::CompressConfig, data::compress::Compression};
+use salvo::{
+    hyper::{header::CONTENT_TYPE, Body},
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct CompressReq {
+    #[extract(source(from = "param"))]
+    pub algo: Compression,
+
+    #[extract(source(from = "query"))]
+    pub level: Option<i32>,
+}
+
+enum CompressRes {
+    Text(String),
+    Binary(Vec<u8>, &'static str),
+}
+
+impl Piece for CompressRes {
+    fn render(self, res: &mut Response) {
+        match self {
+            CompressRes::Text(s) => res.render(s),
+            CompressRes::Binary(data, content_type) => {
+                // The content types of the algorithms and of infer are always valid
+                let _ = res.add_header(CONTENT_TYPE, content_type, true);
+                res.set_body(Body::from(data).into());
+            }
+        }
+    }
+}
+
+pub fn api(cfg: CompressConfig) -> Router {
+    Router::new()
+        .push(Router::with_path("/compress/<algo>").post(compress))
+        .push(Router::with_path("/decompress/<algo>").post(Decompress(cfg)))
+}
+
+#[handler]
+async fn compress(req: &mut Request) -> Result<CompressRes> {
+    let data = req.payload().await?.clone();
+    let req = req.extract::<CompressReq>().await?;
+
+    let compressed = httpbin::data::compress::compress(&data, req.algo, req.level)?;
+
+    Ok(CompressRes::Binary(compressed, req.algo.content_type()))
+}
+
+struct Decompress(CompressConfig);
+
+#[handler]
+impl Decompress {
+    async fn handle(&self, req: &mut Request) -> Result<CompressRes> {
+        let data = req.payload().await?.clone();
+        let req = req.extract::<CompressReq>().await?;
+
+        let decompressed = httpbin::data::compress::decompress(&data, req.algo, self.0.max_size)?;
+
+        match String::from_utf8(decompressed) {
+            Ok(s) => Ok(CompressRes::Text(s)),
+            Err(e) => {
+                let decompressed = e.into_bytes();
+                let content_type = infer::get(&decompressed)
+                    .map(|kind| kind.mime_type())
+                    .unwrap_or("application/octet-stream");
+
+                Ok(CompressRes::Binary(decompressed, content_type))
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use httpbin::{
+    cli::FakeConfig,
+    data::fake::{FakeKind, FakeLocale},
+};
+use salvo::prelude::*;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct FakeReq {
+    #[extract(source(from = "param"))]
+    pub kind: Option<FakeKind>,
+
+    #[extract(source(from = "query"))]
+    pub locale: Option<FakeLocale>,
+
+    #[extract(source(from = "query"))]
+    pub count: Option<usize>,
+
+    #[extract(source(from = "query"))]
+    pub seed: Option<u64>,
+}
+
+pub fn api(cfg: FakeConfig) -> Router {
+    Router::new()
+        .push(Router::with_path("/schema").post(FakeSchema(cfg.clone())))
+        .push(Router::with_path("/<kind>").get(FakeRecords(cfg)))
+}
+
+struct FakeSchema(FakeConfig);
+
+#[handler]
+impl FakeSchema {
+    async fn handle(&self, req: &mut Request) -> Result<Json<Value>> {
+        let schema = req.parse_json::<Value>().await?;
+        let req = req.extract::<FakeReq>().await?;
+
+        let instances =
+            httpbin::data::fake::schema(&schema, req.count, req.seed, self.0.max_count)?;
+
+        Ok(Json(instances))
+    }
+}
+
+struct FakeRecords(FakeConfig);
+
+#[handler]
+impl FakeRecords {
+    async fn handle(&self, req: &mut Request) -> Result<Json<Value>> {
+        let req = req.extract::<FakeReq>().await?;
+
+        let kind = req
+            .kind
+            .ok_or_else(|| anyhow::anyhow!("the kind of record is missing"))?;
+        let records =
+            httpbin::data::fake::records(kind, req.locale, req.count, req.seed, self.0.max_count)?;
+
+        Ok(Json(records))
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use httpbin::data::{
+    base64::Base64Engine,
+    hmac::{HmacAlgorithm, HmacKeyEncoding, HmacMac, HmacMacEncoding, HmacVerification},
+};
+use salvo::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct HmacReq {
+    #[extract(source(from = "param"))]
+    pub algorithm: HmacAlgorithm,
+
+    #[extract(source(from = "query"))]
+    pub key: String,
+
+    #[extract(source(from = "query"))]
+    pub key_encoding: Option<HmacKeyEncoding>,
+
+    #[extract(source(from = "query"))]
+    pub engine: Option<Base64Engine>,
+
+    #[extract(source(from = "query"))]
+    pub mac: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub mac_encoding: Option<HmacMacEncoding>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .push(Router::with_path("/<algorithm>/sign").post(hmac_sign))
+        .push(Router::with_path("/<algorithm>/verify").post(hmac_verify))
+}
+
+#[handler]
+async fn hmac_sign(req: &mut Request) -> Result<Json<HmacMac>> {
+    let data = req.payload().await?.clone();
+    let req = req.extract::<HmacReq>().await?;
+
+    let key = httpbin::data::hmac::decode_key(
+        &req.key,
+        req.key_encoding.unwrap_or_default(),
+        req.engine,
+    )?;
+
+    let mac = httpbin::data::hmac::sign(req.algorithm, &key, &data);
+
+    Ok(Json(mac.as_slice().into()))
+}
+
+#[handler]
+async fn hmac_verify(req: &mut Request) -> Result<Json<HmacVerification>> {
+    let data = req.payload().await?.clone();
+    let req = req.extract::<HmacReq>().await?;
+
+    let key = httpbin::data::hmac::decode_key(
+        &req.key,
+        req.key_encoding.unwrap_or_default(),
+        req.engine,
+    )?;
+    let mac = httpbin::data::hmac::decode_mac(
+        &req.mac.unwrap_or_default(),
+        req.mac_encoding.unwrap_or_default(),
+    )?;
+
+    let verification = httpbin::data::hmac::verify(req.algorithm, &key, &data, &mac);
+
+    Ok(Json(verification))
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use httpbin::data::ids::{IdInspection, IdKind, IdOptions};
+use salvo::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct IdReq {
+    #[extract(source(from = "param"))]
+    pub kind: Option<IdKind>,
+
+    #[extract(source(from = "query"))]
+    pub id: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub timestamp: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub epoch: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub worker_id: Option<u16>,
+
+    #[extract(source(from = "query"))]
+    pub alphabet: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub length: Option<usize>,
+
+    #[extract(source(from = "query"))]
+    pub prefix: Option<String>,
+}
+
+impl IdReq {
+    fn kind(&self) -> Result<IdKind> {
+        self.kind
+            .ok_or_else(|| anyhow::anyhow!("the kind of id is missing"))
+    }
+
+    fn options(self) -> IdOptions {
+        IdOptions {
+            timestamp: self.timestamp,
+            epoch: self.epoch,
+            worker_id: self.worker_id,
+            alphabet: self.alphabet,
+            length: self.length,
+            prefix: self.prefix,
+        }
+    }
+}
+
+pub fn api() -> Router {
+    Router::with_path("/<kind>")
+        .get(id_generate)
+        .push(Router::with_path("/parse").get(id_parse))
+}
+
+#[handler]
+async fn id_generate(req: &mut Request) -> Result<String> {
+    let req = req.extract::<IdReq>().await?;
+
+    let id = httpbin::data::ids::generate(req.kind()?, req.options())?;
+
+    Ok(id)
+}
+
+#[handler]
+async fn id_parse(req: &mut Request) -> Result<Json<IdInspection>> {
+    let mut req = req.extract::<IdReq>().await?;
+
+    let kind = req.kind()?;
+    let id = req
+        .id
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("the id is missing"))?;
+    let inspection = httpbin::data::ids::parse(kind, &id, req.options())?;
+
+    Ok(Json(inspection))
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use salvo::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct RandomReq {
+    #[extract(source(from = "query"))]
+    pub min: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub max: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub charset: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub length: Option<usize>,
+
+    #[extract(source(from = "query"))]
+    pub items: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub seed: Option<u64>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .push(Router::with_path("/int").get(random_int))
+        .push(Router::with_path("/float").get(random_float))
+        .push(Router::with_path("/string").get(random_string))
+        .push(Router::with_path("/choice").get(random_choice))
+        .push(Router::with_path("/shuffle").get(random_shuffle))
+}
+
+#[handler]
+async fn random_int(req: &mut Request) -> Result<String> {
+    let req = req.extract::<RandomReq>().await?;
+
+    let min = req.min.map(|min| min.parse()).transpose()?;
+    let max = req.max.map(|max| max.parse()).transpose()?;
+    let int = httpbin::data::random::int(min, max, req.seed)?;
+
+    Ok(int.to_string())
+}
+
+#[handler]
+async fn random_float(req: &mut Request) -> Result<String> {
+    let req = req.extract::<RandomReq>().await?;
+
+    let min = req.min.map(|min| min.parse()).transpose()?;
+    let max = req.max.map(|max| max.parse()).transpose()?;
+    let float = httpbin::data::random::float(min, max, req.seed)?;
+
+    Ok(float.to_string())
+}
+
+#[handler]
+async fn random_string(req: &mut Request) -> Result<String> {
+    let req = req.extract::<RandomReq>().await?;
+
+    // Parsing a charset never fails
+    let charset = req.charset.map(|charset| charset.parse().unwrap());
+    let string = httpbin::data::random::string(charset, req.length, req.seed)?;
+
+    Ok(string)
+}
+
+#[handler]
+async fn random_choice(req: &mut Request) -> Result<String> {
+    let req = req.extract::<RandomReq>().await?;
+
+    // Parsing items never fails
+    let items = req.items.unwrap_or_default().parse().unwrap();
+    let choice = httpbin::data::random::choice(items, req.seed)?;
+
+    Ok(choice)
+}
+
+#[handler]
+async fn random_shuffle(req: &mut Request) -> Result<Json<Vec<String>>> {
+    let req = req.extract::<RandomReq>().await?;
+
+    // Parsing items never fails
+    let items = req.items.unwrap_or_default().parse().unwrap();
+
+    Ok(Json(httpbin::data::random::shuffle(items, req.seed)))
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use httpbin::data::time::{TimeFormat, TimeInspection};
+use salvo::prelude::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct TimeReq {
+    #[extract(source(from = "query"))]
+    pub time: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub from: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub tz: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub format: Option<TimeFormat>,
+}
+
+pub fn api() -> Router {
+    Router::new()
+        .push(Router::with_path("/now").get(time_now))
+        .push(Router::with_path("/parse").get(time_parse))
+        .push(Router::with_path("/convert").get(time_convert))
+}
+
+#[handler]
+async fn time_now(req: &mut Request) -> Result<String> {
+    let req = req.extract::<TimeReq>().await?;
+
+    let now = httpbin::data::time::now(req.format.unwrap_or_default(), req.tz.as_deref())?;
+
+    Ok(now)
+}
+
+#[handler]
+async fn time_parse(req: &mut Request) -> Result<Json<TimeInspection>> {
+    let req = req.extract::<TimeReq>().await?;
+
+    let inspection = httpbin::data::time::parse(
+        req.time.as_deref().unwrap_or_default(),
+        req.from.as_deref(),
+        req.tz.as_deref(),
+    )?;
+
+    Ok(Json(inspection))
+}
+
+#[handler]
+async fn time_convert(req: &mut Request) -> Result<String> {
+    let req = req.extract::<TimeReq>().await?;
+
+    let time = httpbin::data::time::convert(
+        req.time.as_deref().unwrap_or_default(),
+        req.from.as_deref(),
+        req.tz.as_deref(),
+        req.format.unwrap_or_default(),
+    )?;
+
+    Ok(time)
+}
@@ -0,0 +1,48 @@
+use httpbin::listener;
+use salvo::{
+    addr::SocketAddr,
+    hyper::{self, server::conn::Http, service::service_fn, Body},
+    Router, Service,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+};
+
+/// Serve `router` on a cleartext TCP listener
+///
+/// salvo's own server cannot switch connections to h2c, so connections are served here the way
+/// salvo's server does.
+pub async fn serve(listener: TcpListener, router: Router) {
+    let service = Service::new(router);
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        serve_connection(stream, &service, remote_addr.into());
+    }
+}
+
+/// Serve a cleartext connection with `service`, switching it to HTTP/2 if a request asks for it
+pub fn serve_connection<S>(io: S, service: &Service, remote_addr: SocketAddr)
+where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let handler = service.hyper_handler(Some(remote_addr));
+    let service = service_fn(move |req: hyper::Request<Body>| {
+        let mut handler = handler.clone();
+        async move {
+            match httpbin::h2c::upgrade(req, handler.clone()).await {
+                Ok(res) => Ok(res),
+                Err(req) => hyper::service::Service::call(&mut handler, req).await,
+            }
+        }
+    });
+
+    tokio::spawn(Http::new().serve_connection(io, service).with_upgrades());
+}
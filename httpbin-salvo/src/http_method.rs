@@ -7,6 +7,7 @@ use serde::Serialize;
 struct Http {
     method: String,
     uri: String,
+    protocol: String,
     headers: HashMap<String, String>,
     origin: Option<String>,
     query: Option<HashMap<String, String>>,
@@ -99,6 +100,7 @@ async fn anything(req: &mut Request) -> Json<Http> {
     Json(Http {
         method: req.method().to_string(),
         uri: req.uri().to_string(),
+        protocol: format!("{:?}", req.version()),
         headers,
         origin: req.remote_addr().map(|addr| addr.to_string()),
         query,
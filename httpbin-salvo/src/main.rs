@@ -1,13 +1,25 @@
-use httpbin::cli::Cli;
+use anyhow::{bail, Result};
+use futures_util::{future, FutureExt};
+use httpbin::{
+    cli::{Cli, Config, ListenerConfig},
+    listener,
+    tls::{Ca, Tls},
+};
 use salvo::cors::Cors;
 use salvo::prelude::*;
 
 mod data;
+mod h2c;
 mod http_method;
 mod request_inspection;
+mod sse;
+mod tls;
+#[cfg(unix)]
+mod unix;
+mod websocket;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<()> {
     let cfg = Cli::parse().load_config();
 
     if std::env::var_os("RUST_LOG").is_none() {
@@ -15,17 +27,56 @@ async fn main() {
     }
     tracing_subscriber::fmt::init();
 
+    let tls = cfg.tls.as_ref().map(Tls::load).transpose()?;
+    if tls.as_ref().is_some_and(|tls| tls.http3) {
+        bail!("salvo cannot serve HTTP/3, so tls.http3 cannot be set");
+    }
+    let tls_config = tls.as_ref().map(Tls::server_config).transpose()?;
+
+    // Each listener gets its own server, as salvo can only join listeners of known types
+    let mut servers = Vec::new();
+    #[cfg(unix)]
+    let mut sockets = Vec::new();
+    for listener in cfg.listeners() {
+        let router = router(&cfg, Ca::from(tls.as_ref()));
+        servers.push(match listener {
+            ListenerConfig::Tcp { ip, port } => {
+                let listener = tokio::net::TcpListener::bind((ip, port)).await?;
+                match &tls_config {
+                    Some(config) => tls::serve(listener, config.clone(), router).boxed_local(),
+                    None => h2c::serve(listener, router).boxed_local(),
+                }
+            }
+            #[cfg(unix)]
+            ListenerConfig::Unix { path, mode } => {
+                let (listener, socket) = listener::bind_unix(&path, mode)?;
+                sockets.push(socket);
+                listener.set_nonblocking(true)?;
+                let listener = tokio::net::UnixListener::from_std(listener)?;
+                unix::serve(listener, router).boxed_local()
+            }
+        });
+    }
+    // The sockets are removed once the servers return
+    tokio::select! {
+        _ = future::join_all(servers) => {}
+        signal = listener::shutdown_signal() => signal?,
+    }
+
+    Ok(())
+}
+
+fn router(cfg: &Config, ca: Ca) -> Router {
     let cors = Cors::builder().allow_any_origin().build();
 
-    let router = Router::new()
+    Router::new()
         .hoop(cors)
         .hoop(TrailingSlash::new_remove())
         .hoop(Logger)
-        .push(data::api())
+        .push(data::api(cfg))
         .push(http_method::api())
-        .push(request_inspection::api());
-
-    Server::new(TcpListener::bind((cfg.ip, cfg.port)))
-        .serve(router)
-        .await
+        .push(request_inspection::api())
+        .push(sse::api())
+        .push(tls::api(ca))
+        .push(websocket::api())
 }
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use salvo::{hyper::header::USER_AGENT, prelude::*};
+use httpbin::inspect::MimeInspection;
+use salvo::{
+    hyper::header::{CONTENT_TYPE, USER_AGENT},
+    prelude::*,
+};
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -26,6 +30,7 @@ pub fn api() -> Router {
         .push(Router::with_path("/headers").get(headers))
         .push(Router::with_path("/ip").get(ip))
         .push(Router::with_path("/user-agent").get(user_agent))
+        .push(Router::with_path("/inspect/mime").post(inspect_mime))
 }
 
 #[handler]
@@ -80,3 +85,15 @@ async fn user_agent(req: &Request, res: &mut Response) {
         ),
     }
 }
+
+#[handler]
+async fn inspect_mime(req: &mut Request) -> anyhow::Result<Json<MimeInspection>> {
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    let body = req.payload().await?;
+
+    Ok(Json(httpbin::inspect::mime(body, content_type.as_deref())))
+}
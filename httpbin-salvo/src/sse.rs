@@ -0,0 +1,66 @@
+use anyhow::Result;
+use httpbin::sse::SseOptions;
+use salvo::{
+    hyper::header::{CACHE_CONTROL, CONTENT_TYPE},
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct SseReq {
+    #[extract(source(from = "query"))]
+    pub count: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub interval: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub retry: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub heartbeat: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub drop_after: Option<u64>,
+}
+
+pub fn api() -> Router {
+    Router::with_path("/sse").get(sse)
+}
+
+#[handler]
+async fn sse(req: &mut Request, res: &mut Response) -> Result<()> {
+    let SseReq {
+        count,
+        interval,
+        retry,
+        heartbeat,
+        drop_after,
+    } = req.extract::<SseReq>().await?;
+    let last_event_id = req
+        .headers()
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let events = httpbin::sse::events(
+        SseOptions {
+            count,
+            interval,
+            retry,
+            heartbeat,
+            drop_after,
+        },
+        last_event_id,
+    )?;
+
+    match events {
+        Some(events) => {
+            res.add_header(CONTENT_TYPE, "text/event-stream", true)?;
+            res.add_header(CACHE_CONTROL, "no-cache", true)?;
+            res.streaming(events)?;
+        }
+        None => res.set_status_code(StatusCode::NO_CONTENT),
+    }
+
+    Ok(())
+}
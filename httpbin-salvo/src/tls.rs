@@ -0,0 +1,76 @@
+use httpbin::tls::{Ca, CertificateInfo, ClientCertificates};
+use rustls::ServerConfig;
+use salvo::{
+    hyper::{
+        self,
+        header::CONTENT_TYPE,
+        server::conn::Http,
+        service::{service_fn, Service as _},
+        Body,
+    },
+    prelude::*,
+    Service,
+};
+use tokio::net::TcpListener;
+
+pub fn api(ca: Ca) -> Router {
+    Router::new()
+        .push(Router::with_path("/tls/ca.pem").get(CaPem(ca)))
+        .push(Router::with_path("/client-cert").get(client_cert))
+}
+
+struct CaPem(Ca);
+
+#[handler]
+impl CaPem {
+    async fn handle(&self, res: &mut Response) {
+        let CaPem(Ca(pem)) = self;
+        match pem {
+            Some(pem) => {
+                // The content type is always valid
+                let _ = res.add_header(CONTENT_TYPE, "application/x-pem-file", true);
+                res.set_body(Body::from(pem.clone()).into());
+            }
+            None => {
+                res.set_status_code(StatusCode::NOT_FOUND);
+                res.render("the certificate was not generated at startup");
+            }
+        }
+    }
+}
+
+#[handler]
+async fn client_cert(req: &mut Request) -> anyhow::Result<Json<Vec<CertificateInfo>>> {
+    let certs = req
+        .extensions()
+        .get::<ClientCertificates>()
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Json(certs.inspect()?))
+}
+
+/// Serve `router` on the TLS connections of a TCP listener, with the [`ClientCertificates`] of
+/// each connection in the request extensions
+///
+/// salvo's own rustls listener always offers TLS 1.2 and 1.3 with h2 and http/1.1, and its
+/// connection types are sealed, so connections accepted by `httpbin::tls::accept` are served
+/// here the way salvo's server does.
+pub async fn serve(listener: TcpListener, config: ServerConfig, router: Router) {
+    let service = Service::new(router);
+    let mut incoming = httpbin::tls::accept(listener, config);
+
+    while let Some((stream, remote_addr)) = incoming.recv().await {
+        let certs = ClientCertificates::from_peer(stream.get_ref().1.peer_certificates());
+        let mut handler = service.hyper_handler(Some(remote_addr.into()));
+        let handler = service_fn(move |mut req: hyper::Request<Body>| {
+            req.extensions_mut().insert(certs.clone());
+            handler.call(req)
+        });
+        tokio::spawn(
+            Http::new()
+                .serve_connection(stream, handler)
+                .with_upgrades(),
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use httpbin::listener;
+use salvo::{Router, Service};
+use tokio::net::UnixListener;
+
+use crate::h2c;
+
+/// Serve `router` on a Unix domain socket bound by `listener::bind_unix`
+///
+/// salvo's own Unix listener can only bind the socket itself, so connections are served here the
+/// way salvo's server does.
+pub async fn serve(listener: UnixListener, router: Router) {
+    let service = Service::new(router);
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        h2c::serve_connection(stream, &service, remote_addr.into());
+    }
+}
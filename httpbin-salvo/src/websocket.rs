@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+use httpbin::websocket::EchoOptions;
+use salvo::{
+    hyper::{upgrade::OnUpgrade, HeaderMap},
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize, Extractible, Debug)]
+struct EchoReq {
+    #[extract(source(from = "query"))]
+    pub delay: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub ping: Option<u64>,
+
+    #[extract(source(from = "query"))]
+    pub close_after: Option<usize>,
+
+    #[extract(source(from = "query"))]
+    pub code: Option<u16>,
+
+    #[extract(source(from = "query"))]
+    pub reason: Option<String>,
+
+    #[extract(source(from = "query"))]
+    pub drop_after: Option<usize>,
+}
+
+pub fn api() -> Router {
+    Router::with_path("/ws/echo").get(echo)
+}
+
+/// The values of a header joined by commas, empty if absent
+fn header_values(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[handler]
+async fn echo(req: &mut Request, res: &mut Response) -> Result<()> {
+    let EchoReq {
+        delay,
+        ping,
+        close_after,
+        code,
+        reason,
+        drop_after,
+    } = req.extract::<EchoReq>().await?;
+
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let handshake = httpbin::websocket::handshake(
+        header("upgrade"),
+        header("sec-websocket-key"),
+        header("sec-websocket-version"),
+        &header_values(headers, "sec-websocket-protocol"),
+        &header_values(headers, "sec-websocket-extensions"),
+        EchoOptions {
+            delay,
+            ping,
+            close_after,
+            code,
+            reason,
+            drop_after,
+        },
+    )?;
+
+    let on_upgrade = req
+        .extensions_mut()
+        .remove::<OnUpgrade>()
+        .ok_or_else(|| anyhow!("the connection cannot be upgraded"))?;
+
+    res.set_status_code(StatusCode::SWITCHING_PROTOCOLS);
+    for (name, value) in handshake.headers() {
+        res.add_header(name, value, true)?;
+    }
+
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            let _ = httpbin::websocket::echo(upgraded, handshake).await;
+        }
+    });
+
+    Ok(())
+}
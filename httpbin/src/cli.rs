@@ -1,4 +1,4 @@
-use std::net::IpAddr;
+use std::{net::IpAddr, path::PathBuf};
 
 use clap::Parser;
 use indexmap::IndexMap;
@@ -34,7 +34,44 @@ impl Cli {
 pub struct Config {
     pub ip: IpAddr,
     pub port: u16,
+    /// More addresses to listen on next to `ip` and `port`
+    #[serde(default)]
+    pub listeners: Vec<ListenerConfig>,
     pub openapi: OpenApiConfig,
+    #[serde(default)]
+    pub uuid: UuidConfig,
+    #[serde(default)]
+    pub fake: FakeConfig,
+    #[serde(default)]
+    pub compress: CompressConfig,
+    /// Serve HTTPS instead of HTTP if set
+    pub tls: Option<TlsConfig>,
+}
+
+impl Config {
+    /// All addresses to listen on, starting with `ip` and `port`
+    pub fn listeners(&self) -> Vec<ListenerConfig> {
+        let mut listeners = vec![ListenerConfig::Tcp {
+            ip: self.ip,
+            port: self.port,
+        }];
+        listeners.extend(self.listeners.iter().cloned());
+        listeners
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ListenerConfig {
+    /// A TCP address, served over TLS if it is configured
+    Tcp { ip: IpAddr, port: u16 },
+    /// A Unix domain socket, always served over plain HTTP
+    #[cfg(unix)]
+    Unix {
+        path: PathBuf,
+        /// The permissions of the socket file, such as `0o660`
+        mode: Option<u32>,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -62,3 +99,108 @@ pub struct OpenApiServer {
     pub url: String,
     pub description: String,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UuidConfig {
+    /// The maximum number of UUIDs generated by one request
+    pub max_count: usize,
+}
+
+impl Default for UuidConfig {
+    fn default() -> Self {
+        Self { max_count: 1000 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FakeConfig {
+    /// The maximum number of instances generated by one request
+    pub max_count: usize,
+}
+
+impl Default for FakeConfig {
+    fn default() -> Self {
+        Self { max_count: 100 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CompressConfig {
+    /// The maximum size in bytes of data decompressed by one request
+    pub max_size: usize,
+}
+
+impl Default for CompressConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// The PEM certificate chain to serve
+    pub cert: Option<PathBuf>,
+    /// The PEM private key of the certificate
+    pub key: Option<PathBuf>,
+    /// Generate a certificate signed by a new CA at startup instead of reading `cert` and `key`
+    pub self_signed: bool,
+    /// The DNS names and IPs of the generated certificate
+    pub subject_alt_names: Vec<String>,
+    /// The minimum TLS version to accept
+    pub min_version: TlsVersion,
+    /// The ALPN protocols to offer, in order of preference
+    pub alpn: Vec<String>,
+    /// Also serve HTTP/3 over QUIC on `ip` and the UDP port of `port`, advertised with `Alt-Svc`
+    pub http3: bool,
+    /// Whether to ask clients for a certificate signed by `client_ca`
+    pub client_auth: ClientAuth,
+    /// The PEM CA certificates to verify client certificates with
+    pub client_ca: Option<PathBuf>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            cert: None,
+            key: None,
+            self_signed: false,
+            subject_alt_names: vec![
+                "localhost".to_string(),
+                "127.0.0.1".to_string(),
+                "::1".to_string(),
+            ],
+            min_version: TlsVersion::default(),
+            alpn: vec!["h2".to_string(), "http/1.1".to_string()],
+            http3: false,
+            client_auth: ClientAuth::default(),
+            client_ca: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuth {
+    /// Do not ask for a client certificate
+    #[default]
+    None,
+    /// Ask for a client certificate, but accept clients without one
+    Request,
+    /// Only accept clients with a certificate
+    Require,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TlsVersion {
+    #[default]
+    #[serde(rename = "1.2")]
+    Tls12,
+    #[serde(rename = "1.3")]
+    Tls13,
+}
@@ -1,2 +1,10 @@
+pub mod barcode;
 pub mod base64;
+pub mod codec;
+pub mod compress;
+pub mod fake;
+pub mod hmac;
+pub mod ids;
+pub mod random;
+pub mod time;
 pub mod uuid;
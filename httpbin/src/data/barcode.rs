@@ -0,0 +1,511 @@
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BarcodeFormat {
+    #[default]
+    Png,
+    Svg,
+    /// Unicode block characters, two rows of modules per line
+    Text,
+}
+
+impl BarcodeFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            BarcodeFormat::Png => "image/png",
+            BarcodeFormat::Svg => "image/svg+xml",
+            BarcodeFormat::Text => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// The error correction level of a QR code, recovering about 7%, 15%, 25% or 30% of the data
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QrEcc {
+    L,
+    #[default]
+    M,
+    Q,
+    H,
+}
+
+impl From<QrEcc> for qrcode::EcLevel {
+    fn from(ecc: QrEcc) -> Self {
+        match ecc {
+            QrEcc::L => qrcode::EcLevel::L,
+            QrEcc::M => qrcode::EcLevel::M,
+            QrEcc::Q => qrcode::EcLevel::Q,
+            QrEcc::H => qrcode::EcLevel::H,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Symbology {
+    /// Printable ASCII, switching to digit pairs for runs of digits
+    Code128,
+    /// 12 digits and a computed check digit, or 13 digits with a valid check digit
+    Ean13,
+}
+
+#[derive(Error, Debug)]
+pub enum BarcodeError {
+    #[error("the data cannot be encoded as a QR code: {0}")]
+    InvalidQrData(#[from] qrcode::types::QrError),
+    #[error("the data is empty")]
+    EmptyData,
+    #[error("Code 128 only encodes printable ASCII, not {0:?}")]
+    InvalidCode128Char(char),
+    #[error("EAN-13 must be 12 or 13 digits")]
+    InvalidEan13,
+    #[error("the EAN-13 check digit must be {0}")]
+    InvalidEan13CheckDigit(u8),
+    #[error("the size must be between 1 and {0}")]
+    InvalidSize(usize),
+    #[error("the image must not be larger than {0} pixels")]
+    TooLarge(usize),
+}
+
+/// The maximum size of a module in pixels
+pub const MAX_SIZE: usize = 20;
+
+/// The maximum number of pixels of a PNG image
+pub const MAX_PIXELS: usize = 16 * 1024 * 1024;
+
+/// The height of a 1D barcode in modules, and in modules per line of text
+const BAR_HEIGHT: usize = 50;
+const BAR_TEXT_HEIGHT: usize = 8;
+
+/// Generate a QR code, with `size` pixels per module which defaults to 8
+pub fn qrcode(
+    data: &str,
+    ecc: Option<QrEcc>,
+    size: Option<usize>,
+    format: BarcodeFormat,
+) -> Result<Vec<u8>, BarcodeError> {
+    let size = check_size(size.unwrap_or(8))?;
+    let code = qrcode::QrCode::with_error_correction_level(data, ecc.unwrap_or_default().into())?;
+
+    // A QR code needs a quiet zone of 4 modules on every side
+    let matrix = Matrix::new(code.width(), code.width(), 4, 4, |x, y| {
+        code[(x, y)] == qrcode::Color::Dark
+    });
+
+    matrix.render(size, format)
+}
+
+/// Generate a 1D barcode, with `size` pixels per module which defaults to 2
+pub fn barcode(
+    data: &str,
+    symbology: Symbology,
+    size: Option<usize>,
+    format: BarcodeFormat,
+) -> Result<Vec<u8>, BarcodeError> {
+    let size = check_size(size.unwrap_or(2))?;
+    let bars = match symbology {
+        Symbology::Code128 => code128(data)?,
+        Symbology::Ean13 => ean13(data)?,
+    };
+
+    let height = match format {
+        BarcodeFormat::Text => BAR_TEXT_HEIGHT,
+        _ => BAR_HEIGHT,
+    };
+    // A 1D barcode needs a quiet zone of 10 modules on the left and right
+    let matrix = Matrix::new(bars.len(), height, 10, 0, |x, _| bars[x]);
+
+    matrix.render(size, format)
+}
+
+fn check_size(size: usize) -> Result<usize, BarcodeError> {
+    match size {
+        1..=MAX_SIZE => Ok(size),
+        _ => Err(BarcodeError::InvalidSize(MAX_SIZE)),
+    }
+}
+
+/// The bar and space widths of the Code 128 symbols, with 103 to 105 as the starts and 106 as
+/// the stop
+const CODE128: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE128_CODE_B: usize = 100;
+const CODE128_CODE_C: usize = 99;
+const CODE128_START_B: usize = 104;
+const CODE128_START_C: usize = 105;
+const CODE128_STOP: usize = 106;
+
+/// Encode printable ASCII with code set B, switching to code set C for runs of 4 or more digits
+fn code128(data: &str) -> Result<Vec<bool>, BarcodeError> {
+    if data.is_empty() {
+        return Err(BarcodeError::EmptyData);
+    }
+    if let Some(c) = data.chars().find(|c| !(' '..='~').contains(c)) {
+        return Err(BarcodeError::InvalidCode128Char(c));
+    }
+
+    let data = data.as_bytes();
+    let digits_at = |i: usize| data[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+
+    let mut symbols = Vec::new();
+    let mut code_c = digits_at(0) >= 4 || (data.len() == 2 && digits_at(0) == 2);
+    symbols.push(if code_c {
+        CODE128_START_C
+    } else {
+        CODE128_START_B
+    });
+
+    let mut i = 0;
+    while i < data.len() {
+        let digits = digits_at(i);
+        if code_c {
+            if digits >= 2 {
+                symbols.push(((data[i] - b'0') * 10 + (data[i + 1] - b'0')) as usize);
+                i += 2;
+                continue;
+            }
+            symbols.push(CODE128_CODE_B);
+            code_c = false;
+        } else if digits >= 4 && digits % 2 == 0 {
+            symbols.push(CODE128_CODE_C);
+            code_c = true;
+            continue;
+        }
+        // An odd run of digits takes its first digit in code set B
+        symbols.push((data[i] - b' ') as usize);
+        i += 1;
+    }
+
+    let checksum = symbols
+        .iter()
+        .enumerate()
+        .map(|(i, symbol)| i.max(1) * symbol)
+        .sum::<usize>()
+        % 103;
+    symbols.push(checksum);
+    symbols.push(CODE128_STOP);
+
+    let mut bars = Vec::new();
+    for symbol in symbols {
+        for (i, width) in CODE128[symbol].bytes().enumerate() {
+            // Bars and spaces alternate, starting with a bar
+            bars.extend(std::iter::repeat_n(i % 2 == 0, (width - b'0') as usize));
+        }
+    }
+
+    Ok(bars)
+}
+
+/// The left-hand odd parity patterns of EAN-13, the even ones are the reversed right-hand ones
+const EAN13_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+/// The parity of the left-hand digits, which encodes the first digit
+const EAN13_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+fn ean13(data: &str) -> Result<Vec<bool>, BarcodeError> {
+    if !(data.len() == 12 || data.len() == 13) || !data.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(BarcodeError::InvalidEan13);
+    }
+
+    let mut digits: Vec<u8> = data.bytes().map(|b| b - b'0').collect();
+    let sum: u32 = digits[..12]
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d as u32 * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    let check = ((10 - sum % 10) % 10) as u8;
+    match digits.get(12) {
+        Some(&digit) if digit != check => return Err(BarcodeError::InvalidEan13CheckDigit(check)),
+        Some(_) => {}
+        None => digits.push(check),
+    }
+
+    let modules = |pattern: &str| pattern.bytes().map(|b| b == b'1').collect::<Vec<_>>();
+    let left = |digit: u8| modules(EAN13_L[digit as usize]);
+    let right = |digit: u8| left(digit).into_iter().map(|m| !m).collect::<Vec<_>>();
+
+    let mut bars = modules("101");
+    for (&digit, parity) in digits[1..7]
+        .iter()
+        .zip(EAN13_PARITY[digits[0] as usize].bytes())
+    {
+        match parity {
+            b'L' => bars.extend(left(digit)),
+            _ => bars.extend(right(digit).into_iter().rev()),
+        }
+    }
+    bars.extend(modules("01010"));
+    for &digit in &digits[7..] {
+        bars.extend(right(digit));
+    }
+    bars.extend(modules("101"));
+
+    Ok(bars)
+}
+
+/// Dark and light modules, including the quiet zone
+struct Matrix {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+impl Matrix {
+    fn new(
+        width: usize,
+        height: usize,
+        quiet_x: usize,
+        quiet_y: usize,
+        is_dark: impl Fn(usize, usize) -> bool,
+    ) -> Self {
+        let (full_width, full_height) = (width + 2 * quiet_x, height + 2 * quiet_y);
+        let mut dark = vec![false; full_width * full_height];
+        for y in 0..height {
+            for x in 0..width {
+                dark[(y + quiet_y) * full_width + x + quiet_x] = is_dark(x, y);
+            }
+        }
+
+        Self {
+            width: full_width,
+            height: full_height,
+            dark,
+        }
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        y < self.height && self.dark[y * self.width + x]
+    }
+
+    fn render(&self, size: usize, format: BarcodeFormat) -> Result<Vec<u8>, BarcodeError> {
+        match format {
+            BarcodeFormat::Png => self.png(size),
+            BarcodeFormat::Svg => Ok(self.svg(size).into_bytes()),
+            BarcodeFormat::Text => Ok(self.text().into_bytes()),
+        }
+    }
+
+    /// An 8-bit grayscale PNG, which deflates well as rows of modules repeat
+    fn png(&self, size: usize) -> Result<Vec<u8>, BarcodeError> {
+        let (width, height) = (self.width * size, self.height * size);
+        if width * height > MAX_PIXELS {
+            return Err(BarcodeError::TooLarge(MAX_PIXELS));
+        }
+
+        let mut pixels = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        for y in 0..height {
+            // Every scanline starts with its filter type, which is none
+            let mut row = vec![0];
+            for x in 0..width {
+                row.push(if self.is_dark(x / size, y / size) {
+                    0x00
+                } else {
+                    0xff
+                });
+            }
+            // Writing to a `Vec` never fails
+            pixels.write_all(&row).unwrap();
+        }
+        let pixels = pixels.finish().unwrap();
+
+        let mut header = Vec::new();
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // 8-bit grayscale, deflate, adaptive filtering and no interlacing
+        header.extend([8, 0, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &pixels);
+        png_chunk(&mut png, b"IEND", &[]);
+
+        Ok(png)
+    }
+
+    fn svg(&self, size: usize) -> String {
+        let mut path = String::new();
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                let run = (x..self.width).take_while(|&x| self.is_dark(x, y)).count();
+                if run > 0 {
+                    // Writing to a `String` never fails
+                    write!(path, "M{x},{y}h{run}v1h-{run}z").unwrap();
+                }
+                x += run.max(1);
+            }
+        }
+
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+                r##"<rect width="100%" height="100%" fill="#fff"/><path fill="#000" d="{}"/></svg>"##,
+            ),
+            self.width * size,
+            self.height * size,
+            self.width,
+            self.height,
+            path,
+        )
+    }
+
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                text.push(match (self.is_dark(x, y), self.is_dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut crc = flate2::Crc::new();
+    crc.update(kind);
+    crc.update(data);
+
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc.sum().to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modules(bars: &[bool]) -> String {
+        bars.iter()
+            .map(|&dark| if dark { '1' } else { '0' })
+            .collect()
+    }
+
+    /// Split Code 128 bars back into their symbols
+    fn code128_symbols(bars: &[bool]) -> Vec<usize> {
+        let mut widths = Vec::new();
+        for run in bars.chunk_by(|a, b| a == b) {
+            widths.push(b'0' + run.len() as u8);
+        }
+
+        let (widths, stop) = widths.split_at(widths.len() - 7);
+        let mut symbols: Vec<_> = widths
+            .chunks(6)
+            .map(|symbol| CODE128.iter().position(|s| s.as_bytes() == symbol).unwrap())
+            .collect();
+        symbols.push(CODE128.iter().position(|s| s.as_bytes() == stop).unwrap());
+        symbols
+    }
+
+    #[test]
+    fn code128_patterns() {
+        assert_eq!(CODE128[0], "212222");
+        assert_eq!(CODE128[CODE128_START_B], "211214");
+        assert_eq!(CODE128[CODE128_START_C], "211232");
+        assert_eq!(CODE128[CODE128_STOP], "2331112");
+        // Every symbol is 11 modules wide, and the stop 13
+        assert!(CODE128[..CODE128_STOP].iter().all(|symbol| symbol
+            .bytes()
+            .map(|b| (b - b'0') as usize)
+            .sum::<usize>()
+            == 11));
+
+        let bars = code128("PJJ123C").unwrap();
+        assert_eq!(bars.len(), 9 * 11 + 13);
+        assert_eq!(&modules(&bars)[..11], "11010010000");
+        assert_eq!(&modules(&bars)[bars.len() - 13..], "1100011101011");
+    }
+
+    #[test]
+    fn code128_checksums_and_code_sets() {
+        let vectors: [(&str, &[usize]); 6] = [
+            // The start, data, checksum and stop symbols
+            ("PJJ123C", &[104, 48, 42, 42, 17, 18, 19, 35, 55, 106]),
+            ("1234", &[105, 12, 34, 82, 106]),
+            ("12", &[105, 12, 14, 106]),
+            ("A123456", &[104, 33, 99, 12, 34, 56, 66, 106]),
+            ("12345", &[105, 12, 34, 100, 21, 54, 106]),
+            ("X12345", &[104, 56, 17, 99, 23, 45, 87, 106]),
+        ];
+        for (data, symbols) in vectors {
+            assert_eq!(code128_symbols(&code128(data).unwrap()), symbols, "{data}");
+        }
+    }
+
+    #[test]
+    fn invalid_code128() {
+        assert!(matches!(code128(""), Err(BarcodeError::EmptyData)));
+        assert!(matches!(
+            code128("caf\u{e9}"),
+            Err(BarcodeError::InvalidCode128Char('\u{e9}'))
+        ));
+        assert!(matches!(
+            code128("tab\t"),
+            Err(BarcodeError::InvalidCode128Char('\t'))
+        ));
+    }
+
+    #[test]
+    fn ean13_modules() {
+        let vectors = [
+            (
+                "4006381333931",
+                "10100011010100111010111101111010001001011001101010100001010000101000010111010010000101100110101",
+            ),
+            (
+                "5901234123457",
+                "10100010110100111011001100100110111101001110101010110011011011001000010101110010011101000100101",
+            ),
+            (
+                "0000000000000",
+                "10100011010001101000110100011010001101000110101010111001011100101110010111001011100101110010101",
+            ),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(modules(&ean13(data).unwrap()), expected);
+            // The check digit is computed when left out
+            assert_eq!(modules(&ean13(&data[..12]).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn invalid_ean13() {
+        assert!(matches!(
+            ean13("4006381333932"),
+            Err(BarcodeError::InvalidEan13CheckDigit(1))
+        ));
+        for data in ["", "40063813339", "40063813339311", "40063813339a"] {
+            assert!(matches!(ean13(data), Err(BarcodeError::InvalidEan13)));
+        }
+    }
+}
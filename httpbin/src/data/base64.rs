@@ -1,11 +1,14 @@
+use std::io;
+
 use base64::alphabet;
 use base64::engine::general_purpose::{self, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
-use base64::engine::GeneralPurpose;
+use base64::engine::{DecodePaddingMode, GeneralPurpose};
 use base64::Engine;
-use serde::Deserialize;
+use futures_util::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Base64Engine {
     Standard,
@@ -17,11 +20,87 @@ pub enum Base64Engine {
     Crypt,
     ImapMutf7,
     Custom,
+    /// Detect standard or url-safe, padded or unpadded when decoding
+    Auto,
 }
 
 pub struct Base64Config {
     pub alphabet: String,
     pub pad: bool,
+    /// How padding is treated when decoding, derived from `pad` if not set
+    pub padding: Option<Base64Padding>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Base64Padding {
+    /// Accept both padded and unpadded input
+    Indifferent,
+    /// Require canonical padding
+    Required,
+    /// Reject any padding
+    Forbidden,
+}
+
+impl From<Base64Padding> for DecodePaddingMode {
+    fn from(padding: Base64Padding) -> Self {
+        match padding {
+            Base64Padding::Indifferent => DecodePaddingMode::Indifferent,
+            Base64Padding::Required => DecodePaddingMode::RequireCanonical,
+            Base64Padding::Forbidden => DecodePaddingMode::RequireNone,
+        }
+    }
+}
+
+/// A supported engine with its alphabet and whether it pads when encoding
+#[derive(Serialize, Debug)]
+pub struct Base64EngineInfo {
+    pub engine: Base64Engine,
+    pub alphabet: &'static str,
+    pub pad: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Base64WrapMode {
+    Mime,
+    Pem,
+}
+
+/// How to wrap encoded base64 into lines
+#[derive(Debug, Clone)]
+pub enum Base64Wrap {
+    /// 76-column lines separated by CRLF as specified by RFC 2045
+    Mime,
+    /// 64-column lines separated by LF and armoured with
+    /// `-----BEGIN {label}-----` as specified by RFC 7468
+    Pem { label: String },
+}
+
+impl Base64Wrap {
+    /// Create a wrap from a mode and an optional PEM label, which defaults to `DATA`
+    pub fn new(mode: Base64WrapMode, label: Option<String>) -> Self {
+        match mode {
+            Base64WrapMode::Mime => Self::Mime,
+            Base64WrapMode::Pem => Self::Pem {
+                label: label.unwrap_or_else(|| "DATA".to_string()),
+            },
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Self::Mime => 76,
+            Self::Pem { .. } => 64,
+        }
+    }
+
+    fn line_ending(&self) -> &'static str {
+        match self {
+            Self::Mime => "\r\n",
+            Self::Pem { .. } => "\n",
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -30,6 +109,8 @@ pub enum Base64Error {
     UnknownEngine(String),
     #[error("the config is empty")]
     EmptyConfig,
+    #[error("the auto engine can only be used to decode a whole string")]
+    AutoEngine,
     #[error("the alphabet is invalid: {0}")]
     InvalidAlphabet(#[from] alphabet::ParseAlphabetError),
     #[error("the base64 string is invalid: {0}")]
@@ -42,80 +123,386 @@ pub fn encode(
     engine: Base64Engine,
     config: Option<Base64Config>,
 ) -> Result<String, Base64Error> {
-    let encoded = match engine {
-        Base64Engine::Standard => STANDARD.encode(data),
-        Base64Engine::StandardNoPad => STANDARD_NO_PAD.encode(data),
-        Base64Engine::UrlSafe => URL_SAFE.encode(data),
-        Base64Engine::UrlSafeNoPad => URL_SAFE_NO_PAD.encode(data),
-        Base64Engine::Bcrypt => BCRYPT.encode(data),
-        Base64Engine::BinHex => BIN_HEX.encode(data),
-        Base64Engine::Crypt => CRYPT.encode(data),
-        Base64Engine::ImapMutf7 => IMAP_MUTF7.encode(data),
-        Base64Engine::Custom => {
-            if config.is_none() {
-                return Err(Base64Error::EmptyConfig);
-            }
-
-            let config = config.unwrap();
-
-            let alphabet = alphabet::Alphabet::new(&config.alphabet)?;
+    Ok(general_purpose(engine, config)?.encode(data))
+}
 
-            let engine = GeneralPurpose::new(
-                &alphabet,
-                if config.pad {
-                    general_purpose::PAD
-                } else {
-                    general_purpose::NO_PAD
-                },
-            );
+/// Encode a `&[u8]` to a base64 string wrapped into lines
+pub fn encode_wrapped(
+    data: &[u8],
+    engine: Base64Engine,
+    config: Option<Base64Config>,
+    wrap: Base64Wrap,
+) -> Result<String, Base64Error> {
+    let mut encoder = Base64Encoder::new(engine, config, Some(wrap))?;
 
-            engine.encode(data)
-        }
-    };
+    let mut encoded = encoder.update(data);
+    encoded.push_str(&encoder.finish());
 
     Ok(encoded)
 }
 
 /// Decode a base64 string to a `Vec<u8>`
+///
+/// Whitespace and PEM armour lines are ignored
 pub fn decode(
     data: &str,
     engine: Base64Engine,
     config: Option<Base64Config>,
 ) -> Result<Vec<u8>, Base64Error> {
-    let decoded = match engine {
-        Base64Engine::Standard => STANDARD.decode(data)?,
-        Base64Engine::StandardNoPad => STANDARD_NO_PAD.decode(data)?,
-        Base64Engine::UrlSafe => URL_SAFE.decode(data)?,
-        Base64Engine::UrlSafeNoPad => URL_SAFE_NO_PAD.decode(data)?,
-        Base64Engine::Bcrypt => BCRYPT.decode(data)?,
-        Base64Engine::BinHex => BIN_HEX.decode(data)?,
-        Base64Engine::Crypt => CRYPT.decode(data)?,
-        Base64Engine::ImapMutf7 => IMAP_MUTF7.decode(data)?,
-        Base64Engine::Custom => {
-            if config.is_none() {
-                return Err(Base64Error::EmptyConfig);
+    let data: String = data
+        .lines()
+        .filter(|line| !is_armour(line.trim().as_bytes()))
+        .flat_map(|line| line.chars().filter(|c| !c.is_ascii_whitespace()))
+        .collect();
+
+    let engine = match engine {
+        Base64Engine::Auto => lenient(detect(&data)),
+        engine => general_purpose(engine, config)?,
+    };
+
+    Ok(engine.decode(data)?)
+}
+
+/// Detect whether a base64 string uses the standard or url-safe alphabet and
+/// whether it is padded
+///
+/// Strings without any of `+/-_` are reported as standard
+pub fn detect(data: &str) -> Base64Engine {
+    let url_safe = data.contains(['-', '_']);
+    let padded = data.trim_end().ends_with('=');
+
+    match (url_safe, padded) {
+        (false, true) => Base64Engine::Standard,
+        (false, false) => Base64Engine::StandardNoPad,
+        (true, true) => Base64Engine::UrlSafe,
+        (true, false) => Base64Engine::UrlSafeNoPad,
+    }
+}
+
+/// List the engines with a fixed alphabet
+pub fn engines() -> Vec<Base64EngineInfo> {
+    [
+        (Base64Engine::Standard, STANDARD_ALPHABET, true),
+        (Base64Engine::StandardNoPad, STANDARD_ALPHABET, false),
+        (Base64Engine::UrlSafe, URL_SAFE_ALPHABET, true),
+        (Base64Engine::UrlSafeNoPad, URL_SAFE_ALPHABET, false),
+        (Base64Engine::Bcrypt, BCRYPT_ALPHABET, false),
+        (Base64Engine::BinHex, BIN_HEX_ALPHABET, false),
+        (Base64Engine::Crypt, CRYPT_ALPHABET, false),
+        (Base64Engine::ImapMutf7, IMAP_MUTF7_ALPHABET, false),
+    ]
+    .into_iter()
+    .map(|(engine, alphabet, pad)| Base64EngineInfo {
+        engine,
+        alphabet,
+        pad,
+    })
+    .collect()
+}
+
+/// Encode a stream of bytes to a stream of base64 chunks
+pub fn encode_stream<S, B, E>(
+    stream: S,
+    engine: Base64Engine,
+    config: Option<Base64Config>,
+    wrap: Option<Base64Wrap>,
+) -> Result<impl Stream<Item = Result<Vec<u8>, io::Error>>, Base64Error>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let encoder = Base64Encoder::new(engine, config, wrap)?;
+
+    Ok(stream::unfold(
+        Some((Box::pin(stream), encoder)),
+        |state| async move {
+            let (mut stream, mut encoder) = state?;
+
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    let encoded = encoder.update(chunk.as_ref());
+                    Some((Ok(encoded.into_bytes()), Some((stream, encoder))))
+                }
+                Some(Err(err)) => Some((Err(io::Error::other(err)), None)),
+                None => Some((Ok(encoder.finish().into_bytes()), None)),
             }
+        },
+    ))
+}
 
-            let config = config.unwrap();
+/// Decode a stream of base64 chunks to a stream of bytes
+///
+/// Whitespace and PEM armour lines are ignored
+pub fn decode_stream<S, B, E>(
+    stream: S,
+    engine: Base64Engine,
+    config: Option<Base64Config>,
+) -> Result<impl Stream<Item = Result<Vec<u8>, io::Error>>, Base64Error>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let decoder = Base64Decoder::new(engine, config)?;
 
-            let alphabet = alphabet::Alphabet::new(&config.alphabet)?;
+    Ok(stream::unfold(
+        Some((Box::pin(stream), decoder)),
+        |state| async move {
+            let (mut stream, mut decoder) = state?;
 
-            let engine = GeneralPurpose::new(
-                &alphabet,
-                if config.pad {
-                    general_purpose::PAD
-                } else {
-                    general_purpose::NO_PAD
+            match stream.next().await {
+                Some(Ok(chunk)) => match decoder.update(chunk.as_ref()) {
+                    Ok(decoded) => Some((Ok(decoded), Some((stream, decoder)))),
+                    Err(err) => Some((Err(io::Error::new(io::ErrorKind::InvalidData, err)), None)),
                 },
-            );
+                Some(Err(err)) => Some((Err(io::Error::other(err)), None)),
+                None => Some((
+                    decoder
+                        .finish()
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+                    None,
+                )),
+            }
+        },
+    ))
+}
+
+/// An incremental base64 encoder which optionally wraps its output into lines
+pub struct Base64Encoder {
+    engine: GeneralPurpose,
+    wrap: Option<Base64Wrap>,
+    pending: Vec<u8>,
+    column: usize,
+    started: bool,
+}
 
-            engine.decode(data)?
+impl Base64Encoder {
+    pub fn new(
+        engine: Base64Engine,
+        config: Option<Base64Config>,
+        wrap: Option<Base64Wrap>,
+    ) -> Result<Self, Base64Error> {
+        Ok(Self {
+            engine: general_purpose(engine, config)?,
+            wrap,
+            pending: Vec::with_capacity(3),
+            column: 0,
+            started: false,
+        })
+    }
+
+    /// Feed a chunk of data and return the base64 encoded so far
+    pub fn update(&mut self, data: &[u8]) -> String {
+        self.pending.extend_from_slice(data);
+
+        // Only whole 3-byte groups can be encoded without padding
+        let len = self.pending.len() / 3 * 3;
+        let encoded = self.engine.encode(&self.pending[..len]);
+        self.pending.drain(..len);
+
+        self.wrap_lines(&encoded)
+    }
+
+    /// Encode the remaining data and close the wrapping
+    pub fn finish(mut self) -> String {
+        let pending = std::mem::take(&mut self.pending);
+        let encoded = self.engine.encode(pending);
+        let mut encoded = self.wrap_lines(&encoded);
+
+        if let Some(wrap) = &self.wrap {
+            if self.column > 0 {
+                encoded.push_str(wrap.line_ending());
+            }
+            if let Base64Wrap::Pem { label } = wrap {
+                encoded.push_str(&format!("-----END {label}-----\n"));
+            }
+        }
+
+        encoded
+    }
+
+    fn wrap_lines(&mut self, encoded: &str) -> String {
+        let Some(wrap) = &self.wrap else {
+            return encoded.to_string();
+        };
+
+        let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / 32 + 32);
+
+        if !self.started {
+            self.started = true;
+            if let Base64Wrap::Pem { label } = wrap {
+                wrapped.push_str(&format!("-----BEGIN {label}-----\n"));
+            }
+        }
+
+        let mut rest = encoded;
+        while !rest.is_empty() {
+            if self.column == wrap.width() {
+                wrapped.push_str(wrap.line_ending());
+                self.column = 0;
+            }
+
+            let (line, tail) = rest.split_at((wrap.width() - self.column).min(rest.len()));
+            wrapped.push_str(line);
+            self.column += line.len();
+            rest = tail;
+        }
+
+        wrapped
+    }
+}
+
+/// An incremental base64 decoder which ignores whitespace and PEM armour lines
+pub struct Base64Decoder {
+    engine: GeneralPurpose,
+    pending: Vec<u8>,
+    line: Vec<u8>,
+    line_state: LineState,
+}
+
+#[derive(PartialEq)]
+enum LineState {
+    /// The line so far may still be an armour line
+    Start,
+    /// The line is an armour line and skipped until its end
+    Armour,
+    /// The line is base64
+    Data,
+}
+
+impl Base64Decoder {
+    pub fn new(engine: Base64Engine, config: Option<Base64Config>) -> Result<Self, Base64Error> {
+        Ok(Self {
+            engine: general_purpose(engine, config)?,
+            pending: Vec::with_capacity(4),
+            line: Vec::new(),
+            line_state: LineState::Start,
+        })
+    }
+
+    /// Feed a chunk of base64 and return the data decoded so far
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Base64Error> {
+        for &byte in data {
+            if byte == b'\n' {
+                if self.line_state == LineState::Start {
+                    self.flush_line();
+                }
+                self.line.clear();
+                self.line_state = LineState::Start;
+                continue;
+            }
+
+            match self.line_state {
+                LineState::Armour => {}
+                LineState::Data => self.push(byte),
+                LineState::Start => {
+                    self.line.push(byte);
+                    if is_armour(&self.line) {
+                        self.line_state = LineState::Armour;
+                    } else if !is_armour_prefix(&self.line) {
+                        self.flush_line();
+                        self.line_state = LineState::Data;
+                    }
+                }
+            }
         }
+
+        // Only whole 4-character groups can be decoded before the end
+        let len = self.pending.len() / 4 * 4;
+        let decoded = self.engine.decode(&self.pending[..len])?;
+        self.pending.drain(..len);
+
+        Ok(decoded)
+    }
+
+    /// Decode the remaining base64
+    pub fn finish(mut self) -> Result<Vec<u8>, Base64Error> {
+        if self.line_state == LineState::Start {
+            self.flush_line();
+        }
+
+        Ok(self.engine.decode(&self.pending)?)
+    }
+
+    fn flush_line(&mut self) {
+        for byte in std::mem::take(&mut self.line) {
+            self.push(byte);
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        if !byte.is_ascii_whitespace() {
+            self.pending.push(byte);
+        }
+    }
+}
+
+fn is_armour(line: &[u8]) -> bool {
+    line.starts_with(b"-----BEGIN ") || line.starts_with(b"-----END ")
+}
+
+fn is_armour_prefix(line: &[u8]) -> bool {
+    b"-----BEGIN ".starts_with(line) || b"-----END ".starts_with(line)
+}
+
+fn general_purpose(
+    engine: Base64Engine,
+    config: Option<Base64Config>,
+) -> Result<GeneralPurpose, Base64Error> {
+    let engine = match engine {
+        Base64Engine::Standard => STANDARD,
+        Base64Engine::StandardNoPad => STANDARD_NO_PAD,
+        Base64Engine::UrlSafe => URL_SAFE,
+        Base64Engine::UrlSafeNoPad => URL_SAFE_NO_PAD,
+        Base64Engine::Bcrypt => BCRYPT,
+        Base64Engine::BinHex => BIN_HEX,
+        Base64Engine::Crypt => CRYPT,
+        Base64Engine::ImapMutf7 => IMAP_MUTF7,
+        Base64Engine::Custom => {
+            let config = config.ok_or(Base64Error::EmptyConfig)?;
+
+            let alphabet = alphabet::Alphabet::new(&config.alphabet)?;
+
+            let mut engine_config = if config.pad {
+                general_purpose::PAD
+            } else {
+                general_purpose::NO_PAD
+            };
+            if let Some(padding) = config.padding {
+                engine_config = engine_config.with_decode_padding_mode(padding.into());
+            }
+
+            GeneralPurpose::new(&alphabet, engine_config)
+        }
+        Base64Engine::Auto => return Err(Base64Error::AutoEngine),
     };
 
-    Ok(decoded)
+    Ok(engine)
 }
 
+/// A decoding engine for a detected standard or url-safe engine which accepts
+/// padded and unpadded input
+fn lenient(engine: Base64Engine) -> GeneralPurpose {
+    let alphabet = match engine {
+        Base64Engine::UrlSafe | Base64Engine::UrlSafeNoPad => alphabet::URL_SAFE,
+        _ => alphabet::STANDARD,
+    };
+
+    GeneralPurpose::new(
+        &alphabet,
+        general_purpose::PAD.with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    )
+}
+
+const STANDARD_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const BCRYPT_ALPHABET: &str = "./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const BIN_HEX_ALPHABET: &str = "!\"#$%&'()*+,-0123456789@ABCDEFGHIJKLMNPQRSTUVXYZ[`abcdehijklmpqr";
+const CRYPT_ALPHABET: &str = "./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const IMAP_MUTF7_ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+,";
+
 const BCRYPT: GeneralPurpose = GeneralPurpose::new(&alphabet::BCRYPT, general_purpose::NO_PAD);
 const BIN_HEX: GeneralPurpose = GeneralPurpose::new(&alphabet::BIN_HEX, general_purpose::NO_PAD);
 const CRYPT: GeneralPurpose = GeneralPurpose::new(&alphabet::CRYPT, general_purpose::NO_PAD);
@@ -0,0 +1,200 @@
+use percent_encoding::{AsciiSet, CONTROLS, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use thiserror::Error;
+
+mod base85;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    Hex,
+    HexUpper,
+    Base32,
+    Base32NoPad,
+    Base32Crockford,
+    Base58,
+    Base58Flickr,
+    Base85,
+    Z85,
+    Ascii85,
+    Percent,
+    QuotedPrintable,
+}
+
+/// The set of bytes to escape when percent-encoding, as defined by the
+/// [URL Standard](https://url.spec.whatwg.org/#percent-encoded-bytes)
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PercentSet {
+    Controls,
+    Fragment,
+    Query,
+    Path,
+    Userinfo,
+    #[default]
+    Component,
+    NonAlphanumeric,
+}
+
+#[derive(Debug, Default)]
+pub struct CodecConfig {
+    pub percent_set: PercentSet,
+}
+
+#[derive(Error, Debug)]
+pub enum CodecError {
+    #[error("the hex string is invalid: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+    #[error("the base32 string is invalid")]
+    InvalidBase32,
+    #[error("the base58 string is invalid: {0}")]
+    InvalidBase58(#[from] bs58::decode::Error),
+    #[error("the base85 string contains an invalid character: {0:?}")]
+    InvalidBase85Char(char),
+    #[error("the base85 string contains a group that overflows 32 bits")]
+    InvalidBase85Group,
+    #[error("the length must be a multiple of {0}")]
+    InvalidLength(usize),
+    #[error("the quoted-printable string is invalid: {0}")]
+    InvalidQuotedPrintable(#[from] quoted_printable::QuotedPrintableError),
+}
+
+const FRAGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
+const QUERY: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'#').add(b'<').add(b'>');
+const PATH: &AsciiSet = &QUERY.add(b'?').add(b'`').add(b'{').add(b'}');
+const USERINFO: &AsciiSet = &PATH
+    .add(b'/')
+    .add(b':')
+    .add(b';')
+    .add(b'=')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'|');
+const COMPONENT: &AsciiSet = &USERINFO.add(b'$').add(b'%').add(b'&').add(b'+').add(b',');
+
+impl PercentSet {
+    fn ascii_set(&self) -> &'static AsciiSet {
+        match self {
+            PercentSet::Controls => CONTROLS,
+            PercentSet::Fragment => FRAGMENT,
+            PercentSet::Query => QUERY,
+            PercentSet::Path => PATH,
+            PercentSet::Userinfo => USERINFO,
+            PercentSet::Component => COMPONENT,
+            PercentSet::NonAlphanumeric => NON_ALPHANUMERIC,
+        }
+    }
+}
+
+/// Encode a `&[u8]` to a string with the given codec
+pub fn encode(
+    data: &[u8],
+    codec: Codec,
+    config: Option<CodecConfig>,
+) -> Result<String, CodecError> {
+    let config = config.unwrap_or_default();
+
+    let encoded = match codec {
+        Codec::Hex => hex::encode(data),
+        Codec::HexUpper => hex::encode_upper(data),
+        Codec::Base32 => base32::encode(base32::Alphabet::RFC4648 { padding: true }, data),
+        Codec::Base32NoPad => base32::encode(base32::Alphabet::RFC4648 { padding: false }, data),
+        Codec::Base32Crockford => base32::encode(base32::Alphabet::Crockford, data),
+        Codec::Base58 => bs58::encode(data).into_string(),
+        Codec::Base58Flickr => bs58::encode(data)
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_string(),
+        Codec::Base85 => base85::encode(data, base85::RFC1924, None),
+        Codec::Z85 => {
+            if !data.len().is_multiple_of(4) {
+                return Err(CodecError::InvalidLength(4));
+            }
+
+            base85::encode(data, base85::Z85, None)
+        }
+        Codec::Ascii85 => base85::encode(data, base85::ASCII85, Some(b'z')),
+        Codec::Percent => {
+            percent_encoding::percent_encode(data, config.percent_set.ascii_set()).to_string()
+        }
+        Codec::QuotedPrintable => quoted_printable::encode_to_str(data),
+    };
+
+    Ok(encoded)
+}
+
+/// Decode a string to a `Vec<u8>` with the given codec
+pub fn decode(data: &str, codec: Codec) -> Result<Vec<u8>, CodecError> {
+    let decoded = match codec {
+        Codec::Hex | Codec::HexUpper => hex::decode(data)?,
+        Codec::Base32 => base32::decode(base32::Alphabet::RFC4648 { padding: true }, data)
+            .ok_or(CodecError::InvalidBase32)?,
+        Codec::Base32NoPad => base32::decode(base32::Alphabet::RFC4648 { padding: false }, data)
+            .ok_or(CodecError::InvalidBase32)?,
+        Codec::Base32Crockford => {
+            base32::decode(base32::Alphabet::Crockford, data).ok_or(CodecError::InvalidBase32)?
+        }
+        Codec::Base58 => bs58::decode(data).into_vec()?,
+        Codec::Base58Flickr => bs58::decode(data)
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_vec()?,
+        Codec::Base85 => base85::decode(data, base85::RFC1924, None)?,
+        Codec::Z85 => {
+            // Whitespace is ignored by the decoder, so it does not count
+            let len = data.bytes().filter(|c| !c.is_ascii_whitespace()).count();
+            if !len.is_multiple_of(5) {
+                return Err(CodecError::InvalidLength(5));
+            }
+
+            base85::decode(data, base85::Z85, None)?
+        }
+        Codec::Ascii85 => {
+            let data = data.trim();
+            let data = data.strip_prefix("<~").unwrap_or(data);
+            let data = data.strip_suffix("~>").unwrap_or(data);
+
+            base85::decode(data, base85::ASCII85, Some(b'z'))?
+        }
+        Codec::Percent => percent_encoding::percent_decode_str(data).collect(),
+        Codec::QuotedPrintable => {
+            quoted_printable::decode(data, quoted_printable::ParseMode::Strict)?
+        }
+    };
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The test vector of the Z85 spec, https://rfc.zeromq.org/spec/32/
+    const HELLO_WORLD: [u8; 8] = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+
+    #[test]
+    fn z85() {
+        assert_eq!(
+            encode(&HELLO_WORLD, Codec::Z85, None).unwrap(),
+            "HelloWorld"
+        );
+        assert_eq!(decode("HelloWorld", Codec::Z85).unwrap(), HELLO_WORLD);
+        // Wrapped lines
+        assert_eq!(decode("Hello\nWorld\n", Codec::Z85).unwrap(), HELLO_WORLD);
+
+        assert!(matches!(
+            encode(&HELLO_WORLD[..7], Codec::Z85, None),
+            Err(CodecError::InvalidLength(4))
+        ));
+        assert!(matches!(
+            decode("Hello Worl", Codec::Z85),
+            Err(CodecError::InvalidLength(5))
+        ));
+    }
+
+    #[test]
+    fn ascii85_armour() {
+        assert_eq!(decode(" <~BOu!rDZ~>\n", Codec::Ascii85).unwrap(), b"hello");
+    }
+}
@@ -0,0 +1,166 @@
+//! Base85 variants share the same 4-byte to 5-character group encoding and
+//! only differ in their alphabets and the `z` shortcut of Ascii85
+
+use super::CodecError;
+
+pub(super) const RFC1924: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+pub(super) const Z85: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+pub(super) const ASCII85: &[u8; 85] =
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+
+/// Encode a `&[u8]` to a base85 string
+///
+/// A trailing partial group of `n` bytes is encoded to `n + 1` characters.
+/// If `zero_group` is set, a full group of zeros is encoded to that character.
+pub(super) fn encode(data: &[u8], alphabet: &[u8; 85], zero_group: Option<u8>) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(4) * 5);
+
+    for chunk in data.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+
+        if let (4, 0, Some(zero)) = (chunk.len(), value, zero_group) {
+            encoded.push(zero as char);
+            continue;
+        }
+
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = alphabet[(value % 85) as usize];
+            value /= 85;
+        }
+
+        encoded.extend(digits[..=chunk.len()].iter().map(|&d| d as char));
+    }
+
+    encoded
+}
+
+/// Decode a base85 string to a `Vec<u8>`
+///
+/// Whitespace is ignored.
+pub(super) fn decode(
+    data: &str,
+    alphabet: &[u8; 85],
+    zero_group: Option<u8>,
+) -> Result<Vec<u8>, CodecError> {
+    let mut table = [None; 256];
+    for (i, &c) in alphabet.iter().enumerate() {
+        table[c as usize] = Some(i as u8);
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() / 5 * 4 + 4);
+    let mut group = [0u8; 5];
+    let mut len = 0;
+
+    for c in data.bytes() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+
+        if len == 0 && Some(c) == zero_group {
+            decoded.extend([0; 4]);
+            continue;
+        }
+
+        group[len] = table[c as usize].ok_or(CodecError::InvalidBase85Char(c as char))?;
+        len += 1;
+
+        if len == 5 {
+            decoded.extend(decode_group(&group)?);
+            len = 0;
+        }
+    }
+
+    match len {
+        0 => {}
+        1 => return Err(CodecError::InvalidLength(5)),
+        _ => {
+            group[len..].fill(84);
+            decoded.extend(&decode_group(&group)?[..len - 1]);
+        }
+    }
+
+    Ok(decoded)
+}
+
+fn decode_group(group: &[u8; 5]) -> Result<[u8; 4], CodecError> {
+    group
+        .iter()
+        .try_fold(0u32, |value, &digit| {
+            value.checked_mul(85)?.checked_add(digit as u32)
+        })
+        .map(u32::to_be_bytes)
+        .ok_or(CodecError::InvalidBase85Group)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vectors of Python's `base64.b85encode` and `base64.a85encode`, which encode partial
+    /// groups the same way
+    const VECTORS: [(&[u8], &str, &str); 6] = [
+        (b"", "", ""),
+        (b"h", "Xa", "BE"),
+        (b"hello", "Xk~0{Zv", "BOu!rDZ"),
+        (b"\0\0\0\0", "00000", "z"),
+        (b"\xff\xff\xff\xff", "|NsC0", "s8W-!"),
+        (
+            b"Man is distinguished",
+            "O<`^zX>%ZCX>)XGZfA9Ab7*B`",
+            "9jqo^BlbD-BleB1DJ+*+F(f,q",
+        ),
+    ];
+
+    #[test]
+    fn rfc1924() {
+        for (data, encoded, _) in VECTORS {
+            assert_eq!(encode(data, RFC1924, None), encoded);
+            assert_eq!(decode(encoded, RFC1924, None).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn ascii85() {
+        for (data, _, encoded) in VECTORS {
+            assert_eq!(encode(data, ASCII85, Some(b'z')), encoded);
+            assert_eq!(decode(encoded, ASCII85, Some(b'z')).unwrap(), data);
+        }
+        assert_eq!(
+            decode("z@:E^", ASCII85, Some(b'z')).unwrap(),
+            b"\0\0\0\0abc"
+        );
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(decode("Xk~0{\r\n Zv", RFC1924, None).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn malformed() {
+        // A single character cannot hold a byte
+        assert!(matches!(
+            decode("Xk~0{Z", RFC1924, None),
+            Err(CodecError::InvalidLength(5))
+        ));
+        assert!(matches!(
+            decode("Xk\"0{", RFC1924, None),
+            Err(CodecError::InvalidBase85Char('"'))
+        ));
+        // Above 2^32 - 1
+        assert!(matches!(
+            decode("|NsC1", RFC1924, None),
+            Err(CodecError::InvalidBase85Group)
+        ));
+        // The zero shortcut only stands for a whole group
+        assert!(matches!(
+            decode("Xz", ASCII85, Some(b'z')),
+            Err(CodecError::InvalidBase85Char('z'))
+        ));
+    }
+}
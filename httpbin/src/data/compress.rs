@@ -0,0 +1,167 @@
+use std::io::{self, Read, Write};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    Gzip,
+    Zlib,
+    /// Raw deflate without a zlib or gzip header
+    Deflate,
+    Brotli,
+    Zstd,
+    /// The LZ4 frame format
+    Lz4,
+    /// The snappy framing format
+    Snappy,
+    /// A raw snappy block without framing
+    SnappyRaw,
+}
+
+impl Compression {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "application/gzip",
+            Compression::Zlib => "application/zlib",
+            Compression::Zstd => "application/zstd",
+            Compression::Snappy => "application/x-snappy-framed",
+            _ => "application/octet-stream",
+        }
+    }
+
+    /// The supported levels and the default level, or `None` if levels are not supported
+    fn levels(&self) -> Option<(i32, i32, i32)> {
+        match self {
+            Compression::Gzip | Compression::Zlib | Compression::Deflate => Some((0, 9, 6)),
+            Compression::Brotli => Some((0, 11, 11)),
+            Compression::Zstd => {
+                let range = zstd::compression_level_range();
+                Some((
+                    *range.start(),
+                    *range.end(),
+                    zstd::DEFAULT_COMPRESSION_LEVEL,
+                ))
+            }
+            Compression::Lz4 | Compression::Snappy | Compression::SnappyRaw => None,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CompressError {
+    #[error("the level must be between {0} and {1}")]
+    InvalidLevel(i32, i32),
+    #[error("the algorithm does not support levels")]
+    UnsupportedLevel,
+    #[error("the data is invalid: {0}")]
+    InvalidData(#[from] io::Error),
+    #[error("the decompressed data is larger than {0} bytes")]
+    TooLarge(usize),
+}
+
+/// Compress data, at `level` if given or the default level of the algorithm otherwise
+pub fn compress(
+    data: &[u8],
+    algo: Compression,
+    level: Option<i32>,
+) -> Result<Vec<u8>, CompressError> {
+    let level = match (algo.levels(), level) {
+        (Some((min, max, _)), Some(level)) if !(min..=max).contains(&level) => {
+            return Err(CompressError::InvalidLevel(min, max))
+        }
+        (Some((_, _, default)), level) => level.unwrap_or(default),
+        (None, Some(_)) => return Err(CompressError::UnsupportedLevel),
+        (None, None) => 0,
+    };
+
+    Ok(match algo {
+        Compression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(level as u32));
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Compression::Zlib => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::new(level as u32));
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Compression::Deflate => {
+            let mut encoder = flate2::write::DeflateEncoder::new(
+                Vec::new(),
+                flate2::Compression::new(level as u32),
+            );
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Compression::Brotli => {
+            // A 4 MiB window, which is the default of the brotli command line tool
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, level as u32, 22);
+            encoder.write_all(data)?;
+            encoder.into_inner()
+        }
+        Compression::Zstd => zstd::encode_all(data, level)?,
+        Compression::Lz4 => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+            encoder.write_all(data)?;
+            encoder.finish().map_err(io::Error::from)?
+        }
+        Compression::Snappy => {
+            let mut compressed = Vec::new();
+            // Write through a borrow, as `into_inner` wraps the flush error in the encoder
+            {
+                let mut encoder = snap::write::FrameEncoder::new(&mut compressed);
+                encoder.write_all(data)?;
+                encoder.flush()?;
+            }
+            compressed
+        }
+        Compression::SnappyRaw => snap::raw::Encoder::new()
+            .compress_vec(data)
+            .map_err(io::Error::from)?,
+    })
+}
+
+/// Decompress data, failing once the output grows beyond `max_size` bytes
+pub fn decompress(
+    data: &[u8],
+    algo: Compression,
+    max_size: usize,
+) -> Result<Vec<u8>, CompressError> {
+    match algo {
+        Compression::Gzip => read_limited(flate2::read::MultiGzDecoder::new(data), max_size),
+        Compression::Zlib => read_limited(flate2::read::ZlibDecoder::new(data), max_size),
+        Compression::Deflate => read_limited(flate2::read::DeflateDecoder::new(data), max_size),
+        Compression::Brotli => read_limited(brotli::Decompressor::new(data, 4096), max_size),
+        Compression::Zstd => read_limited(zstd::Decoder::new(data)?, max_size),
+        Compression::Lz4 => read_limited(lz4_flex::frame::FrameDecoder::new(data), max_size),
+        Compression::Snappy => read_limited(snap::read::FrameDecoder::new(data), max_size),
+        Compression::SnappyRaw => {
+            // A raw block starts with its decompressed length, so it is checked up front
+            let len = snap::raw::decompress_len(data).map_err(io::Error::from)?;
+            if len > max_size {
+                return Err(CompressError::TooLarge(max_size));
+            }
+
+            Ok(snap::raw::Decoder::new()
+                .decompress_vec(data)
+                .map_err(io::Error::from)?)
+        }
+    }
+}
+
+/// Read to the end without holding more than `max_size + 1` bytes, to stop compression bombs
+fn read_limited<R: Read>(reader: R, max_size: usize) -> Result<Vec<u8>, CompressError> {
+    let mut decompressed = Vec::new();
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_size {
+        return Err(CompressError::TooLarge(max_size));
+    }
+
+    Ok(decompressed)
+}
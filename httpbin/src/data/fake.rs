@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+mod records;
+mod schema;
+
+/// The kind of fake record
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum FakeKind {
+    /// A name with a username, email, phone number and birthday
+    Person,
+    /// A street, city, postcode and country
+    Address,
+    /// A name, industry, domain and email
+    Company,
+    /// A payment processor test number with a brand, expiry and CVC
+    CreditCard,
+    /// An IPv4 and an IPv6 address from the documentation ranges
+    Ip,
+    /// A browser `User-Agent`
+    UserAgent,
+    /// A paragraph of lorem ipsum
+    Lorem,
+}
+
+/// The locale of names, addresses and phone numbers
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum FakeLocale {
+    #[default]
+    En,
+    De,
+    Fr,
+}
+
+#[derive(Error, Debug)]
+pub enum FakeError {
+    #[error("the count must be between 1 and {0}")]
+    InvalidCount(usize),
+    #[error("the schema is invalid: {0}")]
+    InvalidSchema(String),
+    #[error("the pattern is invalid: {0}")]
+    InvalidPattern(#[from] Box<regex_syntax::Error>),
+    #[error("the reference cannot be resolved: {0}")]
+    UnresolvedRef(String),
+    #[error("the schema is nested too deeply")]
+    TooDeep,
+    #[error("the generated instances are too large")]
+    TooLarge,
+    #[error("no instance satisfies the schema: {0}")]
+    Unsatisfiable(&'static str),
+}
+
+/// Generate instances of a JSON Schema
+///
+/// A single instance is returned if `count` is not given, otherwise an array of `count` instances
+pub fn schema(
+    schema: &Value,
+    count: Option<usize>,
+    seed: Option<u64>,
+    max_count: usize,
+) -> Result<Value, FakeError> {
+    let mut generator = schema::Generator::new(schema, super::random::rng(seed));
+
+    match count {
+        None => generator.generate(),
+        Some(count) => {
+            check_count(count, max_count)?;
+
+            (0..count)
+                .map(|_| generator.generate())
+                .collect::<Result<_, _>>()
+                .map(Value::Array)
+        }
+    }
+}
+
+/// Generate fake records of a kind
+///
+/// A single record is returned if `count` is not given, otherwise an array of `count` records
+pub fn records(
+    kind: FakeKind,
+    locale: Option<FakeLocale>,
+    count: Option<usize>,
+    seed: Option<u64>,
+    max_count: usize,
+) -> Result<Value, FakeError> {
+    let mut faker = records::Faker::new(locale.unwrap_or_default(), super::random::rng(seed));
+
+    match count {
+        None => Ok(faker.record(kind)),
+        Some(count) => {
+            check_count(count, max_count)?;
+
+            Ok(Value::Array(
+                (0..count).map(|_| faker.record(kind)).collect(),
+            ))
+        }
+    }
+}
+
+fn check_count(count: usize, max_count: usize) -> Result<(), FakeError> {
+    if count == 0 || count > max_count {
+        return Err(FakeError::InvalidCount(max_count));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,603 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::NaiveDate;
+use rand::{seq::SliceRandom, Rng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{FakeKind, FakeLocale};
+
+/// A person
+#[derive(Serialize)]
+struct Person {
+    first_name: &'static str,
+    last_name: &'static str,
+    name: String,
+    username: String,
+    email: String,
+    phone: String,
+    /// The birthday in `YYYY-MM-DD`
+    birthday: String,
+}
+
+/// A postal address
+#[derive(Serialize)]
+struct Address {
+    street: String,
+    city: &'static str,
+    postcode: String,
+    country: &'static str,
+    /// The ISO 3166-1 alpha-2 code of the country
+    country_code: &'static str,
+}
+
+/// A company
+#[derive(Serialize)]
+struct Company {
+    name: String,
+    industry: &'static str,
+    domain: String,
+    email: String,
+}
+
+/// A credit card, always one of the test numbers published by payment processors
+#[derive(Serialize)]
+struct CreditCard {
+    brand: &'static str,
+    number: &'static str,
+    /// The expiry in `MM/YY`
+    expiry: String,
+    cvc: String,
+}
+
+/// IP addresses from the documentation ranges of RFC 5737 and RFC 3849
+#[derive(Serialize)]
+struct Ip {
+    ipv4: Ipv4Addr,
+    ipv6: Ipv6Addr,
+}
+
+/// The words and formats of a locale
+struct Locale {
+    first_names: &'static [&'static str],
+    last_names: &'static [&'static str],
+    cities: &'static [&'static str],
+    streets: &'static [&'static str],
+    company_suffixes: &'static [&'static str],
+    country: &'static str,
+    country_code: &'static str,
+    /// The phone format, where each `#` is replaced by a digit
+    phone: &'static str,
+    /// The email domains of people
+    domains: &'static [&'static str],
+}
+
+const EN: Locale = Locale {
+    first_names: &[
+        "James",
+        "Mary",
+        "Robert",
+        "Patricia",
+        "John",
+        "Jennifer",
+        "Michael",
+        "Linda",
+        "David",
+        "Elizabeth",
+        "William",
+        "Barbara",
+        "Richard",
+        "Susan",
+        "Joseph",
+        "Jessica",
+        "Thomas",
+        "Sarah",
+        "Charles",
+        "Karen",
+    ],
+    last_names: &[
+        "Smith",
+        "Johnson",
+        "Williams",
+        "Brown",
+        "Jones",
+        "Garcia",
+        "Miller",
+        "Davis",
+        "Rodriguez",
+        "Martinez",
+        "Hernandez",
+        "Lopez",
+        "Wilson",
+        "Anderson",
+        "Taylor",
+        "Thomas",
+        "Moore",
+        "Jackson",
+        "Martin",
+        "Lee",
+    ],
+    cities: &[
+        "Springfield",
+        "Riverside",
+        "Franklin",
+        "Greenville",
+        "Bristol",
+        "Clinton",
+        "Fairview",
+        "Salem",
+        "Madison",
+        "Georgetown",
+        "Arlington",
+        "Ashland",
+        "Oxford",
+        "Milton",
+        "Newport",
+    ],
+    streets: &[
+        "Main Street",
+        "Oak Avenue",
+        "Maple Drive",
+        "Cedar Lane",
+        "Pine Street",
+        "Elm Street",
+        "Washington Avenue",
+        "Lake Road",
+        "Hill Street",
+        "Park Avenue",
+        "Sunset Boulevard",
+        "River Road",
+        "Church Street",
+        "Mill Lane",
+        "Spring Street",
+    ],
+    company_suffixes: &["Inc.", "LLC", "Group", "Ltd.", "and Sons", "Corp."],
+    country: "United States",
+    country_code: "US",
+    phone: "+1 (###) 555-####",
+    domains: &["example.com", "example.net", "example.org"],
+};
+
+const DE: Locale = Locale {
+    first_names: &[
+        "Lukas",
+        "Anna",
+        "Leon",
+        "Lena",
+        "Paul",
+        "Marie",
+        "Jonas",
+        "Sophie",
+        "Felix",
+        "Laura",
+        "Maximilian",
+        "Julia",
+        "Tim",
+        "Hannah",
+        "Niklas",
+        "Lea",
+        "Jan",
+        "Katharina",
+        "Moritz",
+        "Jürgen",
+    ],
+    last_names: &[
+        "Müller",
+        "Schmidt",
+        "Schneider",
+        "Fischer",
+        "Weber",
+        "Meyer",
+        "Wagner",
+        "Becker",
+        "Schulz",
+        "Hoffmann",
+        "Schäfer",
+        "Koch",
+        "Bauer",
+        "Richter",
+        "Klein",
+        "Wolf",
+        "Schröder",
+        "Neumann",
+        "Schwarz",
+        "Zimmermann",
+    ],
+    cities: &[
+        "Berlin",
+        "Hamburg",
+        "München",
+        "Köln",
+        "Frankfurt am Main",
+        "Stuttgart",
+        "Düsseldorf",
+        "Leipzig",
+        "Dortmund",
+        "Essen",
+        "Bremen",
+        "Dresden",
+        "Hannover",
+        "Nürnberg",
+        "Freiburg",
+    ],
+    streets: &[
+        "Hauptstraße",
+        "Schulstraße",
+        "Gartenstraße",
+        "Bahnhofstraße",
+        "Dorfstraße",
+        "Bergstraße",
+        "Birkenweg",
+        "Lindenstraße",
+        "Kirchstraße",
+        "Waldstraße",
+        "Ringstraße",
+        "Schillerstraße",
+        "Goethestraße",
+        "Am Markt",
+        "Mühlenweg",
+    ],
+    company_suffixes: &["GmbH", "AG", "KG", "GmbH & Co. KG", "e.K."],
+    country: "Deutschland",
+    country_code: "DE",
+    phone: "+49 30 #######",
+    domains: &["example.de", "example.com", "example.org"],
+};
+
+const FR: Locale = Locale {
+    first_names: &[
+        "Louis", "Camille", "Gabriel", "Léa", "Arthur", "Manon", "Jules", "Chloé", "Hugo", "Inès",
+        "Raphaël", "Sarah", "Lucas", "Jade", "Nathan", "Louise", "Théo", "Emma", "Adam", "Zoé",
+    ],
+    last_names: &[
+        "Martin", "Bernard", "Dubois", "Thomas", "Robert", "Richard", "Petit", "Durand", "Leroy",
+        "Moreau", "Simon", "Laurent", "Lefèvre", "Michel", "Garcia", "David", "Bertrand", "Roux",
+        "Vincent", "Fournier",
+    ],
+    cities: &[
+        "Paris",
+        "Marseille",
+        "Lyon",
+        "Toulouse",
+        "Nice",
+        "Nantes",
+        "Strasbourg",
+        "Montpellier",
+        "Bordeaux",
+        "Lille",
+        "Rennes",
+        "Reims",
+        "Le Havre",
+        "Grenoble",
+        "Dijon",
+    ],
+    streets: &[
+        "rue de la Paix",
+        "rue Victor Hugo",
+        "avenue Jean Jaurès",
+        "rue Pasteur",
+        "boulevard Voltaire",
+        "rue de la République",
+        "place de la Mairie",
+        "rue du Moulin",
+        "chemin des Vignes",
+        "rue Nationale",
+        "allée des Tilleuls",
+        "rue de l'Église",
+        "avenue de la Gare",
+        "rue des Écoles",
+        "quai des Fleurs",
+    ],
+    company_suffixes: &["SA", "SARL", "SAS", "et Fils", "Groupe"],
+    country: "France",
+    country_code: "FR",
+    phone: "+33 1 ## ## ## ##",
+    domains: &["example.fr", "example.com", "example.org"],
+};
+
+const INDUSTRIES: &[&str] = &[
+    "Software",
+    "Manufacturing",
+    "Retail",
+    "Logistics",
+    "Healthcare",
+    "Finance",
+    "Energy",
+    "Education",
+    "Media",
+    "Agriculture",
+    "Construction",
+    "Hospitality",
+];
+
+/// Test numbers published by Stripe, Adyen and others, which never charge a real card
+const CREDIT_CARDS: &[(&str, &str)] = &[
+    ("Visa", "4242424242424242"),
+    ("Visa", "4000056655665556"),
+    ("Visa", "4111111111111111"),
+    ("Mastercard", "5555555555554444"),
+    ("Mastercard", "2223003122003222"),
+    ("Mastercard", "5200828282828210"),
+    ("American Express", "378282246310005"),
+    ("American Express", "371449635398431"),
+    ("Discover", "6011111111111117"),
+    ("Discover", "6011000990139424"),
+    ("Diners Club", "3056930009020004"),
+    ("JCB", "3566002020360505"),
+    ("UnionPay", "6200000000000005"),
+];
+
+const BROWSERS: &[&str] = &["chrome", "firefox", "safari", "edge"];
+
+const PLATFORMS: &[&str] = &[
+    "Windows NT 10.0; Win64; x64",
+    "Macintosh; Intel Mac OS X 10_15_7",
+    "X11; Linux x86_64",
+    "X11; Ubuntu; Linux x86_64",
+];
+
+const LOREM: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+];
+
+pub(super) struct Faker {
+    locale: &'static Locale,
+    rng: ChaCha8Rng,
+}
+
+impl Faker {
+    pub(super) fn new(locale: FakeLocale, rng: ChaCha8Rng) -> Self {
+        let locale = match locale {
+            FakeLocale::En => &EN,
+            FakeLocale::De => &DE,
+            FakeLocale::Fr => &FR,
+        };
+
+        Self { locale, rng }
+    }
+
+    pub(super) fn record(&mut self, kind: FakeKind) -> Value {
+        // Serializing these records never fails
+        match kind {
+            FakeKind::Person => serde_json::to_value(self.person()).unwrap(),
+            FakeKind::Address => serde_json::to_value(self.address()).unwrap(),
+            FakeKind::Company => serde_json::to_value(self.company()).unwrap(),
+            FakeKind::CreditCard => serde_json::to_value(self.credit_card()).unwrap(),
+            FakeKind::Ip => serde_json::to_value(self.ip()).unwrap(),
+            FakeKind::UserAgent => Value::String(self.user_agent()),
+            FakeKind::Lorem => Value::String(self.lorem()),
+        }
+    }
+
+    fn person(&mut self) -> Person {
+        let first_name = self.pick(self.locale.first_names);
+        let last_name = self.pick(self.locale.last_names);
+        let username = format!(
+            "{}.{}{}",
+            ascii(first_name),
+            ascii(last_name),
+            self.rng.gen_range(1..100)
+        );
+        let domain = self.pick(self.locale.domains);
+
+        // Days between 1950-01-01 and 2005-12-31
+        let birthday = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap()
+            + chrono::Days::new(self.rng.gen_range(0..20_454));
+
+        Person {
+            first_name,
+            last_name,
+            name: format!("{first_name} {last_name}"),
+            email: format!("{username}@{domain}"),
+            username,
+            phone: self.digits(self.locale.phone),
+            birthday: birthday.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    fn address(&mut self) -> Address {
+        let street = self.pick(self.locale.streets);
+        let number = self.rng.gen_range(1..200);
+
+        Address {
+            street: match self.locale.country_code {
+                "DE" => format!("{street} {number}"),
+                _ => format!("{number} {street}"),
+            },
+            city: self.pick(self.locale.cities),
+            postcode: self.digits("#####"),
+            country: self.locale.country,
+            country_code: self.locale.country_code,
+        }
+    }
+
+    fn company(&mut self) -> Company {
+        let last_name = self.pick(self.locale.last_names);
+        let suffix = self.pick(self.locale.company_suffixes);
+        // `.example` is reserved by RFC 2606
+        let domain = format!("{}.example", ascii(last_name));
+
+        Company {
+            name: format!("{last_name} {suffix}"),
+            industry: self.pick(INDUSTRIES),
+            email: format!("info@{domain}"),
+            domain,
+        }
+    }
+
+    fn credit_card(&mut self) -> CreditCard {
+        let (brand, number) = *CREDIT_CARDS.choose(&mut self.rng).unwrap();
+        // American Express uses a 4-digit security code
+        let cvc = match brand {
+            "American Express" => self.digits("####"),
+            _ => self.digits("###"),
+        };
+
+        CreditCard {
+            brand,
+            number,
+            // Expiring between 2030 and 2035 keeps the output independent of the current date
+            expiry: format!(
+                "{:02}/{}",
+                self.rng.gen_range(1..=12),
+                self.rng.gen_range(30..=35)
+            ),
+            cvc,
+        }
+    }
+
+    fn ip(&mut self) -> Ip {
+        let ipv4_networks = [[192, 0, 2], [198, 51, 100], [203, 0, 113]];
+        let [a, b, c] = *ipv4_networks.choose(&mut self.rng).unwrap();
+
+        Ip {
+            ipv4: Ipv4Addr::new(a, b, c, self.rng.gen_range(1..255)),
+            ipv6: Ipv6Addr::from((0x2001_0db8_u128 << 96) | self.rng.gen_range(1..1 << 96)),
+        }
+    }
+
+    fn user_agent(&mut self) -> String {
+        let platform = self.pick(PLATFORMS);
+
+        match self.pick(BROWSERS) {
+            "firefox" => {
+                let version = self.rng.gen_range(100..=130);
+                format!("Mozilla/5.0 ({platform}; rv:{version}.0) Gecko/20100101 Firefox/{version}.0")
+            }
+            "safari" => format!(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/{}.{} Safari/605.1.15",
+                self.rng.gen_range(15..=17),
+                self.rng.gen_range(0..=6)
+            ),
+            browser => {
+                let version = self.rng.gen_range(100..=130);
+                let edge = match browser {
+                    "edge" => format!(" Edg/{version}.0.0.0"),
+                    _ => String::new(),
+                };
+                format!(
+                    "Mozilla/5.0 ({platform}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{version}.0.0.0 Safari/537.36{edge}"
+                )
+            }
+        }
+    }
+
+    /// A paragraph of 3 to 6 sentences
+    fn lorem(&mut self) -> String {
+        let sentences = self.rng.gen_range(3..=6);
+
+        (0..sentences)
+            .map(|_| {
+                let words = self.rng.gen_range(6..=14);
+                let sentence = (0..words)
+                    .map(|_| self.pick(LOREM))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let mut chars = sentence.chars();
+                // A sentence always has at least one word
+                let first = chars.next().unwrap().to_ascii_uppercase();
+                format!("{first}{}.", chars.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn pick(&mut self, words: &'static [&'static str]) -> &'static str {
+        // The lists are never empty
+        words.choose(&mut self.rng).unwrap()
+    }
+
+    /// Replace each `#` in a format with a random digit
+    fn digits(&mut self, format: &str) -> String {
+        format
+            .chars()
+            .map(|c| match c {
+                '#' => char::from(b'0' + self.rng.gen_range(0..10)),
+                c => c,
+            })
+            .collect()
+    }
+}
+
+/// Lowercase a name and replace the non-ASCII letters of the locales for usernames and domains
+fn ascii(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'ä' => Some("ae".to_string()),
+            'ö' => Some("oe".to_string()),
+            'ü' => Some("ue".to_string()),
+            'ß' => Some("ss".to_string()),
+            'é' | 'è' | 'ê' | 'ë' => Some("e".to_string()),
+            'à' | 'â' => Some("a".to_string()),
+            'î' | 'ï' => Some("i".to_string()),
+            'ô' => Some("o".to_string()),
+            'ç' => Some("c".to_string()),
+            c if c.is_ascii_alphanumeric() => Some(c.to_string()),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,561 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::{seq::SliceRandom, Rng};
+use rand_chacha::ChaCha8Rng;
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind};
+use serde_json::{Map, Number, Value};
+
+use super::FakeError;
+use crate::data::random::MAX_LENGTH;
+
+/// The maximum depth of nested schemas and `$ref`s, which stops recursive schemas
+const MAX_DEPTH: usize = 32;
+
+/// The number of extra repetitions of unbounded `*`, `+` and `{n,}` in patterns
+const MAX_EXTRA_REPETITIONS: u32 = 8;
+
+/// The number of attempts to generate a distinct item for `uniqueItems`
+const MAX_UNIQUE_ATTEMPTS: usize = 16;
+
+/// The size of all instances of a generation, where each value counts one and each
+/// character of a string one more, which stops nested arrays from growing exponentially
+const MAX_SIZE: usize = 1 << 20;
+
+/// The largest multiplier tried to turn a fractional `multipleOf` into an integer step
+const MAX_STEP_MULTIPLIER: u32 = 1000;
+
+/// 2000-01-01T00:00:00Z and 2030-01-01T00:00:00Z, the range of generated dates
+const MIN_TIMESTAMP: i64 = 946_684_800;
+const MAX_TIMESTAMP: i64 = 1_893_456_000;
+
+/// Generates instances of a JSON Schema
+///
+/// The keywords `type`, `const`, `enum`, `$ref` (local only), `allOf`, `anyOf`,
+/// `oneOf`, `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`,
+/// `multipleOf`, `minLength`, `maxLength`, `pattern`, `format`, `items`,
+/// `prefixItems`, `minItems`, `maxItems`, `uniqueItems`, `properties`,
+/// `required` and `additionalProperties` are understood, others are ignored
+pub(super) struct Generator<'a> {
+    root: &'a Value,
+    rng: ChaCha8Rng,
+    /// The size generated so far, counted towards `MAX_SIZE`
+    size: usize,
+}
+
+impl<'a> Generator<'a> {
+    pub(super) fn new(root: &'a Value, rng: ChaCha8Rng) -> Self {
+        Self { root, rng, size: 0 }
+    }
+
+    pub(super) fn generate(&mut self) -> Result<Value, FakeError> {
+        self.value(self.root, 0)
+    }
+
+    fn value(&mut self, schema: &Value, depth: usize) -> Result<Value, FakeError> {
+        if depth > MAX_DEPTH {
+            return Err(FakeError::TooDeep);
+        }
+        self.grow(1)?;
+
+        let schema = match schema {
+            // `true` accepts any instance
+            Value::Bool(true) => return Ok(Value::Null),
+            Value::Bool(false) => return Err(FakeError::Unsatisfiable("the schema is `false`")),
+            Value::Object(schema) => schema,
+            _ => {
+                return Err(FakeError::InvalidSchema(
+                    "a schema must be an object or a boolean".to_string(),
+                ))
+            }
+        };
+
+        if let Some(reference) = schema.get("$ref") {
+            let target = self.resolve(reference)?;
+            return self.value(target, depth + 1);
+        }
+
+        if let Some(value) = schema.get("const") {
+            return Ok(value.clone());
+        }
+
+        if let Some(values) = schema.get("enum") {
+            return values
+                .as_array()
+                .ok_or_else(|| FakeError::InvalidSchema("`enum` must be an array".to_string()))?
+                .choose(&mut self.rng)
+                .cloned()
+                .ok_or(FakeError::Unsatisfiable("`enum` is empty"));
+        }
+
+        if let Some(schemas) = schema.get("allOf") {
+            let merged = self.merge(schema, schemas)?;
+            return self.value(&merged, depth + 1);
+        }
+
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(schemas) = schema.get(keyword) {
+                let branch = schemas
+                    .as_array()
+                    .ok_or_else(|| {
+                        FakeError::InvalidSchema(format!("`{keyword}` must be an array"))
+                    })?
+                    .choose(&mut self.rng)
+                    .ok_or(FakeError::Unsatisfiable("the list of subschemas is empty"))?;
+
+                return self.value(branch, depth + 1);
+            }
+        }
+
+        let ty = match schema.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            Some(Value::Array(types)) => types
+                .choose(&mut self.rng)
+                .and_then(Value::as_str)
+                .ok_or_else(|| {
+                    FakeError::InvalidSchema(
+                        "`type` must be a non-empty list of strings".to_string(),
+                    )
+                })?,
+            Some(_) => {
+                return Err(FakeError::InvalidSchema(
+                    "`type` must be a string or a list of strings".to_string(),
+                ))
+            }
+            None => infer_type(schema),
+        };
+
+        match ty {
+            "null" => Ok(Value::Null),
+            "boolean" => Ok(Value::Bool(self.rng.gen())),
+            "integer" => self.integer(schema),
+            "number" => self.number(schema),
+            "string" => {
+                let value = self.string(schema)?;
+                self.grow(value.len())?;
+                Ok(Value::String(value))
+            }
+            "array" => self.array(schema, depth),
+            "object" => self.object(schema, depth),
+            ty => Err(FakeError::InvalidSchema(format!("unknown type `{ty}`"))),
+        }
+    }
+
+    fn grow(&mut self, size: usize) -> Result<(), FakeError> {
+        self.size += size;
+        if self.size > MAX_SIZE {
+            return Err(FakeError::TooLarge);
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a local `$ref` like `#/$defs/name` against the root schema
+    fn resolve(&self, reference: &Value) -> Result<&'a Value, FakeError> {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| FakeError::InvalidSchema("`$ref` must be a string".to_string()))?;
+
+        reference
+            .strip_prefix('#')
+            .and_then(|pointer| self.root.pointer(pointer))
+            .ok_or_else(|| FakeError::UnresolvedRef(reference.to_string()))
+    }
+
+    /// Merge the subschemas of `allOf` into the schema, joining `properties` and `required`
+    fn merge(&self, schema: &Map<String, Value>, schemas: &Value) -> Result<Value, FakeError> {
+        let schemas = schemas
+            .as_array()
+            .ok_or_else(|| FakeError::InvalidSchema("`allOf` must be an array".to_string()))?;
+
+        let mut merged = schema.clone();
+        merged.remove("allOf");
+
+        for subschema in schemas {
+            let subschema = match subschema.get("$ref") {
+                Some(reference) => self.resolve(reference)?,
+                None => subschema,
+            };
+            let Some(subschema) = subschema.as_object() else {
+                continue;
+            };
+
+            for (key, value) in subschema {
+                match (key.as_str(), merged.get_mut(key), value) {
+                    ("properties", Some(Value::Object(properties)), Value::Object(value)) => {
+                        properties.extend(value.clone());
+                    }
+                    ("required", Some(Value::Array(required)), Value::Array(value)) => {
+                        required.extend(value.iter().cloned());
+                    }
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(Value::Object(merged))
+    }
+
+    fn integer(&mut self, schema: &Map<String, Value>) -> Result<Value, FakeError> {
+        let (min, max) = bounds(schema, 0.0, 1000.0);
+        let (mut min, mut max) = (min.ceil(), max.floor());
+        if exclusive(schema, "exclusiveMinimum") == Some(min) {
+            min += 1.0;
+        }
+        if exclusive(schema, "exclusiveMaximum") == Some(max) {
+            max -= 1.0;
+        }
+
+        let step = match schema.get("multipleOf") {
+            Some(step) => integer_step(step)?,
+            None => 1.0,
+        };
+        // `as` saturates to the range of `i64`
+        let (min, max) = ((min / step).ceil() as i64, (max / step).floor() as i64);
+        if min > max {
+            return Err(FakeError::Unsatisfiable("the integer range is empty"));
+        }
+
+        let value = self.rng.gen_range(min..=max).saturating_mul(step as i64);
+        Ok(Value::Number(value.into()))
+    }
+
+    fn number(&mut self, schema: &Map<String, Value>) -> Result<Value, FakeError> {
+        let (min, max) = bounds(schema, 0.0, 1000.0);
+        if !(min <= max && (max - min).is_finite()) {
+            return Err(FakeError::Unsatisfiable("the number range is empty"));
+        }
+
+        let exclusive_min = exclusive(schema, "exclusiveMinimum") == Some(min);
+        let exclusive_max = exclusive(schema, "exclusiveMaximum") == Some(max);
+
+        let value = match schema.get("multipleOf").and_then(Value::as_f64) {
+            Some(step) if step > 0.0 => {
+                let (mut low, mut high) = ((min / step).ceil() as i64, (max / step).floor() as i64);
+                if exclusive_min && low as f64 * step <= min {
+                    low += 1;
+                }
+                if exclusive_max && high as f64 * step >= max {
+                    high -= 1;
+                }
+                if low > high {
+                    return Err(FakeError::Unsatisfiable("the number range is empty"));
+                }
+
+                self.rng.gen_range(low..=high) as f64 * step
+            }
+            _ => {
+                if min == max && (exclusive_min || exclusive_max) {
+                    return Err(FakeError::Unsatisfiable("the number range is empty"));
+                }
+
+                // An exclusive bound is drawn with a negligible chance, so this rarely repeats
+                loop {
+                    let value = self.rng.gen_range(min..=max);
+                    if !(exclusive_min && value == min || exclusive_max && value == max) {
+                        break value;
+                    }
+                }
+            }
+        };
+
+        // The value is finite as the range is
+        Ok(Value::Number(Number::from_f64(value).unwrap()))
+    }
+
+    fn string(&mut self, schema: &Map<String, Value>) -> Result<String, FakeError> {
+        if let Some(format) = schema.get("format").and_then(Value::as_str) {
+            if let Some(value) = self.format(format) {
+                return Ok(value);
+            }
+        }
+
+        if let Some(pattern) = schema.get("pattern") {
+            let pattern = pattern.as_str().ok_or_else(|| {
+                FakeError::InvalidSchema("`pattern` must be a string".to_string())
+            })?;
+            let hir = regex_syntax::parse(pattern).map_err(Box::new)?;
+
+            let mut value = String::new();
+            self.pattern(&hir, &mut value)?;
+            return Ok(value);
+        }
+
+        let length = self.length(schema, "minLength", "maxLength", (1, 16))?;
+        Ok((0..length)
+            .map(|_| self.rng.gen_range(b'a'..=b'z') as char)
+            .collect())
+    }
+
+    /// Generate a string in a known format, or `None` if the format is unknown
+    fn format(&mut self, format: &str) -> Option<String> {
+        let value = match format {
+            "uuid" => uuid::Builder::from_random_bytes(self.rng.gen())
+                .into_uuid()
+                .to_string(),
+            "email" => format!("{}.{}@example.com", self.word(), self.word()),
+            "date-time" => self.datetime().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "date" => self.datetime().format("%Y-%m-%d").to_string(),
+            "time" => self.datetime().format("%H:%M:%SZ").to_string(),
+            "uri" | "url" => format!("https://example.com/{}", self.word()),
+            "hostname" => format!("{}.example.com", self.word()),
+            "ipv4" => Ipv4Addr::from(self.rng.gen::<u32>()).to_string(),
+            "ipv6" => Ipv6Addr::from(self.rng.gen::<u128>()).to_string(),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    fn word(&mut self) -> String {
+        let length = self.rng.gen_range(3..=8);
+        (0..length)
+            .map(|_| self.rng.gen_range(b'a'..=b'z') as char)
+            .collect()
+    }
+
+    fn datetime(&mut self) -> DateTime<Utc> {
+        let timestamp = self.rng.gen_range(MIN_TIMESTAMP..MAX_TIMESTAMP);
+        // The range is well within what chrono supports
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    /// Generate a string matched by a regular expression
+    fn pattern(&mut self, hir: &Hir, value: &mut String) -> Result<(), FakeError> {
+        if value.len() > MAX_LENGTH {
+            return Err(FakeError::Unsatisfiable(
+                "the pattern matches too long strings",
+            ));
+        }
+
+        match hir.kind() {
+            // Anchors and word boundaries are not checked
+            HirKind::Empty | HirKind::Look(_) => {}
+            HirKind::Literal(literal) => value.push_str(&String::from_utf8_lossy(&literal.0)),
+            HirKind::Class(Class::Unicode(class)) => value.push(self.class(class)?),
+            HirKind::Class(Class::Bytes(class)) => {
+                let class = class.to_unicode_class().ok_or(FakeError::Unsatisfiable(
+                    "the pattern matches non-ASCII bytes",
+                ))?;
+                value.push(self.class(&class)?);
+            }
+            HirKind::Repetition(repetition) => {
+                let max = repetition
+                    .max
+                    .unwrap_or(repetition.min.saturating_add(MAX_EXTRA_REPETITIONS));
+                for _ in 0..self.rng.gen_range(repetition.min..=max) {
+                    self.pattern(&repetition.sub, value)?;
+                }
+            }
+            HirKind::Capture(capture) => self.pattern(&capture.sub, value)?,
+            HirKind::Concat(hirs) => {
+                for hir in hirs {
+                    self.pattern(hir, value)?;
+                }
+            }
+            HirKind::Alternation(hirs) => {
+                // An alternation always has at least two branches
+                let hir = hirs.choose(&mut self.rng).unwrap();
+                self.pattern(hir, value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pick a character of a class, preferring printable ASCII
+    fn class(&mut self, class: &ClassUnicode) -> Result<char, FakeError> {
+        let mut ascii = ClassUnicode::new([ClassUnicodeRange::new(' ', '~')]);
+        ascii.intersect(class);
+        let class = if ascii.ranges().is_empty() {
+            class
+        } else {
+            &ascii
+        };
+
+        let size: u32 = class
+            .ranges()
+            .iter()
+            .map(|range| range.end() as u32 - range.start() as u32 + 1)
+            .sum();
+        if size == 0 {
+            return Err(FakeError::Unsatisfiable("the character class is empty"));
+        }
+
+        let mut index = self.rng.gen_range(0..size);
+        for range in class.ranges() {
+            let len = range.end() as u32 - range.start() as u32 + 1;
+            if index < len {
+                // Classes never contain surrogates, so every code point in a range is a char
+                return Ok(char::from_u32(range.start() as u32 + index).unwrap());
+            }
+            index -= len;
+        }
+
+        unreachable!("the index is less than the size of the class")
+    }
+
+    fn array(&mut self, schema: &Map<String, Value>, depth: usize) -> Result<Value, FakeError> {
+        // `items` was an array of schemas before draft 2020-12
+        let (prefix, items) = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(prefix)), items) => (prefix.as_slice(), items),
+            (None, Some(Value::Array(prefix))) => {
+                (prefix.as_slice(), schema.get("additionalItems"))
+            }
+            (_, items) => (&[][..], items),
+        };
+        let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+
+        let length = self.length(schema, "minItems", "maxItems", (1, 4))?;
+        let mut values = Vec::with_capacity(length);
+        for index in 0..length {
+            let item = prefix.get(index).or(items).unwrap_or(&Value::Bool(true));
+
+            let mut attempts = 0;
+            let value = loop {
+                let value = self.value(item, depth + 1)?;
+                if !unique || !values.contains(&value) {
+                    break value;
+                }
+
+                attempts += 1;
+                if attempts == MAX_UNIQUE_ATTEMPTS {
+                    return Err(FakeError::Unsatisfiable("the items cannot be unique"));
+                }
+            };
+            values.push(value);
+        }
+
+        Ok(Value::Array(values))
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, depth: usize) -> Result<Value, FakeError> {
+        let mut object = Map::new();
+
+        if let Some(properties) = schema.get("properties") {
+            let properties = properties.as_object().ok_or_else(|| {
+                FakeError::InvalidSchema("`properties` must be an object".to_string())
+            })?;
+
+            for (name, property) in properties {
+                object.insert(name.clone(), self.value(property, depth + 1)?);
+            }
+        }
+
+        // Required properties without a schema follow `additionalProperties`
+        if let Some(Value::Array(required)) = schema.get("required") {
+            let additional = match schema.get("additionalProperties") {
+                Some(Value::Object(additional)) => Value::Object(additional.clone()),
+                _ => Value::Bool(true),
+            };
+
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    object.insert(name.to_string(), self.value(&additional, depth + 1)?);
+                }
+            }
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    /// Pick a length between the `min` and `max` keywords, defaulting to `default`
+    fn length(
+        &mut self,
+        schema: &Map<String, Value>,
+        min: &str,
+        max: &str,
+        default: (u64, u64),
+    ) -> Result<usize, FakeError> {
+        let max = schema.get(max).and_then(Value::as_u64);
+        let min = schema
+            .get(min)
+            .and_then(Value::as_u64)
+            .unwrap_or(default.0.min(max.unwrap_or(u64::MAX)));
+        let max = max.unwrap_or(min.max(default.1));
+
+        if min > max {
+            return Err(FakeError::Unsatisfiable("the length range is empty"));
+        }
+        if min > MAX_LENGTH as u64 {
+            return Err(FakeError::Unsatisfiable("the minimum length is too large"));
+        }
+
+        Ok(self.rng.gen_range(min..=max.min(MAX_LENGTH as u64)) as usize)
+    }
+}
+
+/// Guess the type of a schema without `type` from its keywords
+fn infer_type(schema: &Map<String, Value>) -> &'static str {
+    let has = |keywords: &[&str]| keywords.iter().any(|keyword| schema.contains_key(*keyword));
+
+    if has(&["properties", "required", "additionalProperties"]) {
+        "object"
+    } else if has(&[
+        "items",
+        "prefixItems",
+        "minItems",
+        "maxItems",
+        "uniqueItems",
+    ]) {
+        "array"
+    } else if has(&["minLength", "maxLength", "pattern", "format"]) {
+        "string"
+    } else if has(&[
+        "minimum",
+        "maximum",
+        "exclusiveMinimum",
+        "exclusiveMaximum",
+        "multipleOf",
+    ]) {
+        "number"
+    } else {
+        "null"
+    }
+}
+
+/// The smallest positive integer which is a multiple of `multipleOf`
+fn integer_step(step: &Value) -> Result<f64, FakeError> {
+    let step = step
+        .as_f64()
+        .filter(|step| *step > 0.0 && step.is_finite())
+        .ok_or_else(|| {
+            FakeError::InvalidSchema("`multipleOf` must be a positive number".to_string())
+        })?;
+
+    // Tolerate the rounding error of fractions without an exact binary representation
+    (1..=MAX_STEP_MULTIPLIER)
+        .map(|multiplier| f64::from(multiplier) * step)
+        .find(|multiple| (multiple - multiple.round()).abs() <= 1e-9 * multiple.max(1.0))
+        .map(f64::round)
+        .ok_or(FakeError::Unsatisfiable(
+            "no small integer is a multiple of `multipleOf`",
+        ))
+}
+
+/// The numeric `exclusiveMinimum` or `exclusiveMaximum` of draft 6 and later
+fn exclusive(schema: &Map<String, Value>, keyword: &str) -> Option<f64> {
+    schema.get(keyword).and_then(Value::as_f64)
+}
+
+/// The inclusive bounds of a number, where one missing bound is derived from the other
+fn bounds(schema: &Map<String, Value>, default_min: f64, default_max: f64) -> (f64, f64) {
+    let min = ["minimum", "exclusiveMinimum"]
+        .iter()
+        .filter_map(|keyword| schema.get(*keyword).and_then(Value::as_f64))
+        .reduce(f64::max);
+    let max = ["maximum", "exclusiveMaximum"]
+        .iter()
+        .filter_map(|keyword| schema.get(*keyword).and_then(Value::as_f64))
+        .reduce(f64::min);
+
+    let span = default_max - default_min;
+    match (min, max) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min + span),
+        (None, Some(max)) => (max - span, max),
+        (None, None) => (default_min, default_max),
+    }
+}
@@ -0,0 +1,139 @@
+use hmac::{digest::KeyInit, Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::base64::{Base64Engine, Base64Error};
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacKeyEncoding {
+    #[default]
+    Raw,
+    Hex,
+    Base64,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacMacEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+/// The MAC in all supported encodings
+#[derive(Serialize, Debug)]
+pub struct HmacMac {
+    pub hex: String,
+    pub base64: String,
+    pub base64_url: String,
+}
+
+impl From<&[u8]> for HmacMac {
+    fn from(mac: &[u8]) -> Self {
+        use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+        use base64::Engine;
+
+        Self {
+            hex: hex::encode(mac),
+            base64: STANDARD.encode(mac),
+            base64_url: URL_SAFE_NO_PAD.encode(mac),
+        }
+    }
+}
+
+/// The result of verifying a MAC
+#[derive(Serialize, Debug)]
+pub struct HmacVerification {
+    pub valid: bool,
+    pub expected: HmacMac,
+}
+
+#[derive(Error, Debug)]
+pub enum HmacError {
+    #[error("the hex string is invalid: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+    #[error(transparent)]
+    InvalidBase64(#[from] Base64Error),
+}
+
+/// Decode a key given as a raw, hex or base64 string to a `Vec<u8>`
+///
+/// `engine` is only used by the `base64` encoding and defaults to `standard`
+pub fn decode_key(
+    key: &str,
+    encoding: HmacKeyEncoding,
+    engine: Option<Base64Engine>,
+) -> Result<Vec<u8>, HmacError> {
+    Ok(match encoding {
+        HmacKeyEncoding::Raw => key.as_bytes().to_vec(),
+        HmacKeyEncoding::Hex => hex::decode(key)?,
+        HmacKeyEncoding::Base64 => {
+            super::base64::decode(key, engine.unwrap_or(Base64Engine::Standard), None)?
+        }
+    })
+}
+
+/// Decode a MAC given as a hex or base64 string to a `Vec<u8>`
+///
+/// Base64 MACs are accepted with the standard or url-safe alphabet, padded or not
+pub fn decode_mac(mac: &str, encoding: HmacMacEncoding) -> Result<Vec<u8>, HmacError> {
+    Ok(match encoding {
+        HmacMacEncoding::Hex => hex::decode(mac)?,
+        HmacMacEncoding::Base64 => super::base64::decode(mac, Base64Engine::Auto, None)?,
+    })
+}
+
+/// Sign `data` with `key` and return the MAC
+pub fn sign(algorithm: HmacAlgorithm, key: &[u8], data: &[u8]) -> Vec<u8> {
+    match algorithm {
+        HmacAlgorithm::Md5 => sign_with::<Hmac<md5::Md5>>(key, data),
+        HmacAlgorithm::Sha1 => sign_with::<Hmac<sha1::Sha1>>(key, data),
+        HmacAlgorithm::Sha224 => sign_with::<Hmac<sha2::Sha224>>(key, data),
+        HmacAlgorithm::Sha256 => sign_with::<Hmac<sha2::Sha256>>(key, data),
+        HmacAlgorithm::Sha384 => sign_with::<Hmac<sha2::Sha384>>(key, data),
+        HmacAlgorithm::Sha512 => sign_with::<Hmac<sha2::Sha512>>(key, data),
+    }
+}
+
+/// Verify `mac` of `data` with `key` in constant time
+pub fn verify(algorithm: HmacAlgorithm, key: &[u8], data: &[u8], mac: &[u8]) -> HmacVerification {
+    let valid = match algorithm {
+        HmacAlgorithm::Md5 => verify_with::<Hmac<md5::Md5>>(key, data, mac),
+        HmacAlgorithm::Sha1 => verify_with::<Hmac<sha1::Sha1>>(key, data, mac),
+        HmacAlgorithm::Sha224 => verify_with::<Hmac<sha2::Sha224>>(key, data, mac),
+        HmacAlgorithm::Sha256 => verify_with::<Hmac<sha2::Sha256>>(key, data, mac),
+        HmacAlgorithm::Sha384 => verify_with::<Hmac<sha2::Sha384>>(key, data, mac),
+        HmacAlgorithm::Sha512 => verify_with::<Hmac<sha2::Sha512>>(key, data, mac),
+    };
+
+    HmacVerification {
+        valid,
+        expected: sign(algorithm, key, data).as_slice().into(),
+    }
+}
+
+fn sign_with<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length
+    let mut mac = <M as Mac>::new_from_slice(key).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn verify_with<M: Mac + KeyInit>(key: &[u8], data: &[u8], mac: &[u8]) -> bool {
+    // HMAC accepts keys of any length
+    let mut m = <M as Mac>::new_from_slice(key).unwrap();
+    m.update(data);
+    m.verify_slice(mac).is_ok()
+}
@@ -0,0 +1,475 @@
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, SecondsFormat};
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum IdKind {
+    Ulid,
+    Ksuid,
+    Snowflake,
+    Nanoid,
+    Typeid,
+}
+
+/// Options of the id generators and parsers
+///
+/// Options which do not apply to the kind of id are ignored
+#[derive(Deserialize, Debug, Default)]
+pub struct IdOptions {
+    /// The Unix timestamp in milliseconds, the current time by default
+    pub timestamp: Option<u64>,
+    /// The Snowflake epoch as a Unix timestamp in milliseconds, the Twitter
+    /// epoch by default
+    pub epoch: Option<u64>,
+    /// The Snowflake worker id, `0` by default
+    pub worker_id: Option<u16>,
+    /// The NanoID alphabet, the url-safe alphabet by default
+    pub alphabet: Option<String>,
+    /// The NanoID length, `21` by default
+    pub length: Option<usize>,
+    /// The TypeID prefix, none by default
+    pub prefix: Option<String>,
+}
+
+/// The fields of an id
+#[derive(Serialize, Debug)]
+pub struct IdInspection {
+    pub kind: IdKind,
+    /// The embedded timestamp in RFC 3339
+    pub timestamp: Option<String>,
+    /// The Snowflake worker id
+    pub worker_id: Option<u16>,
+    /// The Snowflake sequence number
+    pub sequence: Option<u16>,
+    /// The TypeID prefix
+    pub prefix: Option<String>,
+    /// The equivalent UUID of a ULID or TypeID
+    pub uuid: Option<String>,
+    /// The raw bytes as a hex string
+    pub hex: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum IdError {
+    #[error("the length of the id must be {0}")]
+    InvalidLength(usize),
+    #[error("the id contains an invalid character: {0:?}")]
+    InvalidCharacter(char),
+    #[error("the id is out of range")]
+    Overflow,
+    #[error("the prefix must be at most 63 lowercase letters or underscores and must not start or end with an underscore")]
+    InvalidPrefix,
+    #[error("the worker id must be less than 1024")]
+    InvalidWorkerId,
+    #[error("the timestamp must not be before the epoch")]
+    InvalidTimestamp,
+    #[error("all 4096 sequence numbers of the timestamp are used")]
+    SequenceExhausted,
+    #[error("the alphabet must have between 1 and 256 unique characters")]
+    InvalidAlphabet,
+    #[error("the length must be between 1 and 1024")]
+    InvalidNanoidLength,
+    #[error("the id is invalid: {0}")]
+    InvalidId(String),
+}
+
+/// Generate an id of the given kind
+pub fn generate(kind: IdKind, options: IdOptions) -> Result<String, IdError> {
+    let timestamp = options.timestamp.unwrap_or_else(now);
+
+    match kind {
+        IdKind::Ulid => {
+            let mut random = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut random[6..]);
+
+            let value = u128::from(timestamp & 0xffff_ffff_ffff) << 80
+                | u128::from_be_bytes(random) & ((1 << 80) - 1);
+
+            Ok(crockford_encode(value))
+        }
+        IdKind::Ksuid => {
+            let seconds = (timestamp / 1000)
+                .checked_sub(KSUID_EPOCH)
+                .and_then(|seconds| u32::try_from(seconds).ok())
+                .ok_or(IdError::InvalidTimestamp)?;
+
+            let mut bytes = [0u8; 20];
+            bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+            rand::thread_rng().fill_bytes(&mut bytes[4..]);
+
+            Ok(base62_encode(&bytes))
+        }
+        IdKind::Snowflake => {
+            let epoch = options.epoch.unwrap_or(TWITTER_EPOCH);
+            let worker_id = options.worker_id.unwrap_or(0);
+            if worker_id >= 1 << 10 {
+                return Err(IdError::InvalidWorkerId);
+            }
+
+            let (elapsed, sequence) = snowflake_next(epoch, worker_id, options.timestamp)?;
+
+            Ok((elapsed << 22 | u64::from(worker_id) << 12 | u64::from(sequence)).to_string())
+        }
+        IdKind::Nanoid => {
+            let alphabet: Vec<char> = options
+                .alphabet
+                .as_deref()
+                .unwrap_or(NANOID_ALPHABET)
+                .chars()
+                .collect();
+            let mut unique = alphabet.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            if alphabet.is_empty() || alphabet.len() > 256 || unique.len() != alphabet.len() {
+                return Err(IdError::InvalidAlphabet);
+            }
+
+            let length = options.length.unwrap_or(21);
+            if !(1..=1024).contains(&length) {
+                return Err(IdError::InvalidNanoidLength);
+            }
+
+            let mut rng = rand::thread_rng();
+            Ok((0..length)
+                // The alphabet is not empty
+                .map(|_| *alphabet.choose(&mut rng).unwrap())
+                .collect())
+        }
+        IdKind::Typeid => {
+            let prefix = options.prefix.unwrap_or_default();
+            check_prefix(&prefix)?;
+
+            let uuid = uuid::Uuid::new_v7(uuid::Timestamp::from_unix(
+                uuid::NoContext,
+                timestamp / 1000,
+                (timestamp % 1000) as u32 * 1_000_000,
+            ));
+            let suffix = crockford_encode(uuid.as_u128()).to_ascii_lowercase();
+
+            Ok(if prefix.is_empty() {
+                suffix
+            } else {
+                format!("{}_{}", prefix, suffix)
+            })
+        }
+    }
+}
+
+/// Parse an id of the given kind
+///
+/// Only `epoch` of the options is used, to parse Snowflake ids
+pub fn parse(kind: IdKind, id: &str, options: IdOptions) -> Result<IdInspection, IdError> {
+    let mut inspection = IdInspection {
+        kind,
+        timestamp: None,
+        worker_id: None,
+        sequence: None,
+        prefix: None,
+        uuid: None,
+        hex: None,
+    };
+
+    match kind {
+        IdKind::Ulid => {
+            let value = crockford_decode(id)?;
+
+            inspection.timestamp = rfc3339((value >> 80) as u64);
+            inspection.uuid = Some(uuid::Uuid::from_u128(value).hyphenated().to_string());
+            inspection.hex = Some(hex::encode(value.to_be_bytes()));
+        }
+        IdKind::Ksuid => {
+            let bytes = base62_decode(id)?;
+            let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+            inspection.timestamp = rfc3339((u64::from(seconds) + KSUID_EPOCH) * 1000);
+            inspection.hex = Some(hex::encode(bytes));
+        }
+        IdKind::Snowflake => {
+            let value: u64 = id
+                .parse()
+                .map_err(|e: std::num::ParseIntError| IdError::InvalidId(e.to_string()))?;
+            let epoch = options.epoch.unwrap_or(TWITTER_EPOCH);
+
+            inspection.timestamp = rfc3339((value >> 22).saturating_add(epoch));
+            inspection.worker_id = Some((value >> 12 & 0x3ff) as u16);
+            inspection.sequence = Some((value & 0xfff) as u16);
+            inspection.hex = Some(hex::encode(value.to_be_bytes()));
+        }
+        IdKind::Nanoid => {
+            let alphabet = options.alphabet.as_deref().unwrap_or(NANOID_ALPHABET);
+            if let Some(c) = id.chars().find(|c| !alphabet.contains(*c)) {
+                return Err(IdError::InvalidCharacter(c));
+            }
+        }
+        IdKind::Typeid => {
+            let (prefix, suffix) = id.rsplit_once('_').unwrap_or(("", id));
+            check_prefix(prefix)?;
+            if suffix.bytes().any(|b| b.is_ascii_uppercase()) {
+                return Err(IdError::InvalidId(
+                    "the suffix must be lowercase".to_string(),
+                ));
+            }
+            let uuid = uuid::Uuid::from_u128(crockford_decode(suffix)?);
+
+            inspection.timestamp = uuid.get_timestamp().and_then(|timestamp| {
+                let (seconds, nanos) = timestamp.to_unix();
+                rfc3339(seconds * 1000 + u64::from(nanos) / 1_000_000)
+            });
+            inspection.prefix = Some(prefix.to_string());
+            inspection.uuid = Some(uuid.hyphenated().to_string());
+            inspection.hex = Some(hex::encode(uuid.as_bytes()));
+        }
+    }
+
+    Ok(inspection)
+}
+
+/// The Twitter Snowflake epoch, 2010-11-04T01:42:54.657Z
+const TWITTER_EPOCH: u64 = 1_288_834_974_657;
+
+/// The KSUID epoch in seconds, 2014-05-13T16:53:20Z
+const KSUID_EPOCH: u64 = 1_400_000_000;
+
+const NANOID_ALPHABET: &str = "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+const BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The Snowflake sequence numbers handed out by this process
+static SNOWFLAKES: Mutex<Snowflakes> = Mutex::new(Snowflakes {
+    clock: BTreeMap::new(),
+    sequences: BTreeMap::new(),
+});
+
+struct Snowflakes {
+    /// The last millisecond since the epoch of the current time for each epoch and worker id,
+    /// which never goes back even if the system clock does
+    clock: BTreeMap<(u64, u16), u64>,
+    /// The next sequence number for each epoch, worker id and millisecond since the epoch, shared
+    /// by the current time and fixed timestamps and kept for the life of the process
+    sequences: BTreeMap<(u64, u16, u64), u16>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn rfc3339(millis: u64) -> Option<String> {
+    let secs = i64::try_from(millis / 1000).ok()?;
+    DateTime::from_timestamp(secs, (millis % 1000) as u32 * 1_000_000)
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+/// Return the next Snowflake timestamp since the epoch and sequence number
+///
+/// An id is never handed out twice for the same epoch and worker id: the current time moves on to
+/// the next millisecond once its sequence numbers are used, while a fixed timestamp fails.
+fn snowflake_next(
+    epoch: u64,
+    worker_id: u16,
+    timestamp: Option<u64>,
+) -> Result<(u64, u16), IdError> {
+    let elapsed = |timestamp: u64| {
+        timestamp
+            .checked_sub(epoch)
+            .filter(|elapsed| *elapsed < 1 << 41)
+            .ok_or(IdError::InvalidTimestamp)
+    };
+
+    loop {
+        let wait = {
+            let mut snowflakes = SNOWFLAKES.lock().unwrap_or_else(|e| e.into_inner());
+            let Snowflakes { clock, sequences } = &mut *snowflakes;
+
+            let (elapsed, clock) = match timestamp {
+                Some(timestamp) => (elapsed(timestamp)?, None),
+                None => {
+                    let clock = clock.entry((epoch, worker_id)).or_default();
+                    *clock = elapsed(now())?.max(*clock);
+                    (*clock, Some(clock))
+                }
+            };
+
+            let next = sequences.entry((epoch, worker_id, elapsed)).or_default();
+            if *next <= 0xfff {
+                let sequence = *next;
+                *next += 1;
+                return Ok((elapsed, sequence));
+            }
+            let Some(clock) = clock else {
+                return Err(IdError::SequenceExhausted);
+            };
+
+            // Wait for the next millisecond outside the lock, or not at all if the system clock
+            // went back and is behind it anyway
+            *clock = elapsed + 1;
+            Duration::from_millis(epoch + elapsed + 1)
+                .saturating_sub(
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default(),
+                )
+                .min(Duration::from_millis(1))
+        };
+
+        std::thread::sleep(wait);
+    }
+}
+
+fn check_prefix(prefix: &str) -> Result<(), IdError> {
+    if prefix.len() > 63
+        || prefix.starts_with('_')
+        || prefix.ends_with('_')
+        || !prefix.bytes().all(|b| b.is_ascii_lowercase() || b == b'_')
+    {
+        return Err(IdError::InvalidPrefix);
+    }
+
+    Ok(())
+}
+
+/// Encode 128 bits as 26 Crockford base32 characters, as used by ULID and TypeID
+fn crockford_encode(value: u128) -> String {
+    (0..26)
+        .map(|i| CROCKFORD_ALPHABET[(value >> (125 - 5 * i) & 0x1f) as usize] as char)
+        .collect()
+}
+
+fn crockford_decode(data: &str) -> Result<u128, IdError> {
+    if data.len() != 26 {
+        return Err(IdError::InvalidLength(26));
+    }
+
+    let mut value = 0u128;
+    for (i, c) in data.chars().enumerate() {
+        let digit = CROCKFORD_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase() as u8)
+            .ok_or(IdError::InvalidCharacter(c))? as u128;
+
+        // The first character only holds 3 bits
+        if i == 0 && digit > 7 {
+            return Err(IdError::Overflow);
+        }
+        value = value << 5 | digit;
+    }
+
+    Ok(value)
+}
+
+/// Encode 20 bytes as 27 base62 characters, as used by KSUID
+fn base62_encode(bytes: &[u8; 20]) -> String {
+    let mut number = bytes.to_vec();
+    let mut encoded = Vec::with_capacity(27);
+
+    for _ in 0..27 {
+        // Divide the big-endian number by 62 in place
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let value = remainder << 8 | u32::from(*byte);
+            *byte = (value / 62) as u8;
+            remainder = value % 62;
+        }
+        encoded.push(BASE62_ALPHABET[remainder as usize]);
+    }
+    encoded.reverse();
+
+    String::from_utf8(encoded).unwrap()
+}
+
+fn base62_decode(data: &str) -> Result<[u8; 20], IdError> {
+    if data.len() != 27 {
+        return Err(IdError::InvalidLength(27));
+    }
+
+    let mut bytes = [0u8; 20];
+    for c in data.chars() {
+        let digit = BASE62_ALPHABET
+            .iter()
+            .position(|&a| a as char == c)
+            .ok_or(IdError::InvalidCharacter(c))? as u32;
+
+        // Multiply the big-endian number by 62 in place and add the digit
+        let mut carry = digit;
+        for byte in bytes.iter_mut().rev() {
+            let value = u32::from(*byte) * 62 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(IdError::Overflow);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn snowflake(epoch: u64, worker_id: u16, timestamp: Option<u64>) -> String {
+        let options = IdOptions {
+            timestamp,
+            epoch: Some(epoch),
+            worker_id: Some(worker_id),
+            ..Default::default()
+        };
+        generate(IdKind::Snowflake, options).unwrap()
+    }
+
+    #[test]
+    fn snowflakes_are_unique() {
+        // Each test uses its own worker id, as the sequences are shared by the process
+        let mut ids = HashSet::new();
+        let fixed = now();
+        for _ in 0..100 {
+            for epoch in [TWITTER_EPOCH, 0] {
+                assert!(ids.insert(snowflake(epoch, 1, None)));
+                assert!(ids.insert(snowflake(epoch, 1, Some(fixed))));
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_timestamp_sequence() {
+        let timestamp = TWITTER_EPOCH + 1;
+        for sequence in 0..=0xfff {
+            let id = snowflake(TWITTER_EPOCH, 2, Some(timestamp));
+            let inspection = parse(IdKind::Snowflake, &id, IdOptions::default()).unwrap();
+            assert_eq!(inspection.sequence, Some(sequence));
+            assert_eq!(inspection.worker_id, Some(2));
+        }
+
+        let options = IdOptions {
+            timestamp: Some(timestamp),
+            worker_id: Some(2),
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate(IdKind::Snowflake, options),
+            Err(IdError::SequenceExhausted)
+        ));
+    }
+
+    #[test]
+    fn current_time_moves_on() {
+        let ids: HashSet<_> = (0..0x3000).map(|_| snowflake(0, 3, None)).collect();
+        assert_eq!(ids.len(), 0x3000);
+    }
+}
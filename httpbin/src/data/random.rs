@@ -0,0 +1,175 @@
+use std::{convert::Infallible, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
+use thiserror::Error;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum RandomConstCharset {
+    /// `A-Z`, `a-z` and `0-9`
+    Alphanumeric,
+    /// `A-Z` and `a-z`
+    Alpha,
+    /// `0-9`
+    Numeric,
+    /// `0-9` and `a-f`
+    Hex,
+    /// `a-z`
+    Lower,
+    /// `A-Z`
+    Upper,
+}
+
+impl RandomConstCharset {
+    fn chars(&self) -> &'static str {
+        match self {
+            RandomConstCharset::Alphanumeric => {
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+            }
+            RandomConstCharset::Alpha => "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+            RandomConstCharset::Numeric => "0123456789",
+            RandomConstCharset::Hex => "0123456789abcdef",
+            RandomConstCharset::Lower => "abcdefghijklmnopqrstuvwxyz",
+            RandomConstCharset::Upper => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        }
+    }
+}
+
+/// The characters of a random string, either a named set or the characters themselves
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum RandomCharset {
+    Const(RandomConstCharset),
+    Custom(String),
+}
+
+impl FromStr for RandomCharset {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "alphanumeric" => Self::Const(RandomConstCharset::Alphanumeric),
+            "alpha" => Self::Const(RandomConstCharset::Alpha),
+            "numeric" => Self::Const(RandomConstCharset::Numeric),
+            "hex" => Self::Const(RandomConstCharset::Hex),
+            "lower" => Self::Const(RandomConstCharset::Lower),
+            "upper" => Self::Const(RandomConstCharset::Upper),
+            _ => Self::Custom(s.to_owned()),
+        })
+    }
+}
+
+/// Comma-separated items to choose from or shuffle
+#[serde_as]
+#[derive(Deserialize, Debug)]
+pub struct RandomItems(
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")] pub Vec<String>,
+);
+
+impl From<Vec<String>> for RandomItems {
+    fn from(items: Vec<String>) -> Self {
+        Self(items)
+    }
+}
+
+impl FromStr for RandomItems {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "" => Self(Vec::new()),
+            _ => Self(s.split(',').map(str::to_owned).collect()),
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RandomError {
+    #[error("the range is empty or not finite")]
+    InvalidRange,
+    #[error("the length must be between 1 and {0}")]
+    InvalidLength(usize),
+    #[error("the charset is empty")]
+    EmptyCharset,
+    #[error("the items are empty")]
+    EmptyItems,
+}
+
+/// The maximum length of a random string
+pub const MAX_LENGTH: usize = 4096;
+
+/// Generate a random integer in `min..=max`, which defaults to `0..=100`
+pub fn int(min: Option<i64>, max: Option<i64>, seed: Option<u64>) -> Result<i64, RandomError> {
+    let (min, max) = (min.unwrap_or(0), max.unwrap_or(100));
+    if min > max {
+        return Err(RandomError::InvalidRange);
+    }
+
+    Ok(rng(seed).gen_range(min..=max))
+}
+
+/// Generate a random float in `min..max`, which defaults to `0..1`
+pub fn float(min: Option<f64>, max: Option<f64>, seed: Option<u64>) -> Result<f64, RandomError> {
+    let (min, max) = (min.unwrap_or(0.0), max.unwrap_or(1.0));
+    // `gen_range` panics if the width of the range overflows
+    if !(min < max && (max - min).is_finite()) {
+        return Err(RandomError::InvalidRange);
+    }
+
+    Ok(rng(seed).gen_range(min..max))
+}
+
+/// Generate a random string of `length` characters, which defaults to 16 alphanumeric characters
+pub fn string(
+    charset: Option<RandomCharset>,
+    length: Option<usize>,
+    seed: Option<u64>,
+) -> Result<String, RandomError> {
+    let chars: Vec<char> = match &charset {
+        None => RandomConstCharset::Alphanumeric.chars().chars().collect(),
+        Some(RandomCharset::Const(charset)) => charset.chars().chars().collect(),
+        Some(RandomCharset::Custom(charset)) => charset.chars().collect(),
+    };
+    if chars.is_empty() {
+        return Err(RandomError::EmptyCharset);
+    }
+
+    let length = length.unwrap_or(16);
+    if length == 0 || length > MAX_LENGTH {
+        return Err(RandomError::InvalidLength(MAX_LENGTH));
+    }
+
+    let mut rng = rng(seed);
+    // Sample a `u32` rather than a `usize` to be the same on 32-bit and 64-bit platforms
+    Ok((0..length)
+        .map(|_| chars[rng.gen_range(0..chars.len() as u32) as usize])
+        .collect())
+}
+
+/// Choose one of the items at random
+pub fn choice(items: RandomItems, seed: Option<u64>) -> Result<String, RandomError> {
+    items
+        .0
+        .choose(&mut rng(seed))
+        .cloned()
+        .ok_or(RandomError::EmptyItems)
+}
+
+/// Shuffle the items
+pub fn shuffle(items: RandomItems, seed: Option<u64>) -> Vec<String> {
+    let mut items = items.0;
+    items.shuffle(&mut rng(seed));
+
+    items
+}
+
+/// A ChaCha8 generator, which produces the same sequence on every platform for the same seed
+pub(crate) fn rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Offset, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormat {
+    /// `2006-01-02T15:04:05Z`
+    #[default]
+    Rfc3339,
+    /// `Mon, 2 Jan 2006 15:04:05 +0000`
+    Rfc2822,
+    /// `Mon, 02 Jan 2006 15:04:05 GMT`, always in GMT
+    HttpDate,
+    /// Seconds since the Unix epoch
+    Unix,
+    /// Milliseconds since the Unix epoch
+    UnixMs,
+    /// Nanoseconds since the Unix epoch
+    UnixNs,
+}
+
+/// The format a time was recognized as
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeInput {
+    /// `2006-01-02T15:04:05Z`
+    Rfc3339,
+    /// `Mon, 2 Jan 2006 15:04:05 +0000`
+    Rfc2822,
+    /// `Mon, 02 Jan 2006 15:04:05 GMT`
+    HttpDate,
+    /// `Monday, 02-Jan-06 15:04:05 GMT`, the obsolete RFC 850 form of HTTP-date
+    Rfc850,
+    /// `Mon Jan  2 15:04:05 2006`, the obsolete asctime form of HTTP-date
+    Asctime,
+    /// Seconds since the Unix epoch, with an optional fraction
+    Unix,
+    /// Milliseconds since the Unix epoch
+    UnixMs,
+    /// Nanoseconds since the Unix epoch
+    UnixNs,
+    /// `2006-01-02T15:04:05`, `2006-01-02T15:04` or `2006-01-02` without an offset, in the source time zone
+    Local,
+}
+
+/// A time in all supported formats
+#[derive(Serialize, Debug)]
+pub struct TimeInspection {
+    /// The format the input was recognized as
+    pub input: TimeInput,
+    /// The time zone the time is reported in
+    pub timezone: String,
+    /// The UTC offset of the time zone at the time
+    pub offset: String,
+    /// The abbreviation of the time zone at the time, e.g. `CET`
+    pub abbreviation: String,
+    pub rfc3339: String,
+    pub rfc2822: String,
+    pub http_date: String,
+    pub unix: i64,
+    pub unix_ms: i64,
+    /// Nanoseconds since the Unix epoch, absent outside of 1677 to 2262
+    pub unix_ns: Option<i64>,
+}
+
+#[derive(Error, Debug)]
+pub enum TimeError {
+    #[error("the time zone {0} is unknown")]
+    UnknownTimezone(String),
+    #[error("the time is invalid")]
+    InvalidTime,
+    #[error("the time must be between the years 0 and 9999")]
+    OutOfRange,
+    #[error("the time must be between the years 1677 and 2262 in nanoseconds")]
+    NanosOutOfRange,
+    #[error("the local time does not exist in {0}")]
+    NonexistentLocalTime(String),
+}
+
+/// Format the current time, in `tz` if given or UTC otherwise
+pub fn now(format: TimeFormat, tz: Option<&str>) -> Result<String, TimeError> {
+    render(Utc::now(), format, timezone(tz)?)
+}
+
+/// Parse a time in any supported format and report it in `tz`
+///
+/// A local time without an offset is taken to be in `from`, which defaults to UTC.
+pub fn parse(
+    time: &str,
+    from: Option<&str>,
+    tz: Option<&str>,
+) -> Result<TimeInspection, TimeError> {
+    let (time, input) = recognize(time, timezone(from)?)?;
+    let tz = timezone(tz)?;
+    let local = time.with_timezone(&tz);
+    check_year(&local)?;
+
+    Ok(TimeInspection {
+        input,
+        timezone: tz.name().to_owned(),
+        offset: local.offset().fix().to_string(),
+        abbreviation: local.format("%Z").to_string(),
+        rfc3339: local.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        rfc2822: local.to_rfc2822(),
+        http_date: http_date(time),
+        unix: time.timestamp(),
+        unix_ms: time.timestamp_millis(),
+        unix_ns: time.timestamp_nanos_opt(),
+    })
+}
+
+/// Parse a time in any supported format and format it in `tz`
+///
+/// A local time without an offset is taken to be in `from`, which defaults to UTC.
+pub fn convert(
+    time: &str,
+    from: Option<&str>,
+    tz: Option<&str>,
+    format: TimeFormat,
+) -> Result<String, TimeError> {
+    let (time, _) = recognize(time, timezone(from)?)?;
+
+    render(time, format, timezone(tz)?)
+}
+
+fn timezone(tz: Option<&str>) -> Result<Tz, TimeError> {
+    match tz {
+        None => Ok(Tz::UTC),
+        Some(tz) => tz
+            .parse()
+            .map_err(|_| TimeError::UnknownTimezone(tz.to_owned())),
+    }
+}
+
+fn render(time: DateTime<Utc>, format: TimeFormat, tz: Tz) -> Result<String, TimeError> {
+    let local = time.with_timezone(&tz);
+    check_year(&local)?;
+
+    Ok(match format {
+        TimeFormat::Rfc3339 => local.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        TimeFormat::Rfc2822 => local.to_rfc2822(),
+        TimeFormat::HttpDate => http_date(time),
+        TimeFormat::Unix => time.timestamp().to_string(),
+        TimeFormat::UnixMs => time.timestamp_millis().to_string(),
+        TimeFormat::UnixNs => time
+            .timestamp_nanos_opt()
+            .ok_or(TimeError::NanosOutOfRange)?
+            .to_string(),
+    })
+}
+
+/// RFC 2822 and HTTP-date only have room for four-digit years
+fn check_year<T: TimeZone>(time: &DateTime<T>) -> Result<(), TimeError> {
+    match time.year() {
+        0..=9999 => Ok(()),
+        _ => Err(TimeError::OutOfRange),
+    }
+}
+
+fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn recognize(time: &str, from: Tz) -> Result<(DateTime<Utc>, TimeInput), TimeError> {
+    let time = time.trim();
+
+    if is_number(time) {
+        let (time, input) = unix(time)?;
+        check_year(&time)?;
+        return Ok((time, input));
+    }
+
+    let recognized = if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        (time.with_timezone(&Utc), TimeInput::Rfc3339)
+    } else if let Ok(time) = NaiveDateTime::parse_from_str(time, "%a, %d %b %Y %H:%M:%S GMT") {
+        (time.and_utc(), TimeInput::HttpDate)
+    } else if let Ok(time) = NaiveDateTime::parse_from_str(time, "%A, %d-%b-%y %H:%M:%S GMT") {
+        (time.and_utc(), TimeInput::Rfc850)
+    } else if let Ok(time) = NaiveDateTime::parse_from_str(time, "%a %b %e %H:%M:%S %Y") {
+        (time.and_utc(), TimeInput::Asctime)
+    } else if let Ok(time) = DateTime::parse_from_rfc2822(time) {
+        (time.with_timezone(&Utc), TimeInput::Rfc2822)
+    } else {
+        let naive = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S%.f"))
+            .or_else(|_| NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M"))
+            .or_else(|_| NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M"))
+            .or_else(|_| {
+                // Midnight always exists
+                NaiveDate::parse_from_str(time, "%Y-%m-%d").map(|d| d.and_hms_opt(0, 0, 0).unwrap())
+            })
+            .map_err(|_| TimeError::InvalidTime)?;
+        // The earlier of two candidates is taken when the clocks go back
+        let time = from
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| TimeError::NonexistentLocalTime(from.name().to_owned()))?;
+
+        (time.with_timezone(&Utc), TimeInput::Local)
+    };
+    check_year(&recognized.0)?;
+
+    Ok(recognized)
+}
+
+fn is_number(time: &str) -> bool {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let digits = time.strip_prefix('-').unwrap_or(time);
+
+    match digits.split_once('.') {
+        Some((int, frac)) => is_digits(int) && is_digits(frac),
+        None => is_digits(digits),
+    }
+}
+
+/// Parse a Unix timestamp by its number of digits
+///
+/// Up to 11 digits are seconds, which may have a fraction, up to 14 digits are milliseconds and
+/// more are nanoseconds.
+fn unix(time: &str) -> Result<(DateTime<Utc>, TimeInput), TimeError> {
+    let (negative, digits) = match time.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, time),
+    };
+    let (int, frac) = match digits.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (digits, None),
+    };
+    let value: i64 = int.parse().map_err(|_| TimeError::OutOfRange)?;
+    let value = if negative { -value } else { value };
+
+    let (secs, nanos, input) = match (int.len(), frac) {
+        (0..=11, _) => {
+            let frac = frac.unwrap_or("");
+            if frac.len() > 9 {
+                return Err(TimeError::InvalidTime);
+            }
+            // Pad the fraction to nanoseconds, e.g. `5` to `500000000`, which never fails
+            let nanos: i64 = format!("{frac:0<9}").parse().unwrap();
+
+            (
+                value,
+                if negative { -nanos } else { nanos },
+                TimeInput::Unix,
+            )
+        }
+        (12..=14, None) => (value, 0, TimeInput::UnixMs),
+        (_, None) => (0, value, TimeInput::UnixNs),
+        (_, Some(_)) => return Err(TimeError::InvalidTime),
+    };
+    let (secs, nanos) = match input {
+        TimeInput::UnixMs => (secs.div_euclid(1_000), secs.rem_euclid(1_000) * 1_000_000),
+        _ => (
+            secs + nanos.div_euclid(1_000_000_000),
+            nanos.rem_euclid(1_000_000_000),
+        ),
+    };
+    // `nanos` is in `0..1_000_000_000` and always fits in a `u32`
+    let time = DateTime::from_timestamp(secs, nanos as u32).ok_or(TimeError::OutOfRange)?;
+
+    Ok((time, input))
+}
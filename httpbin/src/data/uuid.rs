@@ -1,9 +1,17 @@
-use std::{convert::Infallible, str::FromStr};
+use std::{
+    convert::Infallible,
+    str::FromStr,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use serde::Deserialize;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::{formats::CommaSeparator, serde_as, StringWithSeparator};
 use thiserror::Error;
 
+use super::base64::{Base64Engine, Base64Error};
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum UuidConstNamespace {
@@ -34,7 +42,7 @@ impl FromStr for UuidNamespace {
     }
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum UuidFormat {
     #[default]
@@ -44,9 +52,170 @@ pub enum UuidFormat {
     Braced,
 }
 
-#[serde_as]
-#[derive(Deserialize, Debug)]
-pub struct UuidNodeId(#[serde_as(as = "StringWithSeparator::<CommaSeparator, u8>")] pub Vec<u8>);
+/// How to render a batch of UUIDs
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidOutput {
+    /// One UUID per line
+    #[default]
+    Text,
+    /// A JSON array
+    Json,
+    /// One JSON string per line
+    Ndjson,
+}
+
+impl UuidOutput {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            UuidOutput::Text => "text/plain; charset=utf-8",
+            UuidOutput::Json => "application/json",
+            UuidOutput::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+/// The version to reorder a v1 or v6 UUID into
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidConvertVersion {
+    V1,
+    V6,
+}
+
+/// The representation of a converted UUID
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidRepresentation {
+    #[default]
+    Hyphenated,
+    Simple,
+    Urn,
+    Braced,
+    /// The raw 16 bytes
+    Bytes,
+    /// The 16 bytes in base64
+    Base64,
+    /// A 128-bit unsigned integer in decimal
+    Integer,
+}
+
+impl UuidRepresentation {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            UuidRepresentation::Bytes => "application/octet-stream",
+            _ => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+/// How to fill the 12 `rand_a` bits of a v7 UUID, see RFC 9562 section 6.2
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidV7Method {
+    /// Random bits
+    #[default]
+    Random,
+    /// The sub-millisecond fraction of the timestamp (method 3)
+    Precision,
+    /// A counter incremented within the same millisecond (method 1)
+    Counter,
+}
+
+/// The unit of a numeric `UuidTimestamp`
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidTimestampUnit {
+    /// 100-nanosecond intervals since 1582-10-15, as in RFC 9562
+    #[default]
+    Ticks,
+    /// Milliseconds since the Unix epoch
+    Ms,
+}
+
+/// A timestamp for time-based UUIDs, either a number or in RFC 3339
+#[derive(Debug, Clone)]
+pub enum UuidTimestamp {
+    Number(u64),
+    DateTime(DateTime<FixedOffset>),
+}
+
+impl FromStr for UuidTimestamp {
+    type Err = UuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(number) = s.parse() {
+            return Ok(Self::Number(number));
+        }
+
+        DateTime::parse_from_rfc3339(s)
+            .map(Self::DateTime)
+            .map_err(|_| UuidError::InvalidTimestamp)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidTimestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl UuidTimestamp {
+    /// Convert to 100-nanosecond intervals since 1582-10-15
+    ///
+    /// `unit` is only used for numeric timestamps
+    pub fn to_ticks(&self, unit: UuidTimestampUnit) -> Result<u64, UuidError> {
+        let ticks = match (self, unit) {
+            (Self::Number(ticks), UuidTimestampUnit::Ticks) => Some(*ticks),
+            (Self::Number(millis), UuidTimestampUnit::Ms) => millis
+                .checked_mul(10_000)
+                .and_then(|ticks| ticks.checked_add(UUID_TICKS_BETWEEN_EPOCHS as u64)),
+            (Self::DateTime(dt), _) => dt
+                .timestamp()
+                .checked_mul(10_000_000)
+                .and_then(|ticks| ticks.checked_add(dt.timestamp_subsec_nanos() as i64 / 100))
+                .and_then(|ticks| ticks.checked_add(UUID_TICKS_BETWEEN_EPOCHS))
+                .and_then(|ticks| u64::try_from(ticks).ok()),
+        };
+
+        ticks.ok_or(UuidError::TimestampOutOfRange)
+    }
+}
+
+/// A node id, either as a MAC address like `aa:bb:cc:dd:ee:ff` or as comma-separated decimal bytes
+#[derive(Debug)]
+pub struct UuidNodeId(pub Vec<u8>);
+
+impl FromStr for UuidNodeId {
+    type Err = UuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let node_id = if s.contains([':', '-']) {
+            s.split([':', '-'])
+                .map(|b| match b.len() {
+                    2 => u8::from_str_radix(b, 16).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+        } else {
+            s.split(',')
+                .map(|b| b.trim().parse().ok())
+                .collect::<Option<Vec<_>>>()
+        };
+
+        node_id.map(Self).ok_or(UuidError::InvalidNodeId)
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidNodeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
 
 impl From<Vec<u8>> for UuidNodeId {
     fn from(node_id: Vec<u8>) -> Self {
@@ -84,25 +253,73 @@ impl TryInto<[u8; 16]> for UuidBuffer {
     }
 }
 
+/// A UUID in all supported formats
+#[derive(Serialize, Debug)]
+pub struct UuidFormats {
+    pub hyphenated: String,
+    pub simple: String,
+    pub urn: String,
+    pub braced: String,
+}
+
+impl From<&uuid::Uuid> for UuidFormats {
+    fn from(uuid: &uuid::Uuid) -> Self {
+        Self {
+            hyphenated: format_uuid(uuid, UuidFormat::Hyphenated),
+            simple: format_uuid(uuid, UuidFormat::Simple),
+            urn: format_uuid(uuid, UuidFormat::Urn),
+            braced: format_uuid(uuid, UuidFormat::Braced),
+        }
+    }
+}
+
+/// The fields of a UUID
+#[derive(Serialize, Debug)]
+pub struct UuidInspection {
+    /// The version number, `0` for the nil UUID and `15` for the max UUID
+    pub version: usize,
+    /// The variant, one of `ncs`, `rfc4122`, `microsoft` or `future`
+    pub variant: &'static str,
+    /// The embedded timestamp in RFC 3339 for v1, v6 and v7 UUIDs
+    pub timestamp: Option<String>,
+    /// The clock sequence for v1 and v6 UUIDs
+    pub clock_seq: Option<u16>,
+    /// The node id as a MAC address for v1 and v6 UUIDs
+    pub node_id: Option<String>,
+    pub formats: UuidFormats,
+}
+
 #[derive(Error, Debug)]
 pub enum UuidError {
     #[error("the uuid string is invalid: {0}")]
     InvalidUuid(#[from] uuid::Error),
+    #[error("the node_id must be a MAC address or comma-separated decimal bytes")]
+    InvalidNodeId,
     #[error("the length of node_id must be 6")]
     InvalidNodeIdLength,
+    #[error("the timestamp must be a number or in RFC 3339")]
+    InvalidTimestamp,
+    #[error("the timestamp is out of range")]
+    TimestampOutOfRange,
     #[error("the length of buffer must be 16")]
     InvalidBufferLength,
+    #[error("the count must be between 1 and {0}")]
+    InvalidCount(usize),
+    #[error("only v1 and v6 UUIDs can be converted between versions, got v{0}")]
+    UnsupportedConversion(usize),
+    #[error(transparent)]
+    Base64(#[from] Base64Error),
 }
 
 /// Generate a v1 UUID with the given timestamp and node_id
+///
+/// A random node_id is used if none is given
 pub fn new_v1(
     timestamp: Option<(u64, u16)>,
-    node_id: UuidNodeId,
+    node_id: Option<UuidNodeId>,
     format: UuidFormat,
 ) -> Result<String, UuidError> {
-    let node_id: [u8; 6] = node_id
-        .try_into()
-        .map_err(|_| UuidError::InvalidNodeIdLength)?;
+    let node_id = resolve_node_id(node_id)?;
 
     let uuid = match timestamp {
         Some((ticks, counter)) => {
@@ -111,12 +328,36 @@ pub fn new_v1(
         None => uuid::Uuid::now_v1(&node_id),
     };
 
-    Ok(match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    })
+    Ok(format_uuid(&uuid, format))
+}
+
+/// Generate `count` v1 UUIDs with the given timestamp and node_id
+///
+/// The counter is incremented for each UUID if a timestamp is given, carrying over to the next
+/// tick when the 14-bit clock sequence overflows.
+/// A random node_id is shared by the batch if none is given
+pub fn new_v1_batch(
+    timestamp: Option<(u64, u16)>,
+    node_id: Option<UuidNodeId>,
+    format: UuidFormat,
+    count: usize,
+    max_count: usize,
+) -> Result<Vec<String>, UuidError> {
+    check_count(count, max_count)?;
+    let node_id = resolve_node_id(node_id)?;
+
+    Ok((0..count)
+        .map(|i| {
+            let uuid = match timestamp {
+                Some((ticks, counter)) => {
+                    uuid::Uuid::new_v1(batch_timestamp(ticks, counter, i), &node_id)
+                }
+                None => uuid::Uuid::now_v1(&node_id),
+            };
+
+            format_uuid(&uuid, format)
+        })
+        .collect())
 }
 
 /// Generate a v3 UUID with the given namespace and name
@@ -133,23 +374,26 @@ pub fn new_v3(
         UuidNamespace::Custom(ns) => uuid::Uuid::parse_str(&ns)?,
     };
     let uuid = uuid::Uuid::new_v3(&ns, name.as_bytes());
-    Ok(match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    })
+    Ok(format_uuid(&uuid, format))
 }
 
 /// Generate a v4 UUID
 pub fn new_v4(format: UuidFormat) -> String {
     let uuid = uuid::Uuid::new_v4();
-    match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    }
+    format_uuid(&uuid, format)
+}
+
+/// Generate `count` v4 UUIDs
+pub fn new_v4_batch(
+    format: UuidFormat,
+    count: usize,
+    max_count: usize,
+) -> Result<Vec<String>, UuidError> {
+    check_count(count, max_count)?;
+
+    Ok((0..count)
+        .map(|_| format_uuid(&uuid::Uuid::new_v4(), format))
+        .collect())
 }
 
 /// Generate a v5 UUID with the given namespace and name
@@ -166,23 +410,18 @@ pub fn new_v5(
         UuidNamespace::Custom(ns) => uuid::Uuid::parse_str(&ns)?,
     };
     let uuid = uuid::Uuid::new_v5(&ns, name.as_bytes());
-    Ok(match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    })
+    Ok(format_uuid(&uuid, format))
 }
 
 /// Generate a v6 UUID with the given timestamp and node_id
+///
+/// A random node_id is used if none is given
 pub fn new_v6(
     timestamp: Option<(u64, u16)>,
-    node_id: UuidNodeId,
+    node_id: Option<UuidNodeId>,
     format: UuidFormat,
 ) -> Result<String, UuidError> {
-    let node_id: [u8; 6] = node_id
-        .try_into()
-        .map_err(|_| UuidError::InvalidNodeIdLength)?;
+    let node_id = resolve_node_id(node_id)?;
 
     let uuid = match timestamp {
         Some((ticks, counter)) => {
@@ -191,37 +430,357 @@ pub fn new_v6(
         None => uuid::Uuid::now_v6(&node_id),
     };
 
-    Ok(match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    })
+    Ok(format_uuid(&uuid, format))
+}
+
+/// Generate `count` v6 UUIDs with the given timestamp and node_id
+///
+/// The counter is incremented for each UUID if a timestamp is given, carrying over to the next
+/// tick when the 14-bit clock sequence overflows.
+/// A random node_id is shared by the batch if none is given
+pub fn new_v6_batch(
+    timestamp: Option<(u64, u16)>,
+    node_id: Option<UuidNodeId>,
+    format: UuidFormat,
+    count: usize,
+    max_count: usize,
+) -> Result<Vec<String>, UuidError> {
+    check_count(count, max_count)?;
+    let node_id = resolve_node_id(node_id)?;
+
+    Ok((0..count)
+        .map(|i| {
+            let uuid = match timestamp {
+                Some((ticks, counter)) => {
+                    uuid::Uuid::new_v6(batch_timestamp(ticks, counter, i), &node_id)
+                }
+                None => uuid::Uuid::now_v6(&node_id),
+            };
+
+            format_uuid(&uuid, format)
+        })
+        .collect())
 }
 
 /// Generate a v7 UUID with the given timestamp
-pub fn new_v7(timestamp: Option<(u64, u16)>, format: UuidFormat) -> String {
-    let uuid = match timestamp {
-        Some((ticks, counter)) => uuid::Uuid::new_v7(uuid::Timestamp::from_rfc4122(ticks, counter)),
-        None => uuid::Uuid::now_v7(),
-    };
+///
+/// With `UuidV7Method::Counter`, the counter of the timestamp is used if given
+pub fn new_v7(timestamp: Option<(u64, u16)>, method: UuidV7Method, format: UuidFormat) -> String {
+    let uuid = v7_uuids(timestamp, method, 1).remove(0);
 
-    match format {
-        UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
-        UuidFormat::Simple => format!("{}", uuid.as_simple()),
-        UuidFormat::Urn => format!("{}", uuid.as_urn()),
-        UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    }
+    format_uuid(&uuid, format)
+}
+
+/// Generate `count` v7 UUIDs with the given timestamp
+///
+/// The UUIDs are sorted so that they are monotonic within the batch
+pub fn new_v7_batch(
+    timestamp: Option<(u64, u16)>,
+    method: UuidV7Method,
+    format: UuidFormat,
+    count: usize,
+    max_count: usize,
+) -> Result<Vec<String>, UuidError> {
+    check_count(count, max_count)?;
+
+    let mut uuids = v7_uuids(timestamp, method, count);
+    uuids.sort_unstable();
+
+    Ok(uuids.iter().map(|uuid| format_uuid(uuid, format)).collect())
 }
 
 pub fn new_v8(buf: UuidBuffer, format: UuidFormat) -> Result<String, UuidError> {
     let buf = buf.try_into().map_err(|_| UuidError::InvalidBufferLength)?;
 
     let uuid = uuid::Uuid::new_v8(buf);
-    Ok(match format {
+    Ok(format_uuid(&uuid, format))
+}
+
+/// Generate the nil UUID, with all bits set to 0
+pub fn new_nil(format: UuidFormat) -> String {
+    format_uuid(&uuid::Uuid::nil(), format)
+}
+
+/// Generate the max UUID, with all bits set to 1
+pub fn new_max(format: UuidFormat) -> String {
+    format_uuid(&uuid::Uuid::from_u128(u128::MAX), format)
+}
+
+/// Inspect a UUID given in any of the `UuidFormat` formats, or `nil` or `max`
+pub fn inspect(uuid: &str) -> Result<UuidInspection, UuidError> {
+    let uuid = parse_uuid(uuid)?;
+
+    let variant = match uuid.get_variant() {
+        uuid::Variant::NCS => "ncs",
+        uuid::Variant::RFC4122 => "rfc4122",
+        uuid::Variant::Microsoft => "microsoft",
+        _ => "future",
+    };
+
+    let timestamp = uuid.get_timestamp().and_then(|timestamp| {
+        // 100-nanosecond intervals since the Unix epoch, negative before 1970
+        let (ticks, _) = timestamp.to_rfc4122();
+        let ticks = ticks as i64 - UUID_TICKS_BETWEEN_EPOCHS;
+
+        DateTime::from_timestamp(
+            ticks.div_euclid(10_000_000),
+            ticks.rem_euclid(10_000_000) as u32 * 100,
+        )
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    });
+
+    // Only v1 and v6 UUIDs carry a clock sequence and node id
+    let (clock_seq, node_id) = match uuid.get_version_num() {
+        1 | 6 => {
+            let bytes = uuid.as_bytes();
+            let clock_seq = u16::from_be_bytes([bytes[8], bytes[9]]) & 0x3fff;
+            let node_id = bytes[10..]
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(":");
+
+            (Some(clock_seq), Some(node_id))
+        }
+        _ => (None, None),
+    };
+
+    Ok(UuidInspection {
+        version: uuid.get_version_num(),
+        variant,
+        timestamp,
+        clock_seq,
+        node_id,
+        formats: (&uuid).into(),
+    })
+}
+
+/// Convert a UUID given in any of the `UuidFormat` formats, or `nil` or `max`
+///
+/// A v1 or v6 UUID is reordered into `version` if given. The base64 `engine` defaults to `standard`
+pub fn convert(
+    uuid: &str,
+    version: Option<UuidConvertVersion>,
+    to: UuidRepresentation,
+    engine: Option<Base64Engine>,
+) -> Result<Vec<u8>, UuidError> {
+    let mut uuid = parse_uuid(uuid)?;
+
+    if let Some(version) = version {
+        let ts = match (uuid.get_version_num(), uuid.get_timestamp()) {
+            (1 | 6, Some(ts)) => ts,
+            (v, _) => return Err(UuidError::UnsupportedConversion(v)),
+        };
+        // The node id is the last 6 bytes of both v1 and v6 UUIDs
+        // Slicing 6 bytes from 16 bytes always fits
+        let node_id: [u8; 6] = uuid.as_bytes()[10..].try_into().unwrap();
+
+        uuid = match version {
+            UuidConvertVersion::V1 => uuid::Uuid::new_v1(ts, &node_id),
+            UuidConvertVersion::V6 => uuid::Uuid::new_v6(ts, &node_id),
+        };
+    }
+
+    Ok(match to {
+        UuidRepresentation::Hyphenated => format_uuid(&uuid, UuidFormat::Hyphenated).into_bytes(),
+        UuidRepresentation::Simple => format_uuid(&uuid, UuidFormat::Simple).into_bytes(),
+        UuidRepresentation::Urn => format_uuid(&uuid, UuidFormat::Urn).into_bytes(),
+        UuidRepresentation::Braced => format_uuid(&uuid, UuidFormat::Braced).into_bytes(),
+        UuidRepresentation::Bytes => uuid.as_bytes().to_vec(),
+        UuidRepresentation::Base64 => super::base64::encode(
+            uuid.as_bytes(),
+            engine.unwrap_or(Base64Engine::Standard),
+            None,
+        )?
+        .into_bytes(),
+        UuidRepresentation::Integer => uuid.as_u128().to_string().into_bytes(),
+    })
+}
+
+/// Render a batch of UUIDs
+pub fn render(uuids: &[String], output: UuidOutput) -> String {
+    match output {
+        UuidOutput::Text => uuids.iter().map(|uuid| format!("{}\n", uuid)).collect(),
+        // Serializing strings never fails
+        UuidOutput::Json => serde_json::to_string(uuids).unwrap(),
+        UuidOutput::Ndjson => uuids
+            .iter()
+            .map(|uuid| format!("{}\n", serde_json::Value::from(uuid.as_str())))
+            .collect(),
+    }
+}
+
+fn check_count(count: usize, max_count: usize) -> Result<(), UuidError> {
+    if count == 0 || count > max_count {
+        return Err(UuidError::InvalidCount(max_count));
+    }
+
+    Ok(())
+}
+
+/// The number of 100-nanosecond intervals between the Gregorian and Unix epochs
+const UUID_TICKS_BETWEEN_EPOCHS: i64 = 0x01B2_1DD2_1381_4000;
+
+/// The last millisecond and counter of v7 UUIDs generated with `UuidV7Method::Counter`
+static V7_COUNTER: Mutex<(u64, u16)> = Mutex::new((0, 0));
+
+/// Use the given node_id or a random one with the multicast bit set, see RFC 9562 section 6.10
+fn resolve_node_id(node_id: Option<UuidNodeId>) -> Result<[u8; 6], UuidError> {
+    match node_id {
+        Some(node_id) => node_id
+            .try_into()
+            .map_err(|_| UuidError::InvalidNodeIdLength),
+        None => {
+            let mut node_id: [u8; 6] = rand::random();
+            node_id[0] |= 0x01;
+
+            Ok(node_id)
+        }
+    }
+}
+
+/// Split RFC 4122 ticks, or the current time, into Unix milliseconds and the nanoseconds within
+fn unix_millis(ticks: Option<u64>) -> (u64, u32) {
+    match ticks {
+        Some(ticks) => {
+            let ticks = ticks.saturating_sub(UUID_TICKS_BETWEEN_EPOCHS as u64);
+
+            (ticks / 10_000, (ticks % 10_000) as u32 * 100)
+        }
+        None => {
+            // The system clock is always after the Unix epoch
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+            (now.as_millis() as u64, now.subsec_nanos() % 1_000_000)
+        }
+    }
+}
+
+/// The timestamp of the `i`th v1 or v6 UUID of a batch
+///
+/// The tick is carried over when the 14-bit clock sequence overflows, as it would repeat otherwise
+fn batch_timestamp(ticks: u64, counter: u16, i: usize) -> uuid::Timestamp {
+    let counter = (counter & 0x3fff) as u64 + i as u64;
+    uuid::Timestamp::from_rfc4122(ticks.wrapping_add(counter >> 14), (counter & 0x3fff) as u16)
+}
+
+fn v7_uuids(timestamp: Option<(u64, u16)>, method: UuidV7Method, count: usize) -> Vec<uuid::Uuid> {
+    let ticks = timestamp.map(|(ticks, _)| ticks);
+
+    match method {
+        UuidV7Method::Random => (0..count)
+            .map(|_| {
+                let (millis, _) = unix_millis(ticks);
+                v7_from_parts(millis, rand::random())
+            })
+            .collect(),
+        UuidV7Method::Precision => (0..count)
+            .map(|_| {
+                let (millis, nanos) = unix_millis(ticks);
+                // Scale the fraction of the millisecond to 12 bits
+                v7_from_parts(millis, (nanos as u64 * 4096 / 1_000_000) as u16)
+            })
+            .collect(),
+        UuidV7Method::Counter => {
+            let (millis, counter) = match timestamp {
+                Some((ticks, counter)) => (unix_millis(Some(ticks)).0, counter & 0x0fff),
+                None => reserve_v7_counter(unix_millis(None).0, count),
+            };
+
+            // The millisecond is carried over when the 12-bit counter overflows
+            (0..count as u64)
+                .map(|i| {
+                    let counter = counter as u64 + i;
+                    v7_from_parts(millis + (counter >> 12), (counter & 0x0fff) as u16)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Reserve `count` counter values, keeping them monotonic across requests
+fn reserve_v7_counter(millis: u64, count: usize) -> (u64, u16) {
+    // The lock is never poisoned as nothing in it panics
+    let mut last = V7_COUNTER.lock().unwrap();
+
+    let start = if millis > last.0 {
+        (millis, 0)
+    } else {
+        let next = last.1 as u64 + 1;
+        (last.0 + (next >> 12), (next & 0x0fff) as u16)
+    };
+
+    let end = start.1 as u64 + count as u64 - 1;
+    *last = (start.0 + (end >> 12), (end & 0x0fff) as u16);
+
+    start
+}
+
+fn v7_from_parts(millis: u64, rand_a: u16) -> uuid::Uuid {
+    let mut bytes: uuid::Bytes = rand::random();
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    bytes[6..8].copy_from_slice(&(rand_a & 0x0fff).to_be_bytes());
+
+    uuid::Builder::from_bytes(bytes)
+        .with_variant(uuid::Variant::RFC4122)
+        .with_version(uuid::Version::SortRand)
+        .into_uuid()
+}
+
+/// Parse a UUID in any of the `UuidFormat` formats, or `nil` or `max`
+fn parse_uuid(uuid: &str) -> Result<uuid::Uuid, UuidError> {
+    match uuid.trim() {
+        "nil" => Ok(uuid::Uuid::nil()),
+        "max" => Ok(uuid::Uuid::from_u128(u128::MAX)),
+        uuid => Ok(uuid::Uuid::parse_str(uuid)?),
+    }
+}
+
+fn format_uuid(uuid: &uuid::Uuid, format: UuidFormat) -> String {
+    match format {
         UuidFormat::Hyphenated => format!("{}", uuid.as_hyphenated()),
         UuidFormat::Simple => format!("{}", uuid.as_simple()),
         UuidFormat::Urn => format!("{}", uuid.as_urn()),
         UuidFormat::Braced => format!("{}", uuid.as_braced()),
-    })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn batch_clock_sequence_carries_over() {
+        for new_batch in [new_v1_batch, new_v6_batch] {
+            let uuids = new_batch(
+                Some((0x1234_5678, 0x3ffe)),
+                None,
+                UuidFormat::Hyphenated,
+                0x4001,
+                0x4001,
+            )
+            .unwrap();
+            assert_eq!(uuids.iter().collect::<HashSet<_>>().len(), 0x4001);
+
+            let timestamps: Vec<_> = [0, 1, 2]
+                .map(|i| {
+                    uuid::Uuid::parse_str(&uuids[i])
+                        .unwrap()
+                        .get_timestamp()
+                        .unwrap()
+                })
+                .map(|timestamp| timestamp.to_rfc4122())
+                .to_vec();
+            assert_eq!(
+                timestamps,
+                [
+                    (0x1234_5678, 0x3ffe),
+                    (0x1234_5678, 0x3fff),
+                    (0x1234_5679, 0)
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,565 @@
+use md5::Md5;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::rewind::Rewind;
+
+/// The largest ClientHello read ahead of the handshake
+pub const MAX_CLIENT_HELLO_SIZE: usize = 65_536;
+
+const CONTENT_TYPE_HANDSHAKE: u8 = 22;
+const HANDSHAKE_CLIENT_HELLO: u8 = 1;
+
+const EXTENSION_SERVER_NAME: u16 = 0x0000;
+const EXTENSION_SUPPORTED_GROUPS: u16 = 0x000a;
+const EXTENSION_EC_POINT_FORMATS: u16 = 0x000b;
+const EXTENSION_SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const EXTENSION_ALPN: u16 = 0x0010;
+const EXTENSION_SUPPORTED_VERSIONS: u16 = 0x002b;
+
+#[derive(Error, Debug)]
+pub enum FingerprintError {
+    #[error("the ClientHello is only read from TLS connections over TCP")]
+    Unavailable,
+    #[error("the ClientHello is invalid")]
+    InvalidClientHello,
+}
+
+/// The parts of a ClientHello which tell TLS clients apart
+///
+/// The lists are in the order the client sent them, including GREASE values.
+#[derive(Debug, Clone, Default)]
+pub struct ClientHello {
+    pub version: u16,
+    pub cipher_suites: Vec<u16>,
+    pub extensions: Vec<u16>,
+    pub supported_versions: Vec<u16>,
+    pub supported_groups: Vec<u16>,
+    pub ec_point_formats: Vec<u8>,
+    pub signature_algorithms: Vec<u16>,
+    pub alpn: Vec<String>,
+    pub server_name: Option<String>,
+}
+
+/// The ClientHello of a connection and its JA3 and JA4 fingerprints
+#[derive(Serialize, Debug)]
+pub struct Fingerprint {
+    /// The versions offered in `supported_versions`, or the version of the ClientHello
+    pub tls_versions: Vec<String>,
+    pub cipher_suites: Vec<u16>,
+    pub extensions: Vec<u16>,
+    pub supported_groups: Vec<u16>,
+    pub ec_point_formats: Vec<u8>,
+    pub signature_algorithms: Vec<u16>,
+    pub alpn: Vec<String>,
+    pub sni: Option<String>,
+    pub ja3: String,
+    pub ja3_hash: String,
+    pub ja4: String,
+}
+
+/// Read the ClientHello from a connection before the TLS handshake
+///
+/// The returned connection reads the ClientHello again, so it can be handed to the TLS server.
+/// The ClientHello is `None` if the client sent something else, which the server will reject.
+pub async fn read_client_hello<S: AsyncRead + Unpin>(
+    mut io: S,
+) -> std::io::Result<(Rewind<S>, Option<ClientHello>)> {
+    let mut prefix = Vec::new();
+    let mut handshake = Vec::new();
+
+    loop {
+        let start = prefix.len();
+        prefix.resize(start + 5, 0);
+        io.read_exact(&mut prefix[start..]).await?;
+        let header = &prefix[start..];
+        let length = u16::from_be_bytes([header[3], header[4]]) as usize;
+        if header[0] != CONTENT_TYPE_HANDSHAKE || prefix.len() + length > MAX_CLIENT_HELLO_SIZE {
+            return Ok((Rewind::new(prefix, io), None));
+        }
+
+        let start = prefix.len();
+        prefix.resize(start + length, 0);
+        io.read_exact(&mut prefix[start..]).await?;
+        handshake.extend_from_slice(&prefix[start..]);
+
+        // The ClientHello may be split across records, as it is with large key shares
+        if handshake.len() >= 4 {
+            let length = u32::from_be_bytes([0, handshake[1], handshake[2], handshake[3]]);
+            if handshake.len() >= 4 + length as usize {
+                let client_hello = ClientHello::parse(&handshake).ok();
+                return Ok((Rewind::new(prefix, io), client_hello));
+            }
+        }
+    }
+}
+
+impl ClientHello {
+    /// Parse a ClientHello handshake message
+    pub fn parse(message: &[u8]) -> Result<Self, FingerprintError> {
+        let mut reader = Reader(message);
+        if reader.u8()? != HANDSHAKE_CLIENT_HELLO {
+            return Err(FingerprintError::InvalidClientHello);
+        }
+        let mut body = Reader(reader.take_u24()?);
+
+        let mut client_hello = ClientHello {
+            version: body.u16()?,
+            ..Default::default()
+        };
+        // random
+        body.take(32)?;
+        // legacy_session_id
+        body.take_u8()?;
+        client_hello.cipher_suites = Reader(body.take_u16()?).u16s()?;
+        // legacy_compression_methods
+        body.take_u8()?;
+
+        // SSL 3.0 clients may leave out the extensions
+        if body.0.is_empty() {
+            return Ok(client_hello);
+        }
+        let mut extensions = Reader(body.take_u16()?);
+        while !extensions.0.is_empty() {
+            let extension = extensions.u16()?;
+            let mut data = Reader(extensions.take_u16()?);
+            client_hello.extensions.push(extension);
+
+            match extension {
+                EXTENSION_SERVER_NAME => {
+                    let mut names = Reader(data.take_u16()?);
+                    while !names.0.is_empty() {
+                        let name_type = names.u8()?;
+                        let name = names.take_u16()?;
+                        // host_name
+                        if name_type == 0 {
+                            client_hello.server_name =
+                                Some(String::from_utf8_lossy(name).into_owned());
+                        }
+                    }
+                }
+                EXTENSION_SUPPORTED_GROUPS => {
+                    client_hello.supported_groups = Reader(data.take_u16()?).u16s()?;
+                }
+                EXTENSION_EC_POINT_FORMATS => {
+                    client_hello.ec_point_formats = data.take_u8()?.to_vec();
+                }
+                EXTENSION_SIGNATURE_ALGORITHMS => {
+                    client_hello.signature_algorithms = Reader(data.take_u16()?).u16s()?;
+                }
+                EXTENSION_ALPN => {
+                    let mut protocols = Reader(data.take_u16()?);
+                    while !protocols.0.is_empty() {
+                        let protocol = protocols.take_u8()?;
+                        client_hello
+                            .alpn
+                            .push(String::from_utf8_lossy(protocol).into_owned());
+                    }
+                }
+                EXTENSION_SUPPORTED_VERSIONS => {
+                    client_hello.supported_versions = Reader(data.take_u8()?).u16s()?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(client_hello)
+    }
+
+    /// The JA3 string, with GREASE values left out
+    pub fn ja3(&self) -> String {
+        fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+            values
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join("-")
+        }
+
+        format!(
+            "{},{},{},{},{}",
+            self.version,
+            join(self.cipher_suites.iter().filter(|&&v| !is_grease(v))),
+            join(self.extensions.iter().filter(|&&v| !is_grease(v))),
+            join(self.supported_groups.iter().filter(|&&v| !is_grease(v))),
+            join(self.ec_point_formats.iter()),
+        )
+    }
+
+    /// The JA4 fingerprint of the ClientHello, as received over TCP
+    pub fn ja4(&self) -> String {
+        let cipher_suites: Vec<_> = self
+            .cipher_suites
+            .iter()
+            .copied()
+            .filter(|&v| !is_grease(v))
+            .collect();
+        let extensions: Vec<_> = self
+            .extensions
+            .iter()
+            .copied()
+            .filter(|&v| !is_grease(v))
+            .collect();
+
+        let version = self
+            .supported_versions
+            .iter()
+            .copied()
+            .filter(|&v| !is_grease(v))
+            .max()
+            .unwrap_or(self.version);
+        let version = match version {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            0x0002 => "s2",
+            _ => "00",
+        };
+        let sni = if self.server_name.is_some() { 'd' } else { 'i' };
+        let alpn = match self.alpn.first().map(String::as_bytes) {
+            Some(protocol) if !protocol.is_empty() => {
+                let (first, last) = (protocol[0], protocol[protocol.len() - 1]);
+                if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() {
+                    format!("{}{}", first as char, last as char)
+                } else {
+                    // The first and last characters of the hex of the protocol instead
+                    let (first, last) = (format!("{first:02x}"), format!("{last:02x}"));
+                    format!("{}{}", &first[..1], &last[1..])
+                }
+            }
+            _ => "00".to_string(),
+        };
+
+        let mut sorted_cipher_suites: Vec<_> = cipher_suites.iter().map(|v| hex(*v)).collect();
+        sorted_cipher_suites.sort();
+        // SNI and ALPN are left out, as they are already part of the first section
+        let mut sorted_extensions: Vec<_> = extensions
+            .iter()
+            .filter(|&&v| v != EXTENSION_SERVER_NAME && v != EXTENSION_ALPN)
+            .map(|v| hex(*v))
+            .collect();
+        sorted_extensions.sort();
+        let mut extensions_section = sorted_extensions.join(",");
+        let signature_algorithms: Vec<_> = self
+            .signature_algorithms
+            .iter()
+            .filter(|&&v| !is_grease(v))
+            .map(|v| hex(*v))
+            .collect();
+        if !signature_algorithms.is_empty() {
+            extensions_section = format!("{extensions_section}_{}", signature_algorithms.join(","));
+        }
+
+        format!(
+            "t{version}{sni}{:02}{:02}{alpn}_{}_{}",
+            cipher_suites.len().min(99),
+            extensions.len().min(99),
+            truncated_sha256(&sorted_cipher_suites.join(","), cipher_suites.is_empty()),
+            truncated_sha256(&extensions_section, extensions.is_empty()),
+        )
+    }
+
+    /// Describe the ClientHello with its fingerprints
+    pub fn fingerprint(&self) -> Fingerprint {
+        let ja3 = self.ja3();
+        let versions = if self.supported_versions.is_empty() {
+            vec![self.version]
+        } else {
+            self.supported_versions.clone()
+        };
+
+        Fingerprint {
+            tls_versions: versions
+                .into_iter()
+                .filter(|&v| !is_grease(v))
+                .map(version_name)
+                .collect(),
+            cipher_suites: self.cipher_suites.clone(),
+            extensions: self.extensions.clone(),
+            supported_groups: self.supported_groups.clone(),
+            ec_point_formats: self.ec_point_formats.clone(),
+            signature_algorithms: self.signature_algorithms.clone(),
+            alpn: self.alpn.clone(),
+            sni: self.server_name.clone(),
+            ja3_hash: hex::encode(Md5::digest(&ja3)),
+            ja3,
+            ja4: self.ja4(),
+        }
+    }
+}
+
+/// GREASE values (RFC 8701) are random, so they are not part of a fingerprint
+fn is_grease(value: u16) -> bool {
+    value & 0x0f0f == 0x0a0a && value >> 8 == value & 0xff
+}
+
+fn version_name(version: u16) -> String {
+    match version {
+        0x0304 => "TLSv1.3".to_string(),
+        0x0303 => "TLSv1.2".to_string(),
+        0x0302 => "TLSv1.1".to_string(),
+        0x0301 => "TLSv1.0".to_string(),
+        0x0300 => "SSLv3".to_string(),
+        _ => format!("0x{version:04x}"),
+    }
+}
+
+fn hex(value: u16) -> String {
+    format!("{value:04x}")
+}
+
+fn truncated_sha256(value: &str, empty: bool) -> String {
+    if empty {
+        return "000000000000".to_string();
+    }
+    hex::encode(Sha256::digest(value))[..12].to_string()
+}
+
+/// Reads the big endian, length prefixed fields of a handshake message
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], FingerprintError> {
+        if self.0.len() < length {
+            return Err(FingerprintError::InvalidClientHello);
+        }
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, FingerprintError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, FingerprintError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u16s(mut self) -> Result<Vec<u16>, FingerprintError> {
+        let mut values = Vec::with_capacity(self.0.len() / 2);
+        while !self.0.is_empty() {
+            values.push(self.u16()?);
+        }
+        Ok(values)
+    }
+
+    fn take_u8(&mut self) -> Result<&'a [u8], FingerprintError> {
+        let length = self.u8()? as usize;
+        self.take(length)
+    }
+
+    fn take_u16(&mut self) -> Result<&'a [u8], FingerprintError> {
+        let length = self.u16()? as usize;
+        self.take(length)
+    }
+
+    fn take_u24(&mut self) -> Result<&'a [u8], FingerprintError> {
+        let bytes = self.take(3)?;
+        let length = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize;
+        self.take(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ClientHello record of "The Illustrated TLS 1.3 Connection" (tls13.xargs.org)
+    const CLIENT_HELLO_RECORD: [u8; 253] = [
+        0x16, 0x03, 0x01, 0x00, 0xf8, 0x01, 0x00, 0x00, 0xf4, 0x03, 0x03, 0x00, 0x01, 0x02, 0x03,
+        0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12,
+        0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0xe0,
+        0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef,
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe,
+        0xff, 0x00, 0x08, 0x13, 0x02, 0x13, 0x03, 0x13, 0x01, 0x00, 0xff, 0x01, 0x00, 0x00, 0xa3,
+        0x00, 0x00, 0x00, 0x18, 0x00, 0x16, 0x00, 0x00, 0x13, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c,
+        0x65, 0x2e, 0x75, 0x6c, 0x66, 0x68, 0x65, 0x69, 0x6d, 0x2e, 0x6e, 0x65, 0x74, 0x00, 0x0b,
+        0x00, 0x04, 0x03, 0x00, 0x01, 0x02, 0x00, 0x0a, 0x00, 0x16, 0x00, 0x14, 0x00, 0x1d, 0x00,
+        0x17, 0x00, 0x1e, 0x00, 0x19, 0x00, 0x18, 0x01, 0x00, 0x01, 0x01, 0x01, 0x02, 0x01, 0x03,
+        0x01, 0x04, 0x00, 0x23, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x17, 0x00, 0x00, 0x00,
+        0x0d, 0x00, 0x1e, 0x00, 0x1c, 0x04, 0x03, 0x05, 0x03, 0x06, 0x03, 0x08, 0x07, 0x08, 0x08,
+        0x08, 0x09, 0x08, 0x0a, 0x08, 0x0b, 0x08, 0x04, 0x08, 0x05, 0x08, 0x06, 0x04, 0x01, 0x05,
+        0x01, 0x06, 0x01, 0x00, 0x2b, 0x00, 0x03, 0x02, 0x03, 0x04, 0x00, 0x2d, 0x00, 0x02, 0x01,
+        0x01, 0x00, 0x33, 0x00, 0x26, 0x00, 0x24, 0x00, 0x1d, 0x00, 0x20, 0x35, 0x80, 0x72, 0xd6,
+        0x36, 0x58, 0x80, 0xd1, 0xae, 0xea, 0x32, 0x9a, 0xdf, 0x91, 0x21, 0x38, 0x38, 0x51, 0xed,
+        0x21, 0xa2, 0x8e, 0x3b, 0x75, 0xe9, 0x65, 0xd0, 0xd2, 0xcd, 0x16, 0x62, 0x54,
+    ];
+
+    fn message() -> &'static [u8] {
+        &CLIENT_HELLO_RECORD[5..]
+    }
+
+    #[test]
+    fn parse_capture() {
+        let client_hello = ClientHello::parse(message()).unwrap();
+        assert_eq!(client_hello.version, 0x0303);
+        assert_eq!(client_hello.cipher_suites, [0x1302, 0x1303, 0x1301, 0x00ff]);
+        assert_eq!(
+            client_hello.extensions,
+            [0x0000, 0x000b, 0x000a, 0x0023, 0x0016, 0x0017, 0x000d, 0x002b, 0x002d, 0x0033]
+        );
+        assert_eq!(client_hello.supported_versions, [0x0304]);
+        assert_eq!(
+            client_hello.supported_groups,
+            [0x001d, 0x0017, 0x001e, 0x0019, 0x0018, 0x0100, 0x0101, 0x0102, 0x0103, 0x0104]
+        );
+        assert_eq!(client_hello.ec_point_formats, [0, 1, 2]);
+        assert_eq!(client_hello.signature_algorithms.len(), 14);
+        assert!(client_hello.alpn.is_empty());
+        assert_eq!(
+            client_hello.server_name.as_deref(),
+            Some("example.ulfheim.net")
+        );
+
+        let fingerprint = client_hello.fingerprint();
+        assert_eq!(fingerprint.tls_versions, ["TLSv1.3"]);
+        assert_eq!(
+            fingerprint.ja3,
+            "771,4866-4867-4865-255,0-11-10-35-22-23-13-43-45-51,\
+             29-23-30-25-24-256-257-258-259-260,0-1-2"
+        );
+        assert_eq!(fingerprint.ja3_hash, "f146948b4a599d4d7ddf071b74696983");
+        assert_eq!(fingerprint.ja4, "t13d041000_16476d049b0b_78f1d400d464");
+    }
+
+    #[test]
+    fn ja3_example() {
+        // The example of the JA3 README
+        let client_hello = ClientHello {
+            version: 769,
+            cipher_suites: vec![47, 53, 5, 10, 49161, 49162, 49171, 49172, 50, 56, 19, 4],
+            extensions: vec![0, 10, 11],
+            supported_groups: vec![23, 24, 25],
+            ec_point_formats: vec![0],
+            ..Default::default()
+        };
+        let fingerprint = client_hello.fingerprint();
+        assert_eq!(
+            fingerprint.ja3,
+            "769,47-53-5-10-49161-49162-49171-49172-50-56-19-4,0-10-11,23-24-25,0"
+        );
+        assert_eq!(fingerprint.ja3_hash, "ada70206e40642a3e4461f35503241d5");
+        assert_eq!(fingerprint.tls_versions, ["TLSv1.0"]);
+    }
+
+    #[test]
+    fn ja4_example() {
+        // The Chrome example of the JA4 README, with GREASE values which are left out
+        let client_hello = ClientHello {
+            version: 0x0303,
+            cipher_suites: vec![
+                0x1a1a, 0x1301, 0x1302, 0x1303, 0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8,
+                0xc013, 0xc014, 0x009c, 0x009d, 0x002f, 0x0035,
+            ],
+            extensions: vec![
+                0x2a2a, 0x0000, 0x0017, 0xff01, 0x000a, 0x000b, 0x0023, 0x0010, 0x0005, 0x000d,
+                0x0012, 0x0033, 0x002d, 0x002b, 0x001b, 0x4469, 0x0015, 0x3a3a,
+            ],
+            supported_versions: vec![0x7a7a, 0x0304, 0x0303],
+            signature_algorithms: vec![
+                0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601, 0x4a4a,
+            ],
+            alpn: vec!["h2".to_string(), "http/1.1".to_string()],
+            server_name: Some("example.com".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(client_hello.ja4(), "t13d1516h2_8daaf6152771_e5627efa2ab1");
+    }
+
+    #[test]
+    fn grease() {
+        assert!(is_grease(0x0a0a));
+        assert!(is_grease(0xfafa));
+        assert!(!is_grease(0x0a1a));
+        assert!(!is_grease(0x1301));
+    }
+
+    #[test]
+    fn truncated_client_hello() {
+        for len in 0..message().len() {
+            assert!(ClientHello::parse(&message()[..len]).is_err(), "{len}");
+        }
+    }
+
+    #[test]
+    fn malformed_client_hello() {
+        // A ServerHello
+        let mut message = message().to_vec();
+        message[0] = 2;
+        assert!(ClientHello::parse(&message).is_err());
+
+        // An extension longer than the extensions
+        let mut message = self::message().to_vec();
+        let sni = 4 + 2 + 32 + 1 + 32 + 2 + 8 + 2 + 2 + 2;
+        message[sni + 1] = 0xff;
+        assert!(ClientHello::parse(&message).is_err());
+
+        // A cipher suite cut in half, with the handshake length following the shorter body
+        let mut message = self::message().to_vec();
+        let cipher_suites = 4 + 2 + 32 + 1 + 32;
+        message[cipher_suites + 1] = 7;
+        message.remove(cipher_suites + 2 + 7);
+        message[3] -= 1;
+        assert!(ClientHello::parse(&message).is_err());
+    }
+
+    #[tokio::test]
+    async fn read_record() {
+        let (mut rewind, client_hello) = read_client_hello(&CLIENT_HELLO_RECORD[..]).await.unwrap();
+        assert!(client_hello.is_some());
+
+        // The whole record is read again by the TLS server
+        let mut replayed = Vec::new();
+        rewind.read_to_end(&mut replayed).await.unwrap();
+        assert_eq!(replayed, CLIENT_HELLO_RECORD);
+    }
+
+    #[tokio::test]
+    async fn read_fragmented_record() {
+        let (first, second) = message().split_at(100);
+        let mut records = Vec::new();
+        for fragment in [first, second] {
+            records.extend_from_slice(&[0x16, 0x03, 0x01]);
+            records.extend_from_slice(&(fragment.len() as u16).to_be_bytes());
+            records.extend_from_slice(fragment);
+        }
+
+        let (_, client_hello) = read_client_hello(&records[..]).await.unwrap();
+        assert_eq!(
+            client_hello.unwrap().server_name.as_deref(),
+            Some("example.ulfheim.net")
+        );
+    }
+
+    #[tokio::test]
+    async fn read_truncated_record() {
+        for len in [0, 3, 5, 100, CLIENT_HELLO_RECORD.len() - 1] {
+            let error = read_client_hello(&CLIENT_HELLO_RECORD[..len])
+                .await
+                .unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[tokio::test]
+    async fn read_malformed_record() {
+        // Plain HTTP is handed back as it is
+        let request = b"GET / HTTP/1.1\r\n\r\n";
+        let (mut rewind, client_hello) = read_client_hello(&request[..]).await.unwrap();
+        assert!(client_hello.is_none());
+        let mut replayed = Vec::new();
+        rewind.read_to_end(&mut replayed).await.unwrap();
+        assert_eq!(replayed, request);
+
+        // A complete handshake message which is not a ClientHello
+        let mut record = CLIENT_HELLO_RECORD;
+        record[5] = 2;
+        let (_, client_hello) = read_client_hello(&record[..]).await.unwrap();
+        assert!(client_hello.is_none());
+    }
+}
@@ -0,0 +1,548 @@
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+
+use crate::rewind::Rewind;
+
+/// The largest request body replayed after an upgrade, which fits the initial flow control window
+pub const MAX_BODY_SIZE: usize = 65_535;
+
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// The default `SETTINGS_MAX_FRAME_SIZE`
+const MAX_FRAME_SIZE: usize = 16_384;
+
+const FRAME_DATA: u8 = 0x0;
+const FRAME_HEADERS: u8 = 0x1;
+const FRAME_SETTINGS: u8 = 0x4;
+const FRAME_CONTINUATION: u8 = 0x9;
+
+const FLAG_END_STREAM: u8 = 0x1;
+const FLAG_END_HEADERS: u8 = 0x4;
+
+/// Headers which only apply to the HTTP/1.1 connection
+const CONNECTION_HEADERS: [&str; 7] = [
+    "connection",
+    "host",
+    "http2-settings",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// An accepted `Upgrade: h2c` request
+///
+/// The request is answered on stream 1 of the new HTTP/2 connection, so it is replayed to the
+/// HTTP/2 server as if the client had sent it there.
+#[derive(Debug)]
+pub struct Upgrade {
+    header_block: Vec<u8>,
+    content_length: usize,
+}
+
+impl Upgrade {
+    /// Accept an HTTP/1.1 request asking to upgrade to h2c
+    ///
+    /// Returns `None` if the request does not ask for it, or carries a body that cannot be
+    /// replayed, in which case it is served over HTTP/1.1 as the client must expect.
+    pub fn new<'a>(
+        method: &str,
+        path: &str,
+        headers: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+    ) -> Option<Self> {
+        if method.eq_ignore_ascii_case("CONNECT") {
+            return None;
+        }
+
+        let headers: Vec<_> = headers
+            .into_iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value))
+            .collect();
+        let value = |name: &str| {
+            headers
+                .iter()
+                .find(|(n, _)| n == name)
+                .and_then(|(_, value)| std::str::from_utf8(value).ok())
+        };
+        let has_token = |name: &str, token: &str| {
+            headers
+                .iter()
+                .filter(|(n, _)| n == name)
+                .filter_map(|(_, value)| std::str::from_utf8(value).ok())
+                .flat_map(|value| value.split(','))
+                .any(|t| t.trim().eq_ignore_ascii_case(token))
+        };
+
+        if !has_token("upgrade", "h2c")
+            || !has_token("connection", "upgrade")
+            || !has_token("connection", "http2-settings")
+            || headers
+                .iter()
+                .filter(|(n, _)| n == "http2-settings")
+                .count()
+                != 1
+            || value("transfer-encoding").is_some()
+        {
+            return None;
+        }
+        let content_length = match value("content-length") {
+            None => 0,
+            Some(length) => length.trim().parse().ok()?,
+        };
+        if content_length > MAX_BODY_SIZE {
+            return None;
+        }
+        let authority = value("host")?;
+
+        let mut header_block = Vec::new();
+        encode_header(&mut header_block, b":method", method.as_bytes());
+        encode_header(&mut header_block, b":scheme", b"http");
+        encode_header(&mut header_block, b":authority", authority.as_bytes());
+        encode_header(&mut header_block, b":path", path.as_bytes());
+        for (name, value) in &headers {
+            let hop_by_hop = CONNECTION_HEADERS.contains(&name.as_str())
+                || (name == "te" && !value.eq_ignore_ascii_case(b"trailers"))
+                || has_token("connection", name);
+            if !hop_by_hop {
+                encode_header(&mut header_block, name.as_bytes(), value);
+            }
+        }
+
+        Some(Self {
+            header_block,
+            content_length,
+        })
+    }
+
+    /// The length of the body to read before switching protocols
+    pub fn content_length(&self) -> usize {
+        self.content_length
+    }
+
+    /// The headers of the `101 Switching Protocols` response
+    pub fn headers(&self) -> [(&'static str, &'static str); 2] {
+        [("Connection", "Upgrade"), ("Upgrade", "h2c")]
+    }
+
+    /// Read the client preface from the upgraded connection and replay the request after it
+    ///
+    /// The returned connection should be served by an HTTP/2 server.
+    pub async fn accept<S: AsyncRead + AsyncWrite + Unpin>(
+        self,
+        mut io: S,
+        body: &[u8],
+    ) -> io::Result<Rewind<S>> {
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut prefix = vec![0; PREFACE.len() + 9];
+        io.read_exact(&mut prefix).await?;
+        if &prefix[..PREFACE.len()] != PREFACE {
+            return Err(invalid("the client preface is invalid"));
+        }
+        let settings = &prefix[PREFACE.len()..];
+        let length = u32::from_be_bytes([0, settings[0], settings[1], settings[2]]) as usize;
+        if settings[3] != FRAME_SETTINGS || length > MAX_FRAME_SIZE {
+            return Err(invalid("the client preface must end with a SETTINGS frame"));
+        }
+        let start = prefix.len();
+        prefix.resize(start + length, 0);
+        io.read_exact(&mut prefix[start..]).await?;
+
+        let body = &body[..body.len().min(self.content_length)];
+        let mut blocks = self.header_block.chunks(MAX_FRAME_SIZE).peekable();
+        let mut frame_type = FRAME_HEADERS;
+        let mut flags = if body.is_empty() { FLAG_END_STREAM } else { 0 };
+        while let Some(block) = blocks.next() {
+            if blocks.peek().is_none() {
+                flags |= FLAG_END_HEADERS;
+            }
+            encode_frame(&mut prefix, frame_type, flags, block);
+            frame_type = FRAME_CONTINUATION;
+            flags = 0;
+        }
+        let mut chunks = body.chunks(MAX_FRAME_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            let flags = if chunks.peek().is_none() {
+                FLAG_END_STREAM
+            } else {
+                0
+            };
+            encode_frame(&mut prefix, FRAME_DATA, flags, chunk);
+        }
+
+        Ok(Rewind::new(prefix, io))
+    }
+}
+
+/// Answer a request asking to upgrade to h2c, serving the HTTP/2 connection it switches to with
+/// `service`
+///
+/// Returns the request if it does not ask for it, to be served over HTTP/1.1 as usual. The
+/// HTTP/1.1 connection must be served with upgrades.
+#[cfg(feature = "hyper")]
+pub async fn upgrade<S, B>(
+    mut req: hyper::Request<hyper::Body>,
+    service: S,
+) -> Result<hyper::Response<hyper::Body>, hyper::Request<hyper::Body>>
+where
+    S: hyper::service::Service<hyper::Request<hyper::Body>, Response = hyper::Response<B>>
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    B: hyper::body::HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let path = req.uri().path_and_query().map_or("/", |path| path.as_str());
+    let upgrade = match req.version() {
+        hyper::Version::HTTP_11 => Upgrade::new(
+            req.method().as_str(),
+            path,
+            req.headers()
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+        ),
+        _ => None,
+    };
+    let Some(upgrade) = upgrade else {
+        return Err(req);
+    };
+
+    let on_upgrade = hyper::upgrade::on(&mut req);
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) => body,
+        Err(e) => {
+            let mut res = hyper::Response::new(hyper::Body::from(e.to_string()));
+            *res.status_mut() = hyper::StatusCode::BAD_REQUEST;
+            return Ok(res);
+        }
+    };
+
+    let mut res = hyper::Response::new(hyper::Body::empty());
+    *res.status_mut() = hyper::StatusCode::SWITCHING_PROTOCOLS;
+    for (name, value) in upgrade.headers() {
+        res.headers_mut()
+            .insert(name, hyper::header::HeaderValue::from_static(value));
+    }
+
+    tokio::spawn(async move {
+        if let Ok(upgraded) = on_upgrade.await {
+            if let Ok(io) = upgrade.accept(upgraded, &body).await {
+                let _ = hyper::server::conn::Http::new()
+                    .http2_only(true)
+                    .serve_connection(io, service)
+                    .await;
+            }
+        }
+    });
+
+    Ok(res)
+}
+
+fn encode_frame(buf: &mut Vec<u8>, frame_type: u8, flags: u8, payload: &[u8]) {
+    buf.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    buf.extend_from_slice(&[frame_type, flags]);
+    // Always stream 1, which the upgrade request is assigned to
+    buf.extend_from_slice(&1u32.to_be_bytes());
+    buf.extend_from_slice(payload);
+}
+
+/// Encode a header as an HPACK literal without indexing, so no dynamic table state is shared
+fn encode_header(buf: &mut Vec<u8>, name: &[u8], value: &[u8]) {
+    buf.push(0);
+    encode_string(buf, name);
+    encode_string(buf, value);
+}
+
+/// Encode an HPACK string literal without Huffman coding
+fn encode_string(buf: &mut Vec<u8>, string: &[u8]) {
+    let mut length = string.len();
+    if length < 0x7f {
+        buf.push(length as u8);
+    } else {
+        buf.push(0x7f);
+        length -= 0x7f;
+        while length >= 0x80 {
+            buf.push((length & 0x7f) as u8 | 0x80);
+            length >>= 7;
+        }
+        buf.push(length as u8);
+    }
+    buf.extend_from_slice(string);
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, AsyncWriteExt};
+
+    use super::*;
+
+    const SETTINGS: [u8; 9] = [0, 0, 0, FRAME_SETTINGS, 0, 0, 0, 0, 0];
+
+    fn upgrade(method: &str, headers: &[(&str, &str)]) -> Option<Upgrade> {
+        Upgrade::new(
+            method,
+            "/anything?a=1",
+            headers
+                .iter()
+                .map(|(name, value)| (*name, value.as_bytes())),
+        )
+    }
+
+    fn request_headers<'a>(extra: &[(&'a str, &'a str)]) -> Vec<(&'a str, &'a str)> {
+        let mut headers = vec![
+            ("Host", "example.com"),
+            ("Connection", "Upgrade, HTTP2-Settings"),
+            ("Upgrade", "h2c"),
+            ("HTTP2-Settings", "AAMAAABkAARAAAAAAAIAAAAA"),
+        ];
+        headers.extend_from_slice(extra);
+        headers
+    }
+
+    /// Decode a header block of literals without indexing and without Huffman coding, the only
+    /// representation the replay uses
+    fn decode_headers(mut block: &[u8]) -> Vec<(String, String)> {
+        fn string(block: &mut &[u8]) -> String {
+            assert_eq!(block[0] & 0x80, 0, "Huffman coded");
+            let mut length = (block[0] & 0x7f) as usize;
+            let mut read = 1;
+            if length == 0x7f {
+                let mut shift = 0;
+                loop {
+                    let byte = block[read];
+                    read += 1;
+                    length += ((byte & 0x7f) as usize) << shift;
+                    shift += 7;
+                    if byte & 0x80 == 0 {
+                        break;
+                    }
+                }
+            }
+            let string = String::from_utf8(block[read..read + length].to_vec()).unwrap();
+            *block = &block[read + length..];
+            string
+        }
+
+        let mut headers = Vec::new();
+        while !block.is_empty() {
+            assert_eq!(
+                block[0], 0,
+                "not a literal without indexing with a new name"
+            );
+            block = &block[1..];
+            let name = string(&mut block);
+            let value = string(&mut block);
+            headers.push((name, value));
+        }
+        headers
+    }
+
+    /// Split the replayed bytes after the client preface into (type, flags, stream, payload)
+    fn decode_frames(mut bytes: &[u8]) -> Vec<(u8, u8, u32, Vec<u8>)> {
+        let mut frames = Vec::new();
+        while !bytes.is_empty() {
+            let length = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize;
+            let stream = u32::from_be_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]);
+            frames.push((bytes[3], bytes[4], stream, bytes[9..9 + length].to_vec()));
+            bytes = &bytes[9 + length..];
+        }
+        frames
+    }
+
+    /// Send `client` on an upgraded connection and read back what the HTTP/2 server would see
+    async fn replay(upgrade: Upgrade, body: &[u8], client: &[u8]) -> io::Result<Vec<u8>> {
+        let (mut peer, io) = duplex(1 << 20);
+        peer.write_all(client).await?;
+        drop(peer);
+
+        let mut replayed = Vec::new();
+        upgrade
+            .accept(io, body)
+            .await?
+            .read_to_end(&mut replayed)
+            .await?;
+        Ok(replayed)
+    }
+
+    fn preface() -> Vec<u8> {
+        [PREFACE, &SETTINGS].concat()
+    }
+
+    #[test]
+    fn literal_without_indexing() {
+        // The literal of RFC 7541, section C.2.1, without indexing instead of with it
+        let mut buf = Vec::new();
+        encode_header(&mut buf, b"custom-key", b"custom-header");
+        assert_eq!(
+            buf,
+            [&[0x00, 0x0a][..], b"custom-key", &[0x0d], b"custom-header"].concat()
+        );
+    }
+
+    #[test]
+    fn string_lengths() {
+        for (length, prefix) in [
+            (0x7e, &[0x7e][..]),
+            (0x7f, &[0x7f, 0x00]),
+            (0xff, &[0x7f, 0x80, 0x01]),
+            // 1337 - 127 = 1210, as in the integer example of RFC 7541, section C.1.2
+            (1337, &[0x7f, 0xba, 0x09]),
+        ] {
+            let mut buf = Vec::new();
+            encode_string(&mut buf, &vec![b'a'; length]);
+            assert_eq!(&buf[..prefix.len()], prefix);
+            assert_eq!(buf.len(), prefix.len() + length);
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_request() {
+        let headers = request_headers(&[
+            ("Content-Length", "5"),
+            ("Keep-Alive", "timeout=5"),
+            ("TE", "trailers"),
+            ("Accept", "*/*"),
+        ]);
+        let upgrade = upgrade("POST", &headers).unwrap();
+        assert_eq!(upgrade.content_length(), 5);
+
+        // Only the declared length of the body is replayed, followed by what the client sent next
+        let client = [preface(), vec![0xab]].concat();
+        let replayed = replay(upgrade, b"Hello, world", &client).await.unwrap();
+        assert_eq!(&replayed[..PREFACE.len() + SETTINGS.len()], &preface()[..]);
+        assert_eq!(replayed.last(), Some(&0xab));
+
+        let replayed = &replayed[PREFACE.len() + SETTINGS.len()..replayed.len() - 1];
+        let frames = decode_frames(replayed);
+        assert_eq!(frames.len(), 2);
+        let (frame_type, flags, stream, block) = &frames[0];
+        assert_eq!(
+            (*frame_type, *flags, *stream),
+            (FRAME_HEADERS, FLAG_END_HEADERS, 1)
+        );
+        let pairs = [
+            (":method", "POST"),
+            (":scheme", "http"),
+            (":authority", "example.com"),
+            (":path", "/anything?a=1"),
+            ("content-length", "5"),
+            ("te", "trailers"),
+            ("accept", "*/*"),
+        ];
+        assert_eq!(
+            decode_headers(block),
+            pairs.map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+        assert_eq!(
+            frames[1],
+            (FRAME_DATA, FLAG_END_STREAM, 1, b"Hello".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_large_header_block() {
+        let cookie = "a".repeat(MAX_FRAME_SIZE * 2);
+        let headers = request_headers(&[("Cookie", &cookie)]);
+        let upgrade = upgrade("GET", &headers).unwrap();
+
+        let replayed = replay(upgrade, b"", &preface()).await.unwrap();
+        let frames = decode_frames(&replayed[PREFACE.len() + SETTINGS.len()..]);
+        let types: Vec<_> = frames.iter().map(|(t, flags, _, _)| (*t, *flags)).collect();
+        assert_eq!(
+            types,
+            [
+                (FRAME_HEADERS, FLAG_END_STREAM),
+                (FRAME_CONTINUATION, 0),
+                (FRAME_CONTINUATION, FLAG_END_HEADERS),
+            ]
+        );
+        let block: Vec<u8> = frames.into_iter().flat_map(|(_, _, _, p)| p).collect();
+        assert!(decode_headers(&block).contains(&("cookie".to_string(), cookie)));
+    }
+
+    #[test]
+    fn rejected_upgrades() {
+        assert!(upgrade("GET", &request_headers(&[])).is_some());
+        assert!(upgrade("CONNECT", &request_headers(&[])).is_none());
+
+        let rejected: &[&[(&str, &str)]] = &[
+            // No HTTP2-Settings, or two of them
+            &[
+                ("Host", "example.com"),
+                ("Connection", "Upgrade, HTTP2-Settings"),
+                ("Upgrade", "h2c"),
+            ],
+            &[
+                ("Host", "example.com"),
+                ("Connection", "Upgrade, HTTP2-Settings"),
+                ("Upgrade", "h2c"),
+                ("HTTP2-Settings", ""),
+                ("HTTP2-Settings", ""),
+            ],
+            // HTTP2-Settings is not a connection option
+            &[
+                ("Host", "example.com"),
+                ("Connection", "Upgrade"),
+                ("Upgrade", "h2c"),
+                ("HTTP2-Settings", ""),
+            ],
+            // An upgrade to TLS
+            &[
+                ("Host", "example.com"),
+                ("Connection", "Upgrade, HTTP2-Settings"),
+                ("Upgrade", "h2"),
+                ("HTTP2-Settings", ""),
+            ],
+            // No Host
+            &[
+                ("Connection", "Upgrade, HTTP2-Settings"),
+                ("Upgrade", "h2c"),
+                ("HTTP2-Settings", ""),
+            ],
+        ];
+        for headers in rejected {
+            assert!(upgrade("GET", headers).is_none(), "{headers:?}");
+        }
+
+        let too_large = (MAX_BODY_SIZE + 1).to_string();
+        for extra in [
+            ("Transfer-Encoding", "chunked"),
+            ("Content-Length", "five"),
+            ("Content-Length", too_large.as_str()),
+        ] {
+            assert!(upgrade("POST", &request_headers(&[extra])).is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn truncated_preface() {
+        let preface = preface();
+        // Cut in the client preface, the SETTINGS frame header and the SETTINGS payload
+        let settings = [
+            PREFACE,
+            &[0, 0, 6, FRAME_SETTINGS, 0, 0, 0, 0, 0],
+            &[0, 3, 0],
+        ]
+        .concat();
+        for client in [&preface[..10], &preface[..preface.len() - 1], &settings[..]] {
+            let upgrade = upgrade("GET", &request_headers(&[])).unwrap();
+            let error = replay(upgrade, b"", client).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_preface() {
+        let http1 = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n".repeat(2);
+        let not_settings = [PREFACE, &[0, 0, 0, FRAME_HEADERS, 0, 0, 0, 0, 0]].concat();
+        let too_large = [PREFACE, &[0, 0x40, 0x01, FRAME_SETTINGS, 0, 0, 0, 0, 0]].concat();
+        for client in [&http1[..], &not_settings[..], &too_large[..]] {
+            let upgrade = upgrade("GET", &request_headers(&[])).unwrap();
+            let error = replay(upgrade, b"", client).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
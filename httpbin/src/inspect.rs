@@ -0,0 +1,158 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MimeCategory {
+    App,
+    Archive,
+    Audio,
+    Book,
+    Doc,
+    Font,
+    Image,
+    Text,
+    Video,
+    Custom,
+}
+
+impl From<infer::MatcherType> for MimeCategory {
+    fn from(matcher: infer::MatcherType) -> Self {
+        match matcher {
+            infer::MatcherType::App => MimeCategory::App,
+            infer::MatcherType::Archive => MimeCategory::Archive,
+            infer::MatcherType::Audio => MimeCategory::Audio,
+            infer::MatcherType::Book => MimeCategory::Book,
+            infer::MatcherType::Doc => MimeCategory::Doc,
+            infer::MatcherType::Font => MimeCategory::Font,
+            infer::MatcherType::Image => MimeCategory::Image,
+            infer::MatcherType::Text => MimeCategory::Text,
+            infer::MatcherType::Video => MimeCategory::Video,
+            infer::MatcherType::Custom => MimeCategory::Custom,
+        }
+    }
+}
+
+/// The type of some data sniffed from its content
+#[derive(Serialize, Debug)]
+pub struct MimeInspection {
+    /// The sniffed MIME type, `text/plain` for unrecognized UTF-8 and
+    /// `application/octet-stream` for other unrecognized data
+    pub mime_type: &'static str,
+    /// The usual file extension of the sniffed type, if recognized
+    pub extension: Option<&'static str>,
+    /// The category of the sniffed type, if recognized
+    pub category: Option<MimeCategory>,
+    /// Whether the data is valid UTF-8
+    pub utf8: bool,
+    /// The declared MIME type without parameters, if any
+    pub declared: Option<String>,
+    /// Whether the declared MIME type differs from the sniffed one, if declared,
+    /// where any textual type agrees with unrecognized UTF-8
+    pub mismatch: Option<bool>,
+}
+
+/// Sniff the type of data with [infer](https://crates.io/crates/infer) and
+/// compare it to the declared `Content-Type`
+pub fn mime(data: &[u8], content_type: Option<&str>) -> MimeInspection {
+    let utf8 = std::str::from_utf8(data).is_ok();
+    let (mime_type, extension, category) = match infer::get(data) {
+        Some(kind) => (
+            kind.mime_type(),
+            Some(kind.extension()),
+            Some(kind.matcher_type().into()),
+        ),
+        None if utf8 => ("text/plain", None, None),
+        None => ("application/octet-stream", None, None),
+    };
+    let recognized = extension.is_some();
+
+    // Parameters such as `charset` do not take part in the comparison
+    let declared = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|essence| essence.trim().to_ascii_lowercase())
+        .filter(|essence| !essence.is_empty());
+    let mismatch = declared.as_ref().map(|declared| {
+        // Unrecognized UTF-8 is only known to be text of some kind
+        let compatible = !recognized && utf8 && is_text(declared);
+        declared != mime_type && !compatible
+    });
+
+    MimeInspection {
+        mime_type,
+        extension,
+        category,
+        utf8,
+        declared,
+        mismatch,
+    }
+}
+
+/// Whether data of the MIME type `essence` is text
+fn is_text(essence: &str) -> bool {
+    let (kind, subtype) = essence.split_once('/').unwrap_or((essence, ""));
+    kind == "text"
+        || (kind == "application"
+            && (matches!(subtype, "json" | "xml" | "javascript")
+                || subtype.ends_with("+json")
+                || subtype.ends_with("+xml")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn recognized() {
+        let inspection = mime(PNG, Some("image/png"));
+        assert_eq!(inspection.mime_type, "image/png");
+        assert_eq!(inspection.extension, Some("png"));
+        assert!(matches!(inspection.category, Some(MimeCategory::Image)));
+        assert!(!inspection.utf8);
+        assert_eq!(inspection.mismatch, Some(false));
+
+        let inspection = mime(PNG, Some("Image/JPEG; q=1"));
+        assert_eq!(inspection.declared.as_deref(), Some("image/jpeg"));
+        assert_eq!(inspection.mismatch, Some(true));
+    }
+
+    #[test]
+    fn unrecognized() {
+        let inspection = mime(b"{\"hello\": \"world\"}", None);
+        assert_eq!(inspection.mime_type, "text/plain");
+        assert_eq!(inspection.extension, None);
+        assert!(inspection.category.is_none());
+        assert!(inspection.utf8);
+        assert_eq!(inspection.declared, None);
+        assert_eq!(inspection.mismatch, None);
+
+        let inspection = mime(b"\xff\xfe\x00", Some("text/plain"));
+        assert_eq!(inspection.mime_type, "application/octet-stream");
+        assert_eq!(inspection.extension, None);
+        assert!(!inspection.utf8);
+        assert_eq!(inspection.mismatch, Some(true));
+    }
+
+    #[test]
+    fn textual_types_agree_with_utf8() {
+        for content_type in [
+            "application/json",
+            "application/json; charset=utf-8",
+            "application/problem+json",
+            "application/xml",
+            "text/csv",
+            "text/plain",
+        ] {
+            let inspection = mime(b"{\"hello\": \"world\"}", Some(content_type));
+            assert_eq!(inspection.mismatch, Some(false), "{content_type}");
+        }
+
+        for content_type in ["image/png", "application/octet-stream", "application/pdf"] {
+            let inspection = mime(b"hello", Some(content_type));
+            assert_eq!(inspection.mismatch, Some(true), "{content_type}");
+        }
+        // A recognized type is still compared as is
+        assert_eq!(mime(PNG, Some("text/plain")).mismatch, Some(true));
+    }
+}
@@ -1,2 +1,12 @@
 pub mod cli;
 pub mod data;
+pub mod fingerprint;
+pub mod h2c;
+pub mod inspect;
+pub mod listener;
+#[cfg(feature = "poem")]
+pub mod poem;
+pub mod rewind;
+pub mod sse;
+pub mod tls;
+pub mod websocket;
@@ -0,0 +1,121 @@
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::{DirBuilderExt, FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+};
+use std::{io, time::Duration};
+
+use tokio::signal;
+
+/// A socket bound by [`bind_unix`], removed when dropped so it is not left behind on shutdown
+#[cfg(unix)]
+#[must_use]
+#[derive(Debug)]
+pub struct SocketFile(PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Bind a Unix domain socket, replacing one left behind by a previous run
+///
+/// The socket is bound in a directory only its owner can enter, given its mode and then moved
+/// to `path`, so no one else can connect in between.
+#[cfg(unix)]
+pub fn bind_unix(path: &Path, mode: Option<u32>) -> io::Result<(UnixListener, SocketFile)> {
+    remove_stale_socket(path)?;
+
+    // Next to `path`, so the socket can be renamed into place
+    let name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", path.display()),
+        )
+    })?;
+    let mut private = path.to_owned();
+    private.set_file_name(format!(
+        ".{}.{}",
+        name.to_string_lossy(),
+        std::process::id()
+    ));
+    // Clear what a previous run which was killed while binding left behind, while creating the
+    // directory fails if anything else is still there
+    let _ = fs::remove_file(private.join("socket"));
+    let _ = fs::remove_dir(&private);
+    fs::DirBuilder::new().mode(0o700).create(&private)?;
+
+    let bound = private.join("socket");
+    let listener = UnixListener::bind(&bound).and_then(|listener| {
+        // Without a mode, the socket keeps the permissions binding gave it
+        if let Some(mode) = mode {
+            fs::set_permissions(&bound, fs::Permissions::from_mode(mode))?;
+        }
+        fs::rename(&bound, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_file(&bound);
+    let _ = fs::remove_dir(&private);
+
+    Ok((listener?, SocketFile(path.to_owned())))
+}
+
+/// Remove the socket at `path` if no server is listening on it anymore
+///
+/// Fails if something else is at `path`, so it is never removed by accident.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_socket() => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        Ok(_) if UnixStream::connect(path).is_ok() => Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("{} is in use by another server", path.display()),
+        )),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Wait for Ctrl+C or SIGTERM, so servers can return and the sockets they bound get removed
+#[cfg(unix)]
+pub async fn shutdown_signal() -> io::Result<()> {
+    let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())?;
+    tokio::select! {
+        ctrl_c = signal::ctrl_c() => ctrl_c,
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+/// Wait for Ctrl+C, so servers can return
+#[cfg(not(unix))]
+pub async fn shutdown_signal() -> io::Result<()> {
+    signal::ctrl_c().await
+}
+
+/// Wait after failing to accept a connection, before accepting the next one
+///
+/// Like `axum::serve`, errors of the connection itself are skipped at once, while others such
+/// as running out of file descriptors are logged and retried a second later.
+pub async fn backoff(e: io::Error) {
+    if matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    ) {
+        return;
+    }
+
+    log::error!("failed to accept a connection: {e}");
+    tokio::time::sleep(Duration::from_secs(1)).await;
+}
@@ -0,0 +1,174 @@
+use std::{convert::Infallible, io, sync::Arc};
+
+use ::poem::{
+    http::uri::Scheme,
+    web::{LocalAddr, RemoteAddr},
+    Addr, Endpoint, EndpointExt, IntoEndpoint, Request, Response,
+};
+use futures_util::{future, FutureExt};
+use hyper::{server::conn::Http, service::service_fn};
+use rustls::ServerConfig;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+};
+
+use crate::{
+    cli::ListenerConfig,
+    h2c, listener,
+    tls::{self, ClientCertificates},
+};
+
+type BoxEndpoint = Arc<dyn Endpoint<Output = Response>>;
+
+/// Serve `ep` on the listeners, over TLS on the TCP ones if `tls` is set
+///
+/// poem's own TLS listener always offers TLS 1.2 and 1.3 with h2 and http/1.1, so TLS
+/// connections are accepted by [`tls::accept`] with the configured rustls config instead.
+/// Connections are served the way poem's server does, with `Upgrade: h2c` on cleartext ones.
+pub async fn serve<E>(
+    listeners: Vec<ListenerConfig>,
+    tls: Option<ServerConfig>,
+    ep: E,
+) -> io::Result<()>
+where
+    E: IntoEndpoint,
+    E::Endpoint: 'static,
+{
+    let ep: BoxEndpoint = Arc::new(ep.into_endpoint().map_to_response());
+
+    let mut servers = Vec::new();
+    #[cfg(unix)]
+    let mut sockets = Vec::new();
+    for config in listeners {
+        servers.push(match config {
+            ListenerConfig::Tcp { ip, port } => {
+                let listener = TcpListener::bind((ip, port)).await?;
+                match &tls {
+                    Some(tls) => serve_tls(listener, tls.clone(), ep.clone()).boxed(),
+                    None => serve_tcp(listener, ep.clone()).boxed(),
+                }
+            }
+            #[cfg(unix)]
+            ListenerConfig::Unix { path, mode } => {
+                let (listener, socket) = listener::bind_unix(&path, mode)?;
+                sockets.push(socket);
+                listener.set_nonblocking(true)?;
+                serve_unix(UnixListener::from_std(listener)?, ep.clone()).boxed()
+            }
+        });
+    }
+
+    // The sockets are removed once the servers return
+    tokio::select! {
+        served = future::try_join_all(servers) => served.map(|_| ()),
+        signal = listener::shutdown_signal() => signal,
+    }
+}
+
+async fn serve_tcp(listener: TcpListener, ep: BoxEndpoint) -> io::Result<()> {
+    let local_addr = LocalAddr(listener.local_addr()?.into());
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        let remote_addr = RemoteAddr(remote_addr.into());
+        serve_plain(stream, ep.clone(), local_addr.clone(), remote_addr);
+    }
+}
+
+#[cfg(unix)]
+async fn serve_unix(listener: UnixListener, ep: BoxEndpoint) -> io::Result<()> {
+    let local_addr = LocalAddr(listener.local_addr()?.into());
+
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                listener::backoff(e).await;
+                continue;
+            }
+        };
+        let remote_addr = RemoteAddr(Addr::from(remote_addr));
+        serve_plain(stream, ep.clone(), local_addr.clone(), remote_addr);
+    }
+}
+
+/// Serve `ep` on a cleartext connection, switching it to HTTP/2 if a request asks for it
+fn serve_plain<S>(io: S, ep: BoxEndpoint, local_addr: LocalAddr, remote_addr: RemoteAddr)
+where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let service = service_fn(move |req: hyper::Request<hyper::Body>| {
+        let ep = ep.clone();
+        let local_addr = local_addr.clone();
+        let remote_addr = remote_addr.clone();
+        async move {
+            // The switched connection is served without the upgrade
+            let http2 = service_fn({
+                let ep = ep.clone();
+                let local_addr = local_addr.clone();
+                let remote_addr = remote_addr.clone();
+                move |req| {
+                    respond(
+                        ep.clone(),
+                        Request::from((req, local_addr.clone(), remote_addr.clone(), Scheme::HTTP)),
+                    )
+                }
+            });
+            match h2c::upgrade(req, http2).await {
+                Ok(res) => Ok(res),
+                Err(req) => {
+                    respond(
+                        ep,
+                        Request::from((req, local_addr, remote_addr, Scheme::HTTP)),
+                    )
+                    .await
+                }
+            }
+        }
+    });
+
+    tokio::spawn(Http::new().serve_connection(io, service).with_upgrades());
+}
+
+/// Serve `ep` on the TLS connections of a TCP listener, with the [`ClientCertificates`] of each
+/// connection in the request extensions
+async fn serve_tls(listener: TcpListener, config: ServerConfig, ep: BoxEndpoint) -> io::Result<()> {
+    let local_addr = LocalAddr(listener.local_addr()?.into());
+    let mut incoming = tls::accept(listener, config);
+
+    while let Some((stream, remote_addr)) = incoming.recv().await {
+        let ep = ep.clone();
+        let local_addr = local_addr.clone();
+        let remote_addr = RemoteAddr(remote_addr.into());
+        let certs = ClientCertificates::from_peer(stream.get_ref().1.peer_certificates());
+        let service = service_fn(move |mut req: hyper::Request<hyper::Body>| {
+            req.extensions_mut().insert(certs.clone());
+            let req = Request::from((req, local_addr.clone(), remote_addr.clone(), Scheme::HTTPS));
+            respond(ep.clone(), req)
+        });
+
+        tokio::spawn(
+            Http::new()
+                .serve_connection(stream, service)
+                .with_upgrades(),
+        );
+    }
+
+    Ok(())
+}
+
+async fn respond(
+    ep: BoxEndpoint,
+    req: Request,
+) -> Result<hyper::Response<hyper::Body>, Infallible> {
+    Ok(hyper::Response::from(ep.get_response(req).await))
+}
@@ -0,0 +1,126 @@
+use std::{io, time::Duration};
+
+use chrono::{SecondsFormat, Utc};
+use futures_util::{stream, Stream};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use tokio::time::{self, Instant};
+
+/// The maximum number of events of one stream
+pub const MAX_COUNT: u64 = 10_000;
+
+/// The events of a stream
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct SseOptions {
+    /// The number of events, 10 by default
+    pub count: Option<u64>,
+    /// Milliseconds between events, 1000 by default
+    pub interval: Option<u64>,
+    /// Milliseconds for the client to wait before reconnecting, sent before the first event
+    pub retry: Option<u64>,
+    /// Milliseconds between comment heartbeats
+    pub heartbeat: Option<u64>,
+    /// Drop the connection without ending the stream after sending this many events
+    pub drop_after: Option<u64>,
+}
+
+#[derive(Error, Debug)]
+pub enum SseError {
+    #[error("the count must not be larger than {0}")]
+    TooManyEvents(u64),
+    #[error("the heartbeat interval must be at least 1 millisecond")]
+    InvalidHeartbeat,
+    #[error("the Last-Event-ID header must be the id of an event")]
+    InvalidLastEventId,
+}
+
+struct State {
+    /// The id of the next event, counting from 1
+    next: u64,
+    count: u64,
+    interval: Duration,
+    retry: Option<u64>,
+    heartbeat: Option<Duration>,
+    drop_after: Option<u64>,
+    sent: u64,
+    next_event: Instant,
+    next_heartbeat: Option<Instant>,
+}
+
+/// Stream numbered events as `text/event-stream` chunks, resuming after `last_event_id`
+///
+/// Returns `None` if all events were sent before, which should be answered with
+/// `204 No Content` to stop the client from reconnecting. The stream fails when the connection
+/// is to be dropped.
+pub fn events(
+    options: SseOptions,
+    last_event_id: Option<&str>,
+) -> Result<Option<impl Stream<Item = io::Result<String>>>, SseError> {
+    let count = options.count.unwrap_or(10);
+    if count > MAX_COUNT {
+        return Err(SseError::TooManyEvents(MAX_COUNT));
+    }
+    if options.heartbeat == Some(0) {
+        return Err(SseError::InvalidHeartbeat);
+    }
+
+    let last = match last_event_id.map(str::trim) {
+        None | Some("") => 0,
+        Some(id) => id.parse().map_err(|_| SseError::InvalidLastEventId)?,
+    };
+    if last >= count {
+        return Ok(None);
+    }
+
+    let now = Instant::now();
+    let heartbeat = options.heartbeat.map(Duration::from_millis);
+    let state = State {
+        next: last + 1,
+        count,
+        interval: Duration::from_millis(options.interval.unwrap_or(1000)),
+        retry: options.retry,
+        heartbeat,
+        drop_after: options.drop_after,
+        sent: 0,
+        next_event: now,
+        next_heartbeat: heartbeat.map(|heartbeat| now + heartbeat),
+    };
+
+    Ok(Some(stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+
+        if let Some(retry) = state.retry.take() {
+            return Some((Ok(format!("retry: {retry}\n\n")), Some(state)));
+        }
+        if state.next > state.count {
+            return None;
+        }
+        if let (Some(at), Some(heartbeat)) = (state.next_heartbeat, state.heartbeat) {
+            if at < state.next_event {
+                time::sleep_until(at).await;
+                state.next_heartbeat = Some(at + heartbeat);
+                return Some((Ok(": heartbeat\n\n".to_string()), Some(state)));
+            }
+        }
+
+        time::sleep_until(state.next_event).await;
+        // Dropped in place of the next event, so the previous one has been flushed
+        if state.drop_after == Some(state.sent) {
+            let dropped = io::Error::new(io::ErrorKind::ConnectionAborted, "dropped by request");
+            return Some((Err(dropped), None));
+        }
+
+        let data = json!({
+            "id": state.next,
+            "count": state.count,
+            "time": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        });
+        let event = format!("id: {}\nevent: tick\ndata: {data}\n\n", state.next);
+        state.next += 1;
+        state.sent += 1;
+        state.next_event += state.interval;
+
+        Some((Ok(event), Some(state)))
+    })))
+}
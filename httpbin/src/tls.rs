@@ -0,0 +1,337 @@
+use std::{
+    fs, io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::DateTime;
+
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyUsagePurpose,
+};
+use rustls::{
+    crypto::ring,
+    pki_types::CertificateDer,
+    server::WebPkiClientVerifier,
+    version::{TLS12, TLS13},
+    RootCertStore, ServerConfig, SupportedProtocolVersion,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    time,
+};
+use tokio_rustls::TlsAcceptor;
+use x509_parser::{certificate::X509Certificate, extensions::GeneralName, prelude::FromDer};
+
+use crate::cli::{ClientAuth, TlsConfig, TlsVersion};
+
+/// The time a client has to finish the TLS handshake, so stalled clients do not hold connections
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("either cert and key or self_signed must be set")]
+    MissingCertificate,
+    #[error("failed to read {path}: {source}")]
+    Read { path: String, source: io::Error },
+    #[error("the certificate must be a PEM certificate chain")]
+    InvalidCertificate,
+    #[error("the key must be a PEM private key")]
+    InvalidKey,
+    #[error("client_ca must be set to ask for client certificates")]
+    MissingClientCa,
+    #[error("the client CA must be PEM certificates: {0}")]
+    InvalidClientCa(String),
+    #[error("the client certificate is invalid")]
+    InvalidClientCertificate,
+    #[error("failed to generate the certificate: {0}")]
+    Generate(#[from] rcgen::Error),
+    #[error("failed to configure TLS: {0}")]
+    Rustls(#[from] rustls::Error),
+}
+
+/// The certificate chain, private key and protocols to serve
+#[derive(Debug, Clone)]
+pub struct Tls {
+    pub cert_pem: String,
+    pub key_pem: String,
+    /// The CA which signed the certificate, if it was generated at startup
+    pub ca_pem: Option<String>,
+    pub min_version: TlsVersion,
+    pub alpn: Vec<String>,
+    pub http3: bool,
+    pub client_auth: ClientAuth,
+    /// The CA certificates to verify client certificates with
+    pub client_ca_pem: Option<String>,
+}
+
+/// The CA served at `/tls/ca.pem`, if the certificate was generated at startup
+#[derive(Debug, Clone, Default)]
+pub struct Ca(pub Option<String>);
+
+impl From<Option<&Tls>> for Ca {
+    fn from(tls: Option<&Tls>) -> Self {
+        Self(tls.and_then(|tls| tls.ca_pem.clone()))
+    }
+}
+
+impl Tls {
+    /// Read the configured certificate and key, or generate them if `self_signed` is set
+    pub fn load(config: &TlsConfig) -> Result<Self, TlsError> {
+        let (cert_pem, key_pem, ca_pem) = if config.self_signed {
+            generate(config.subject_alt_names.clone())?
+        } else {
+            match (&config.cert, &config.key) {
+                (Some(cert), Some(key)) => (read(cert)?, read(key)?, None),
+                _ => return Err(TlsError::MissingCertificate),
+            }
+        };
+
+        let client_ca_pem = match (config.client_auth, &config.client_ca) {
+            (ClientAuth::None, _) => None,
+            (_, Some(client_ca)) => Some(read(client_ca)?),
+            (_, None) => return Err(TlsError::MissingClientCa),
+        };
+
+        Ok(Self {
+            cert_pem,
+            key_pem,
+            ca_pem,
+            min_version: config.min_version,
+            alpn: config.alpn.clone(),
+            http3: config.http3,
+            client_auth: config.client_auth,
+            client_ca_pem,
+        })
+    }
+
+    /// A rustls server config with the minimum version and ALPN protocols
+    pub fn server_config(&self) -> Result<ServerConfig, TlsError> {
+        let versions = match self.min_version {
+            TlsVersion::Tls12 => &[&TLS13, &TLS12][..],
+            TlsVersion::Tls13 => &[&TLS13][..],
+        };
+        self.build_server_config(versions, &self.alpn)
+    }
+
+    /// A rustls server config for HTTP/3, which always runs over TLS 1.3
+    pub fn quic_server_config(&self) -> Result<ServerConfig, TlsError> {
+        self.build_server_config(&[&TLS13], &["h3".to_string()])
+    }
+
+    /// The `Alt-Svc` header advertising HTTP/3 on `port`, if it is enabled
+    pub fn alt_svc(&self, port: u16) -> Option<String> {
+        self.http3.then(|| format!("h3=\":{port}\"; ma=86400"))
+    }
+
+    fn build_server_config(
+        &self,
+        versions: &[&'static SupportedProtocolVersion],
+        alpn: &[String],
+    ) -> Result<ServerConfig, TlsError> {
+        let certs = rustls_pemfile::certs(&mut self.cert_pem.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| TlsError::InvalidCertificate)?;
+        if certs.is_empty() {
+            return Err(TlsError::InvalidCertificate);
+        }
+        let key = rustls_pemfile::private_key(&mut self.key_pem.as_bytes())
+            .ok()
+            .flatten()
+            .ok_or(TlsError::InvalidKey)?;
+
+        // The provider is explicit, as the implementations may enable more than one
+        let provider = Arc::new(ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(versions)?;
+        let builder = match (self.client_auth, &self.client_ca_pem) {
+            (ClientAuth::Request | ClientAuth::Require, Some(client_ca_pem)) => {
+                let mut roots = RootCertStore::empty();
+                for cert in rustls_pemfile::certs(&mut client_ca_pem.as_bytes()) {
+                    let cert = cert.map_err(|e| TlsError::InvalidClientCa(e.to_string()))?;
+                    roots.add(cert)?;
+                }
+                let verifier = WebPkiClientVerifier::builder_with_provider(roots.into(), provider);
+                let verifier = match self.client_auth {
+                    ClientAuth::Request => verifier.allow_unauthenticated(),
+                    _ => verifier,
+                };
+                builder.with_client_cert_verifier(
+                    verifier
+                        .build()
+                        .map_err(|e| TlsError::InvalidClientCa(e.to_string()))?,
+                )
+            }
+            _ => builder.with_no_client_auth(),
+        };
+        let mut server_config = builder.with_single_cert(certs, key)?;
+        server_config.alpn_protocols = alpn
+            .iter()
+            .map(|protocol| protocol.as_bytes().to_vec())
+            .collect();
+
+        Ok(server_config)
+    }
+}
+
+/// A TLS connection accepted by [`accept`]
+pub type TlsStream = tokio_rustls::server::TlsStream<TcpStream>;
+
+/// Accept TLS connections on `listener` in the background, with the address of each client
+///
+/// Each handshake runs in its own task within [`HANDSHAKE_TIMEOUT`], so slow clients do not hold
+/// up others, and connections are received as their handshakes finish. Accepting stops once the
+/// receiver is dropped.
+pub fn accept(
+    listener: TcpListener,
+    config: ServerConfig,
+) -> mpsc::Receiver<(TlsStream, SocketAddr)> {
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let (sender, receiver) = mpsc::channel(64);
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, remote_addr) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        crate::listener::backoff(e).await;
+                        continue;
+                    }
+                },
+                () = sender.closed() => return,
+            };
+            let acceptor = acceptor.clone();
+            let sender = sender.clone();
+
+            tokio::spawn(async move {
+                if let Ok(Ok(stream)) =
+                    time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await
+                {
+                    let _ = sender.send((stream, remote_addr)).await;
+                }
+            });
+        }
+    });
+
+    receiver
+}
+
+/// The DER certificate chain presented by a client, leaf first
+#[derive(Debug, Clone, Default)]
+pub struct ClientCertificates(pub Vec<Vec<u8>>);
+
+/// A certificate presented by a client
+#[derive(Serialize, Debug)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    /// The serial number in colon separated hex
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+    /// The SHA-256 fingerprint of the DER certificate in hex
+    pub sha256_fingerprint: String,
+}
+
+impl ClientCertificates {
+    /// The chain a client presented on a connection, empty if it presented none
+    pub fn from_peer(certs: Option<&[CertificateDer<'_>]>) -> Self {
+        Self(
+            certs
+                .unwrap_or_default()
+                .iter()
+                .map(|cert| cert.to_vec())
+                .collect(),
+        )
+    }
+
+    /// Describe the certificates of the chain
+    pub fn inspect(&self) -> Result<Vec<CertificateInfo>, TlsError> {
+        self.0.iter().map(|der| inspect(der)).collect()
+    }
+}
+
+fn inspect(der: &[u8]) -> Result<CertificateInfo, TlsError> {
+    let (_, cert) =
+        X509Certificate::from_der(der).map_err(|_| TlsError::InvalidClientCertificate)?;
+
+    let subject_alt_names = cert
+        .subject_alternative_name()
+        .map_err(|_| TlsError::InvalidClientCertificate)?
+        .map(|san| {
+            san.value
+                .general_names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::DNSName(name) => Some(format!("DNS:{name}")),
+                    GeneralName::RFC822Name(email) => Some(format!("email:{email}")),
+                    GeneralName::URI(uri) => Some(format!("URI:{uri}")),
+                    GeneralName::IPAddress(ip) => {
+                        let ip = match ip.len() {
+                            4 => IpAddr::from(Ipv4Addr::from(<[u8; 4]>::try_from(*ip).ok()?)),
+                            _ => IpAddr::from(Ipv6Addr::from(<[u8; 16]>::try_from(*ip).ok()?)),
+                        };
+                        Some(format!("IP:{ip}"))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let time = |time: i64| {
+        DateTime::from_timestamp(time, 0)
+            .map(|time| time.to_rfc3339())
+            .ok_or(TlsError::InvalidClientCertificate)
+    };
+
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        subject_alt_names,
+        serial: cert.raw_serial_as_string(),
+        not_before: time(cert.validity().not_before.timestamp())?,
+        not_after: time(cert.validity().not_after.timestamp())?,
+        sha256_fingerprint: hex::encode(Sha256::digest(der)),
+    })
+}
+
+/// Generate a certificate for `subject_alt_names` signed by a new CA
+fn generate(subject_alt_names: Vec<String>) -> Result<(String, String, Option<String>), TlsError> {
+    let mut ca_params = CertificateParams::new(Vec::new());
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "httpbin-rs CA");
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    let ca = Certificate::from_params(ca_params)?;
+
+    let mut params = CertificateParams::new(subject_alt_names);
+    params
+        .distinguished_name
+        .push(DnType::CommonName, "httpbin-rs");
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    let cert = Certificate::from_params(params)?;
+
+    Ok((
+        cert.serialize_pem_with_signer(&ca)?,
+        cert.serialize_private_key_pem(),
+        Some(ca.serialize_pem()?),
+    ))
+}
+
+fn read(path: &Path) -> Result<String, TlsError> {
+    fs::read_to_string(path).map_err(|source| TlsError::Read {
+        path: path.display().to_string(),
+        source,
+    })
+}
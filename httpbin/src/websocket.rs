@@ -0,0 +1,910 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::mpsc,
+    time::{self, Instant},
+};
+
+/// The maximum size of a message, after decompression
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// How long to wait for the client to answer a close frame before dropping the connection
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xa;
+
+const CLOSE_NORMAL: u16 = 1000;
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+const CLOSE_INVALID_DATA: u16 = 1007;
+const CLOSE_TOO_BIG: u16 = 1009;
+
+/// The behaviours of an echo connection
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct EchoOptions {
+    /// Milliseconds to delay every reply by
+    pub delay: Option<u64>,
+    /// Milliseconds between pings sent by the server
+    pub ping: Option<u64>,
+    /// Close the connection after echoing this many messages, right after the handshake if 0
+    pub close_after: Option<usize>,
+    /// The code to close with, 1000 by default
+    pub code: Option<u16>,
+    /// The reason to close with
+    pub reason: Option<String>,
+    /// Drop the TCP connection in the middle of the reply after echoing this many messages
+    pub drop_after: Option<usize>,
+}
+
+#[derive(Error, Debug)]
+pub enum WebSocketError {
+    #[error("the Upgrade header must be websocket")]
+    NotWebSocket,
+    #[error("the Sec-WebSocket-Key header is missing or invalid")]
+    InvalidKey,
+    #[error("the Sec-WebSocket-Version header must be 13")]
+    UnsupportedVersion,
+    #[error("the ping interval must be at least 1 millisecond")]
+    InvalidPing,
+    #[error("the close code {0} cannot be sent")]
+    InvalidCloseCode(u16),
+    #[error("the close reason must not be longer than 123 bytes")]
+    ReasonTooLong,
+}
+
+/// A negotiated WebSocket connection, ready to be upgraded
+#[derive(Debug)]
+pub struct Handshake {
+    /// The `Sec-WebSocket-Accept` header
+    pub accept: String,
+    /// The `Sec-WebSocket-Protocol` header, the first subprotocol offered by the client
+    pub protocol: Option<String>,
+    /// The `Sec-WebSocket-Extensions` header
+    pub extensions: Option<String>,
+    deflate: Option<DeflateParams>,
+    options: EchoOptions,
+}
+
+impl Handshake {
+    /// The headers of the `101 Switching Protocols` response
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            ("Upgrade", "websocket".to_string()),
+            ("Connection", "Upgrade".to_string()),
+            ("Sec-WebSocket-Accept", self.accept.clone()),
+        ];
+        if let Some(protocol) = &self.protocol {
+            headers.push(("Sec-WebSocket-Protocol", protocol.clone()));
+        }
+        if let Some(extensions) = &self.extensions {
+            headers.push(("Sec-WebSocket-Extensions", extensions.clone()));
+        }
+
+        headers
+    }
+}
+
+/// The accepted parameters of a permessage-deflate offer
+#[derive(Debug, Clone, Copy, Default)]
+struct DeflateParams {
+    server_no_context_takeover: bool,
+    client_no_context_takeover: bool,
+}
+
+/// Validate the handshake of a client and negotiate a subprotocol and permessage-deflate
+///
+/// `protocols` and `extensions` are the comma-separated values of the `Sec-WebSocket-Protocol`
+/// and `Sec-WebSocket-Extensions` headers, empty if absent.
+pub fn handshake(
+    upgrade: Option<&str>,
+    key: Option<&str>,
+    version: Option<&str>,
+    protocols: &str,
+    extensions: &str,
+    options: EchoOptions,
+) -> Result<Handshake, WebSocketError> {
+    if !upgrade.is_some_and(|upgrade| upgrade.trim().eq_ignore_ascii_case("websocket")) {
+        return Err(WebSocketError::NotWebSocket);
+    }
+    let key = key.map(str::trim).ok_or(WebSocketError::InvalidKey)?;
+    match STANDARD.decode(key) {
+        Ok(nonce) if nonce.len() == 16 => {}
+        _ => return Err(WebSocketError::InvalidKey),
+    }
+    if version.map(str::trim) != Some("13") {
+        return Err(WebSocketError::UnsupportedVersion);
+    }
+
+    if options.ping == Some(0) {
+        return Err(WebSocketError::InvalidPing);
+    }
+    if let Some(code) = options.code {
+        if !is_valid_close_code(code) {
+            return Err(WebSocketError::InvalidCloseCode(code));
+        }
+    }
+    if options
+        .reason
+        .as_ref()
+        .is_some_and(|reason| reason.len() > 123)
+    {
+        return Err(WebSocketError::ReasonTooLong);
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(ACCEPT_GUID.as_bytes());
+    let accept = STANDARD.encode(hasher.finalize());
+
+    let protocol = protocols
+        .split(',')
+        .map(str::trim)
+        .find(|protocol| !protocol.is_empty())
+        .map(str::to_string);
+
+    let deflate = extensions.split(',').find_map(deflate_offer);
+    let extensions = deflate.map(|params| {
+        let mut response = String::from("permessage-deflate");
+        if params.server_no_context_takeover {
+            response.push_str("; server_no_context_takeover");
+        }
+        if params.client_no_context_takeover {
+            response.push_str("; client_no_context_takeover");
+        }
+        response
+    });
+
+    Ok(Handshake {
+        accept,
+        protocol,
+        extensions,
+        deflate,
+        options,
+    })
+}
+
+/// Accept a permessage-deflate offer, unless it asks for a smaller server window than the
+/// 32 KiB one the compressor uses or has unknown parameters
+fn deflate_offer(offer: &str) -> Option<DeflateParams> {
+    let mut params = offer.split(';').map(str::trim);
+    if params.next()? != "permessage-deflate" {
+        return None;
+    }
+
+    let mut accepted = DeflateParams::default();
+    for param in params {
+        let (name, value) = match param.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+            None => (param, None),
+        };
+        match (name, value) {
+            ("server_no_context_takeover", None) => accepted.server_no_context_takeover = true,
+            ("client_no_context_takeover", None) => accepted.client_no_context_takeover = true,
+            ("server_max_window_bits", Some("15")) => {}
+            // Any window of the client can be inflated
+            ("client_max_window_bits", None) => {}
+            ("client_max_window_bits", Some(bits)) if matches!(bits.parse::<u8>(), Ok(8..=15)) => {}
+            _ => return None,
+        }
+    }
+
+    Some(accepted)
+}
+
+/// Codes that may be sent in a close frame, as 1005, 1006 and 1015 are reserved for APIs
+fn is_valid_close_code(code: u16) -> bool {
+    matches!(code, 1000..=1003 | 1007..=1014 | 3000..=4999)
+}
+
+enum Incoming {
+    Message {
+        opcode: u8,
+        data: Vec<u8>,
+    },
+    Ping(Vec<u8>),
+    /// A close frame from the client or a protocol violation, with the code to close with
+    Close(u16),
+}
+
+/// Echo text and binary messages over an upgraded connection until either side closes it
+pub async fn echo<S>(io: S, handshake: Handshake) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (reader, writer) = tokio::io::split(io);
+    let (tx, rx) = mpsc::channel(16);
+
+    let inflater = handshake.deflate.map(Inflater::new);
+    let deflater = handshake.deflate.map(Deflater::new);
+
+    let reader = async move {
+        read_messages(reader, inflater, &tx).await;
+        // Let the writer drain the remaining messages, but keep the read half alive until the
+        // writer is done with the connection
+        drop(tx);
+        std::future::pending::<()>().await
+    };
+
+    tokio::select! {
+        _ = reader => Ok(()),
+        result = write_messages(writer, deflater, rx, handshake.options) => result,
+    }
+}
+
+async fn read_messages<R: AsyncRead + Unpin>(
+    mut reader: R,
+    mut inflater: Option<Inflater>,
+    tx: &mpsc::Sender<Incoming>,
+) {
+    // The opcode, whether it is compressed, and the data of a fragmented message
+    let mut message: Option<(u8, bool, Vec<u8>)> = None;
+
+    loop {
+        let incoming = match read_frame(&mut reader, inflater.is_some()).await {
+            // The client went away
+            Err(None) => return,
+            Err(Some(code)) => Incoming::Close(code),
+            Ok(frame) => match frame.opcode {
+                OPCODE_PING => Incoming::Ping(frame.payload),
+                OPCODE_PONG => continue,
+                OPCODE_CLOSE => Incoming::Close(close_code(&frame.payload)),
+                _ => match assemble(&mut message, frame, inflater.as_mut()) {
+                    Ok(Some(incoming)) => incoming,
+                    Ok(None) => continue,
+                    Err(code) => Incoming::Close(code),
+                },
+            },
+        };
+
+        let last = matches!(incoming, Incoming::Close(_));
+        if tx.send(incoming).await.is_err() || last {
+            return;
+        }
+    }
+}
+
+/// Add a data frame to the fragmented message, returning the message once it is complete or the
+/// code to close with
+fn assemble(
+    message: &mut Option<(u8, bool, Vec<u8>)>,
+    frame: Frame,
+    inflater: Option<&mut Inflater>,
+) -> Result<Option<Incoming>, u16> {
+    let (opcode, compressed, mut data) = match (message.take(), frame.opcode) {
+        (None, OPCODE_TEXT | OPCODE_BINARY) => (frame.opcode, frame.rsv1, frame.payload),
+        (Some((opcode, compressed, mut data)), OPCODE_CONTINUATION) if !frame.rsv1 => {
+            data.extend_from_slice(&frame.payload);
+            (opcode, compressed, data)
+        }
+        _ => return Err(CLOSE_PROTOCOL_ERROR),
+    };
+    if data.len() > MAX_MESSAGE_SIZE {
+        return Err(CLOSE_TOO_BIG);
+    }
+    if !frame.fin {
+        *message = Some((opcode, compressed, data));
+        return Ok(None);
+    }
+
+    if compressed {
+        // `rsv1` is only accepted once permessage-deflate is negotiated
+        data = inflater.unwrap().inflate(&data)?;
+    }
+    if opcode == OPCODE_TEXT && std::str::from_utf8(&data).is_err() {
+        return Err(CLOSE_INVALID_DATA);
+    }
+
+    Ok(Some(Incoming::Message { opcode, data }))
+}
+
+struct Frame {
+    fin: bool,
+    rsv1: bool,
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+/// Read a frame from the client, failing with `None` if the connection is gone or with the code
+/// to close with otherwise
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    deflate: bool,
+) -> Result<Frame, Option<u16>> {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head).await.map_err(|_| None)?;
+
+    let fin = head[0] & 0x80 != 0;
+    let rsv1 = head[0] & 0x40 != 0;
+    let opcode = head[0] & 0x0f;
+    let masked = head[1] & 0x80 != 0;
+    let len = match head[1] & 0x7f {
+        126 => reader.read_u16().await.map_err(|_| None)? as u64,
+        127 => reader.read_u64().await.map_err(|_| None)?,
+        len => len as u64,
+    };
+
+    let control = opcode & 0x8 != 0;
+    let valid = masked
+        && head[0] & 0x30 == 0
+        && matches!(
+            opcode,
+            OPCODE_CONTINUATION
+                | OPCODE_TEXT
+                | OPCODE_BINARY
+                | OPCODE_CLOSE
+                | OPCODE_PING
+                | OPCODE_PONG
+        )
+        && (!rsv1 || (deflate && !control))
+        && (!control || (fin && len <= 125));
+    if !valid {
+        return Err(Some(CLOSE_PROTOCOL_ERROR));
+    }
+    if len > MAX_MESSAGE_SIZE as u64 {
+        return Err(Some(CLOSE_TOO_BIG));
+    }
+
+    let mut mask = [0; 4];
+    reader.read_exact(&mut mask).await.map_err(|_| None)?;
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload).await.map_err(|_| None)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok(Frame {
+        fin,
+        rsv1,
+        opcode,
+        payload,
+    })
+}
+
+/// The code to answer a close frame with, which echoes the code of the client
+fn close_code(payload: &[u8]) -> u16 {
+    match payload {
+        [] => CLOSE_NORMAL,
+        [_] => CLOSE_PROTOCOL_ERROR,
+        [high, low, reason @ ..] => {
+            let code = u16::from_be_bytes([*high, *low]);
+            if !is_valid_close_code(code) {
+                CLOSE_PROTOCOL_ERROR
+            } else if std::str::from_utf8(reason).is_err() {
+                CLOSE_INVALID_DATA
+            } else {
+                code
+            }
+        }
+    }
+}
+
+async fn write_messages<W: AsyncWrite + Unpin>(
+    mut writer: W,
+    mut deflater: Option<Deflater>,
+    mut rx: mpsc::Receiver<Incoming>,
+    options: EchoOptions,
+) -> io::Result<()> {
+    let delay = Duration::from_millis(options.delay.unwrap_or(0));
+    let close = close_payload(
+        options.code.unwrap_or(CLOSE_NORMAL),
+        options.reason.as_deref(),
+    );
+
+    // Replies waiting for their delay, in the order the messages arrived
+    let mut pending = VecDeque::new();
+    let mut echoed = 0;
+    // When to give up on the client answering the close frame of the server
+    let mut closing = None;
+    let mut ping = options.ping.map(|ms| {
+        time::interval_at(
+            Instant::now() + Duration::from_millis(ms),
+            Duration::from_millis(ms),
+        )
+    });
+    let mut pings = 0u64;
+
+    if options.close_after == Some(0) {
+        write_frame(&mut writer, OPCODE_CLOSE, false, &close).await?;
+        closing = Some(Instant::now() + CLOSE_TIMEOUT);
+    }
+
+    loop {
+        // Neither replies nor pings are sent after a close frame
+        let reply_at = pending
+            .front()
+            .map(|(at, _, _)| *at)
+            .filter(|_| closing.is_none());
+        let pinging = ping.is_some() && closing.is_none();
+
+        tokio::select! {
+            incoming = rx.recv() => match incoming {
+                None => return writer.shutdown().await,
+                Some(Incoming::Message { opcode, data }) => {
+                    if closing.is_none() {
+                        pending.push_back((Instant::now() + delay, opcode, data));
+                    }
+                }
+                Some(Incoming::Ping(payload)) => {
+                    write_frame(&mut writer, OPCODE_PONG, false, &payload).await?;
+                }
+                Some(Incoming::Close(code)) => {
+                    if closing.is_none() {
+                        write_frame(&mut writer, OPCODE_CLOSE, false, &code.to_be_bytes()).await?;
+                    }
+                    return writer.shutdown().await;
+                }
+            },
+            _ = time::sleep_until(reply_at.unwrap_or_else(Instant::now)), if reply_at.is_some() => {
+                // The front is the reply the sleep waited for
+                let (_, opcode, data) = pending.pop_front().unwrap();
+                let (data, compressed) = match deflater.as_mut() {
+                    Some(deflater) => (deflater.deflate(&data)?, true),
+                    None => (data, false),
+                };
+
+                if options.drop_after == Some(echoed) {
+                    let frame = frame(opcode, compressed, &data);
+                    writer.write_all(&frame[..frame.len() / 2]).await?;
+                    writer.flush().await?;
+                    // Dropping the connection without a close frame or the rest of the frame
+                    return Ok(());
+                }
+
+                write_frame(&mut writer, opcode, compressed, &data).await?;
+                echoed += 1;
+
+                if options.close_after == Some(echoed) {
+                    write_frame(&mut writer, OPCODE_CLOSE, false, &close).await?;
+                    closing = Some(Instant::now() + CLOSE_TIMEOUT);
+                }
+            }
+            _ = async { ping.as_mut().unwrap().tick().await }, if pinging => {
+                pings += 1;
+                write_frame(&mut writer, OPCODE_PING, false, pings.to_string().as_bytes()).await?;
+            }
+            _ = time::sleep_until(closing.unwrap_or_else(Instant::now)), if closing.is_some() => {
+                return writer.shutdown().await;
+            }
+        }
+    }
+}
+
+fn close_payload(code: u16, reason: Option<&str>) -> Vec<u8> {
+    let mut payload = code.to_be_bytes().to_vec();
+    payload.extend_from_slice(reason.unwrap_or_default().as_bytes());
+    payload
+}
+
+/// A frame from the server, which is never masked or fragmented
+fn frame(opcode: u8, compressed: bool, payload: &[u8]) -> Vec<u8> {
+    let rsv1 = if compressed { 0x40 } else { 0 };
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | rsv1 | opcode);
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xffff => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    opcode: u8,
+    compressed: bool,
+    payload: &[u8],
+) -> io::Result<()> {
+    writer
+        .write_all(&frame(opcode, compressed, payload))
+        .await?;
+    writer.flush().await
+}
+
+/// The tail of a sync flush, which is stripped from and appended to every compressed message
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+struct Deflater {
+    encoder: flate2::write::DeflateEncoder<Vec<u8>>,
+    no_context_takeover: bool,
+}
+
+impl Deflater {
+    fn new(params: DeflateParams) -> Self {
+        Deflater {
+            encoder: flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default()),
+            no_context_takeover: params.server_no_context_takeover,
+        }
+    }
+
+    fn deflate(&mut self, data: &[u8]) -> io::Result<Vec<u8>> {
+        if self.no_context_takeover {
+            self.encoder =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        }
+
+        self.encoder.write_all(data)?;
+        // Flushing the encoder is a sync flush
+        self.encoder.flush()?;
+        let mut compressed = std::mem::take(self.encoder.get_mut());
+        if compressed.ends_with(&DEFLATE_TAIL) {
+            compressed.truncate(compressed.len() - DEFLATE_TAIL.len());
+        }
+
+        Ok(compressed)
+    }
+}
+
+struct Inflater {
+    decoder: flate2::write::DeflateDecoder<LimitedBuf>,
+    no_context_takeover: bool,
+}
+
+impl Inflater {
+    fn new(params: DeflateParams) -> Self {
+        Inflater {
+            decoder: flate2::write::DeflateDecoder::new(LimitedBuf::default()),
+            no_context_takeover: params.client_no_context_takeover,
+        }
+    }
+
+    /// Inflate a message, failing with the code to close with
+    fn inflate(&mut self, data: &[u8]) -> Result<Vec<u8>, u16> {
+        if self.no_context_takeover {
+            self.decoder = flate2::write::DeflateDecoder::new(LimitedBuf::default());
+        }
+
+        let result = self
+            .decoder
+            .write_all(data)
+            .and_then(|_| self.decoder.write_all(&DEFLATE_TAIL))
+            .and_then(|_| self.decoder.flush());
+        let buf = std::mem::take(self.decoder.get_mut());
+
+        match result {
+            Ok(()) => Ok(buf.data),
+            Err(_) if buf.overflowed => Err(CLOSE_TOO_BIG),
+            Err(_) => Err(CLOSE_INVALID_DATA),
+        }
+    }
+}
+
+/// A buffer which refuses to grow beyond [MAX_MESSAGE_SIZE], to stop compression bombs
+#[derive(Default)]
+struct LimitedBuf {
+    data: Vec<u8>,
+    overflowed: bool,
+}
+
+impl Write for LimitedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.data.len() + buf.len() > MAX_MESSAGE_SIZE {
+            self.overflowed = true;
+            return Err(io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                "the message is too large",
+            ));
+        }
+
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The masked "Hello" text frame of RFC 6455, section 5.7
+    const MASKED_HELLO: [u8; 11] = [
+        0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+    ];
+
+    /// "Hello" compressed twice with context takeover, from RFC 7692, section 7.2.3.2
+    const DEFLATED_HELLO: [u8; 7] = [0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00];
+    const DEFLATED_HELLO_AGAIN: [u8; 5] = [0xf2, 0x00, 0x11, 0x00, 0x00];
+
+    async fn read(bytes: &[u8], deflate: bool) -> Result<Frame, Option<u16>> {
+        let mut reader = bytes;
+        read_frame(&mut reader, deflate).await
+    }
+
+    fn data(opcode: u8, fin: bool, payload: &[u8]) -> Frame {
+        Frame {
+            fin,
+            rsv1: false,
+            opcode,
+            payload: payload.to_vec(),
+        }
+    }
+
+    #[test]
+    fn accept_key() {
+        let handshake = handshake(
+            Some("websocket"),
+            Some("dGhlIHNhbXBsZSBub25jZQ=="),
+            Some("13"),
+            "chat, superchat",
+            "",
+            EchoOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(handshake.accept, "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        assert_eq!(handshake.protocol.as_deref(), Some("chat"));
+        assert!(handshake.extensions.is_none());
+    }
+
+    #[test]
+    fn invalid_handshake() {
+        let key = Some("dGhlIHNhbXBsZSBub25jZQ==");
+        let shake = |upgrade, key, version| {
+            handshake(upgrade, key, version, "", "", EchoOptions::default()).err()
+        };
+        assert!(matches!(
+            shake(None, key, Some("13")),
+            Some(WebSocketError::NotWebSocket)
+        ));
+        assert!(matches!(
+            shake(Some("websocket"), Some("dGhlIHNhbXBsZQ=="), Some("13")),
+            Some(WebSocketError::InvalidKey)
+        ));
+        assert!(matches!(
+            shake(Some("websocket"), Some("not base64!"), Some("13")),
+            Some(WebSocketError::InvalidKey)
+        ));
+        assert!(matches!(
+            shake(Some("websocket"), key, Some("8")),
+            Some(WebSocketError::UnsupportedVersion)
+        ));
+    }
+
+    #[test]
+    fn deflate_offers() {
+        let params = deflate_offer("permessage-deflate; client_max_window_bits").unwrap();
+        assert!(!params.server_no_context_takeover && !params.client_no_context_takeover);
+
+        let params = deflate_offer(
+            "permessage-deflate; server_no_context_takeover; client_no_context_takeover; \
+             server_max_window_bits=15; client_max_window_bits=\"10\"",
+        )
+        .unwrap();
+        assert!(params.server_no_context_takeover && params.client_no_context_takeover);
+
+        assert!(deflate_offer("x-webkit-deflate-frame").is_none());
+        assert!(deflate_offer("permessage-deflate; server_max_window_bits=10").is_none());
+        assert!(deflate_offer("permessage-deflate; client_max_window_bits=16").is_none());
+        assert!(deflate_offer("permessage-deflate; client_no_context_takeover=1").is_none());
+        assert!(deflate_offer("permessage-deflate; unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn masked_frame() {
+        let frame = read(&MASKED_HELLO, false).await.unwrap();
+        assert!(frame.fin && !frame.rsv1);
+        assert_eq!(frame.opcode, OPCODE_TEXT);
+        assert_eq!(frame.payload, b"Hello");
+    }
+
+    #[tokio::test]
+    async fn extended_lengths() {
+        let mask = [0x01, 0x02, 0x03, 0x04];
+        let payload = vec![0x55; 0x10000];
+        let masked = payload
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4]);
+
+        let mut bytes = vec![0x82, 0xfe, 0x01, 0x00];
+        bytes.extend_from_slice(&mask);
+        bytes.extend(masked.clone().take(0x100));
+        assert_eq!(
+            read(&bytes, false).await.unwrap().payload,
+            &payload[..0x100]
+        );
+
+        let mut bytes = vec![0x82, 0xff, 0, 0, 0, 0, 0, 0x01, 0, 0];
+        bytes.extend_from_slice(&mask);
+        bytes.extend(masked);
+        assert_eq!(read(&bytes, false).await.unwrap().payload, payload);
+    }
+
+    #[tokio::test]
+    async fn truncated_frames() {
+        for len in 0..MASKED_HELLO.len() {
+            assert_eq!(read(&MASKED_HELLO[..len], false).await.err(), Some(None));
+        }
+        // Cut in the middle of the extended lengths
+        assert_eq!(read(&[0x82, 0xfe, 0x01], false).await.err(), Some(None));
+        assert_eq!(read(&[0x82, 0xff, 0, 0, 0], false).await.err(), Some(None));
+    }
+
+    #[tokio::test]
+    async fn malformed_frames() {
+        let malformed: &[&[u8]] = &[
+            // Unmasked, as in the unmasked example of RFC 6455
+            &[0x81, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f],
+            // RSV2 and RSV3
+            &[0xa1, 0x80, 0, 0, 0, 0],
+            &[0x91, 0x80, 0, 0, 0, 0],
+            // Reserved data and control opcodes
+            &[0x83, 0x80, 0, 0, 0, 0],
+            &[0x8b, 0x80, 0, 0, 0, 0],
+            // A fragmented ping
+            &[0x09, 0x80, 0, 0, 0, 0],
+            // A ping longer than 125 bytes
+            &[0x89, 0xfe, 0x00, 0x7e],
+        ];
+        for bytes in malformed {
+            assert_eq!(
+                read(bytes, false).await.err(),
+                Some(Some(CLOSE_PROTOCOL_ERROR))
+            );
+        }
+
+        // RSV1 before permessage-deflate is negotiated, and on a control frame after
+        let compressed = [0xc1, 0x80, 0, 0, 0, 0];
+        assert_eq!(
+            read(&compressed, false).await.err(),
+            Some(Some(CLOSE_PROTOCOL_ERROR))
+        );
+        assert!(read(&compressed, true).await.unwrap().rsv1);
+        assert_eq!(
+            read(&[0xc9, 0x80, 0, 0, 0, 0], true).await.err(),
+            Some(Some(CLOSE_PROTOCOL_ERROR))
+        );
+
+        // Refused before the payload is read
+        assert_eq!(
+            read(&[0x82, 0xff, 0, 0, 0, 0, 0x01, 0, 0, 0x01], false)
+                .await
+                .err(),
+            Some(Some(CLOSE_TOO_BIG))
+        );
+    }
+
+    #[test]
+    fn fragmented_message() {
+        // The fragmented "Hello" of RFC 6455, section 5.7
+        let mut message = None;
+        assert!(matches!(
+            assemble(&mut message, data(OPCODE_TEXT, false, b"Hel"), None),
+            Ok(None)
+        ));
+        let Ok(Some(Incoming::Message { opcode, data })) =
+            assemble(&mut message, data(OPCODE_CONTINUATION, true, b"lo"), None)
+        else {
+            panic!("the message is not complete");
+        };
+        assert_eq!(opcode, OPCODE_TEXT);
+        assert_eq!(data, b"Hello");
+        assert!(message.is_none());
+    }
+
+    #[test]
+    fn malformed_messages() {
+        let mut message = None;
+        assert!(matches!(
+            assemble(&mut message, data(OPCODE_CONTINUATION, true, b"lo"), None),
+            Err(CLOSE_PROTOCOL_ERROR)
+        ));
+
+        assert!(matches!(
+            assemble(&mut message, data(OPCODE_BINARY, false, b"Hel"), None),
+            Ok(None)
+        ));
+        assert!(matches!(
+            assemble(&mut message, data(OPCODE_TEXT, true, b"lo"), None),
+            Err(CLOSE_PROTOCOL_ERROR)
+        ));
+
+        let mut message = None;
+        assert!(matches!(
+            assemble(&mut message, data(OPCODE_TEXT, true, &[0xc3, 0x28]), None),
+            Err(CLOSE_INVALID_DATA)
+        ));
+    }
+
+    #[test]
+    fn close_codes() {
+        assert_eq!(close_code(&[]), CLOSE_NORMAL);
+        assert_eq!(close_code(&[0x03]), CLOSE_PROTOCOL_ERROR);
+        assert_eq!(close_code(&[0x0b, 0xb8, b'b', b'y', b'e']), 3000);
+        // 1005 is reserved and 1016 is unassigned
+        assert_eq!(close_code(&[0x03, 0xed]), CLOSE_PROTOCOL_ERROR);
+        assert_eq!(close_code(&[0x03, 0xf8]), CLOSE_PROTOCOL_ERROR);
+        assert_eq!(close_code(&[0x03, 0xe8, 0xc3, 0x28]), CLOSE_INVALID_DATA);
+    }
+
+    #[test]
+    fn server_frames() {
+        assert_eq!(
+            frame(OPCODE_TEXT, false, b"Hello"),
+            [0x81, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f]
+        );
+        assert_eq!(
+            frame(OPCODE_BINARY, false, &[0; 0x100])[..4],
+            [0x82, 0x7e, 0x01, 0x00]
+        );
+        assert_eq!(
+            frame(OPCODE_BINARY, false, &[0; 0x10000])[..10],
+            [0x82, 0x7f, 0, 0, 0, 0, 0, 0x01, 0, 0]
+        );
+        assert_eq!(frame(OPCODE_TEXT, true, &DEFLATED_HELLO)[..2], [0xc1, 0x07]);
+    }
+
+    #[test]
+    fn inflate_with_context_takeover() {
+        let mut inflater = Inflater::new(DeflateParams::default());
+        assert_eq!(inflater.inflate(&DEFLATED_HELLO).unwrap(), b"Hello");
+        assert_eq!(inflater.inflate(&DEFLATED_HELLO_AGAIN).unwrap(), b"Hello");
+
+        // The second message refers back to the first one, which is forgotten without context
+        // takeover
+        let params = DeflateParams {
+            client_no_context_takeover: true,
+            ..Default::default()
+        };
+        let mut inflater = Inflater::new(params);
+        assert_eq!(inflater.inflate(&DEFLATED_HELLO).unwrap(), b"Hello");
+        assert_ne!(
+            inflater.inflate(&DEFLATED_HELLO_AGAIN),
+            Ok(b"Hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn deflate_round_trip() {
+        for params in [
+            DeflateParams::default(),
+            DeflateParams {
+                server_no_context_takeover: true,
+                client_no_context_takeover: true,
+            },
+        ] {
+            let mut deflater = Deflater::new(params);
+            let mut inflater = Inflater::new(params);
+            for message in ["Hello", "Hello", "", "Hello, world"] {
+                let compressed = deflater.deflate(message.as_bytes()).unwrap();
+                assert!(!compressed.ends_with(&DEFLATE_TAIL));
+                assert_eq!(inflater.inflate(&compressed).unwrap(), message.as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_compressed_messages() {
+        let mut inflater = Inflater::new(DeflateParams::default());
+        // A block with the reserved type 3
+        assert_eq!(inflater.inflate(&[0xff]), Err(CLOSE_INVALID_DATA));
+
+        let mut deflater = Deflater::new(DeflateParams::default());
+        let bomb = deflater.deflate(&vec![0; MAX_MESSAGE_SIZE + 1]).unwrap();
+        let mut inflater = Inflater::new(DeflateParams::default());
+        assert_eq!(inflater.inflate(&bomb), Err(CLOSE_TOO_BIG));
+    }
+}